@@ -3,8 +3,8 @@ use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 use builder::non_permissioned::{build_instance_state, BuilderConfig};
 use clap::Parser;
 use espresso_types::{
-    eth_signature_key::EthKeyPair, parse_duration, FeeVersion, MarketplaceVersion,
-    SequencerVersions, V0_0,
+    eth_signature_key::EthKeyPair, parse_duration, FeeMarketVersion, FeeVersion,
+    MarketplaceVersion, SequencerVersions, V0_0,
 };
 use futures::future::pending;
 use hotshot::traits::ValidatedState;
@@ -118,7 +118,13 @@ async fn main() -> anyhow::Result<()> {
         (FeeVersion::VERSION, MarketplaceVersion::VERSION) => {
             run::<SequencerVersions<FeeVersion, MarketplaceVersion>>(genesis, opt).await
         }
+        (FeeVersion::VERSION, FeeMarketVersion::VERSION) => {
+            run::<SequencerVersions<FeeVersion, FeeMarketVersion>>(genesis, opt).await
+        }
         (FeeVersion::VERSION, _) => run::<SequencerVersions<FeeVersion, V0_0>>(genesis, opt).await,
+        (FeeMarketVersion::VERSION, _) => {
+            run::<SequencerVersions<FeeMarketVersion, V0_0>>(genesis, opt).await
+        }
         (MarketplaceVersion::VERSION, _) => {
             run::<SequencerVersions<MarketplaceVersion, V0_0>>(genesis, opt).await
         }
@@ -142,12 +148,12 @@ async fn run<V: Versions>(
 
     let builder_server_url: Url = format!("http://0.0.0.0:{}", opt.port).parse().unwrap();
 
+    let base_fee = genesis.next_base_fee(&opt.state_peers).await;
     let instance_state =
         build_instance_state::<V>(genesis.chain_config, l1_params, opt.state_peers)
             .await
             .unwrap();
 
-    let base_fee = genesis.max_base_fee();
     tracing::info!(?base_fee, "base_fee");
 
     let validated_state = ValidatedState::genesis(&instance_state).0;
@@ -4,7 +4,7 @@ use anyhow::Context;
 use async_broadcast::broadcast;
 use async_lock::RwLock;
 use espresso_types::{
-    eth_signature_key::EthKeyPair, v0_99::ChainConfig, FeeAmount, NodeState, Payload, SeqTypes,
+    eth_signature_key::EthKeyPair, v0_4::ChainConfig, FeeAmount, NodeState, Payload, SeqTypes,
     ValidatedState,
 };
 use hotshot::traits::BlockPayload;
@@ -154,7 +154,11 @@ impl BuilderConfig {
             VecDeque::new() /* tx_queue */,
             global_state_clone,
             maximize_txns_count_timeout_duration,
-            base_fee
+            // The builder pays a flat rate per byte, so pay the highest namespace rate to always
+            // cover the block fee.
+            instance_state
+                .chain_config
+                .max_fee_per_byte(base_fee)
                 .as_u64()
                 .context("the base fee exceeds the maximum amount that a builder can pay (defined by u64::MAX)")?,
             Arc::new(instance_state),
@@ -1,48 +1,290 @@
-use anyhow::Context;
-use espresso_types::{FeeAccount, FeeAmount, FeeMerkleTree, Header};
+use std::{cmp::min, future::Future};
+
+use anyhow::{ensure, Context};
+use committable::{Commitment, Committable};
+use espresso_types::{
+    v0_4::ChainConfig, v0_99::TransferTx, AccountQueryData, BlocksFrontier, FeeAccount, FeeAmount,
+    FeeMerkleTree, Header, NamespaceId, NamespaceMultiProofQueryData, NamespaceProofQueryData,
+    NamespaceProofRangeEntry, NsNonInclusionProof, PubKey, PublicNetworkConfig, SeqTypes,
+    SubmitError, Transaction, TransactionProofQueryData, TransactionStatus, BACKOFF_FACTOR,
+    MAX_RETRY_DELAY, MIN_RETRY_DELAY,
+};
 use ethers::types::Address;
-use futures::{stream::BoxStream, StreamExt};
+use futures::{stream::BoxStream, try_join, StreamExt};
+use hotshot_query_service::{
+    availability::{BlockQueryData, LeafQueryData, PayloadQueryData, TransactionQueryData},
+    explorer::{
+        BlockDetail, BlockDetailResponse, BlockSummary, BlockSummaryResponse, ExplorerSummary,
+        ExplorerSummaryResponse, SearchResult, SearchResultResponse, TransactionDetailResponse,
+        TransactionSummariesResponse, TransactionSummary,
+    },
+    node::SyncStatus,
+};
+use hotshot_types::{
+    data::ViewNumber,
+    light_client::StateSignatureRequestBody,
+    stake_table::StakeTableEntry,
+    traits::node_implementation::ConsensusTime,
+    vid::{VidCommon, VidShare},
+};
+use itertools::Itertools;
 use jf_merkle_tree::{
     prelude::{MerkleProof, Sha3Node},
     MerkleTreeScheme,
 };
-use std::time::Duration;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use surf_disco::{
     error::ClientError,
     socket::{Connection, Unsupported},
-    Url,
+    Error as _, StatusCode, Url,
 };
 use tokio::time::sleep;
 use vbs::version::StaticVersion;
 
 pub type SequencerApiVersion = StaticVersion<0, 1>;
 
+/// The default number of times a request is retried after failing with every node.
+pub const DEFAULT_MAX_RETRIES: usize = 5;
+
+/// A client for the sequencer API.
+///
+/// The client can talk to several nodes, given in order of preference. Each request is tried
+/// against each node in turn until one succeeds. If every node fails, the client backs off
+/// exponentially and tries them all again, up to a maximum number of retries.
+///
+/// Transaction submissions are the exception: a submission which fails may still have reached the
+/// node, and resending it could sequence the transaction twice, so submissions are sent once, to
+/// the preferred node only.
 #[derive(Clone, Debug)]
-pub struct SequencerClient(surf_disco::Client<ClientError, SequencerApiVersion>);
+pub struct SequencerClient {
+    clients: Vec<surf_disco::Client<ClientError, SequencerApiVersion>>,
+    max_retries: usize,
+}
 
 pub type FeeMerkleProof = MerkleProof<FeeAmount, FeeAccount, Sha3Node, { FeeMerkleTree::ARITY }>;
 
+/// The parts of the `availability/vid/common/:height` response we need.
+#[derive(Debug, Deserialize)]
+struct VidCommonResponse {
+    common: VidCommon,
+}
+
 impl SequencerClient {
     pub fn new(provider: Url) -> Self {
-        Self(surf_disco::Client::new(provider))
+        Self::with_failover([provider])
+    }
+
+    /// Create a client which fails over between several nodes, in order of preference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `providers` is empty.
+    pub fn with_failover(providers: impl IntoIterator<Item = Url>) -> Self {
+        let clients = providers
+            .into_iter()
+            .map(surf_disco::Client::new)
+            .collect::<Vec<_>>();
+        assert!(
+            !clients.is_empty(),
+            "cannot create SequencerClient with no providers"
+        );
+        Self {
+            clients,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    /// Set the number of times a request is retried after failing with every node.
+    ///
+    /// With 0, each node is tried exactly once.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Send a request with failover and retries.
+    ///
+    /// `f` makes the request to a single node. It is called with each node in turn until it
+    /// succeeds. Errors caused by a bad request are returned immediately, since no node will
+    /// accept the same request.
+    async fn send<T, Fut>(
+        &self,
+        what: &'static str,
+        f: impl Fn(surf_disco::Client<ClientError, SequencerApiVersion>) -> Fut,
+    ) -> anyhow::Result<T>
+    where
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut delay = MIN_RETRY_DELAY;
+        let mut retry = 0;
+        loop {
+            let mut last_err = None;
+            for (i, client) in self.clients.iter().enumerate() {
+                match f(client.clone()).await {
+                    Ok(res) => return Ok(res),
+                    Err(err) if err.status() == StatusCode::BAD_REQUEST => {
+                        return Err(err).context(what);
+                    }
+                    Err(err) => {
+                        tracing::warn!(provider = i, retry, "error {what}: {err:#}");
+                        last_err = Some(err);
+                    }
+                }
+            }
+
+            if retry >= self.max_retries {
+                // `clients` is never empty, so we must have an error from the last attempt.
+                return Err(last_err.unwrap()).context(what);
+            }
+            sleep(delay).await;
+            delay = min(delay * BACKOFF_FACTOR, MAX_RETRY_DELAY);
+            retry += 1;
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, route: &str, what: &'static str) -> anyhow::Result<T> {
+        self.send(
+            what,
+            |client| async move { client.get::<T>(route).send().await },
+        )
+        .await
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: &(impl Serialize + Sync),
+        what: &'static str,
+    ) -> anyhow::Result<T> {
+        self.send(what, |client| async move {
+            client.post::<T>(route).body_json(body)?.send().await
+        })
+        .await
+    }
+
+    /// Send a POST request which is not safe to repeat to the preferred node, exactly once.
+    async fn post_once<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: &(impl Serialize + Sync),
+        what: &'static str,
+    ) -> anyhow::Result<T> {
+        // `clients` is never empty.
+        self.clients[0]
+            .post::<T>(route)
+            .body_json(body)
+            .context(what)?
+            .send()
+            .await
+            .context(what)
+    }
+
+    async fn subscribe<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        what: &'static str,
+    ) -> anyhow::Result<Connection<T, Unsupported, ClientError, SequencerApiVersion>> {
+        self.send(what, |client| async move {
+            client.socket(route).subscribe::<T>().await
+        })
+        .await
     }
 
     /// GET Block Height from the node
     pub async fn get_height(&self) -> anyhow::Result<u64> {
-        self.0
-            .get::<u64>("node/block-height")
-            .send()
+        self.get("node/block-height", "getting Espresso block height")
             .await
-            .context("getting Espresso block height")
     }
 
     /// Get the Number of Transactions
     pub async fn get_transaction_count(&self) -> anyhow::Result<u64> {
-        self.0
-            .get::<u64>("node/transactions/count")
-            .send()
+        self.get(
+            "node/transactions/count",
+            "getting Espresso transaction count",
+        )
+        .await
+    }
+
+    /// Get the stake table for the given epoch.
+    pub async fn get_stake_table(
+        &self,
+        epoch: u64,
+    ) -> anyhow::Result<Vec<StakeTableEntry<PubKey>>> {
+        self.get(
+            &format!("node/stake-table/{epoch}"),
+            "getting Espresso stake table",
+        )
+        .await
+    }
+
+    /// Get the stake table for the current epoch.
+    pub async fn get_current_stake_table(&self) -> anyhow::Result<Vec<StakeTableEntry<PubKey>>> {
+        self.get("node/stake-table/current", "getting Espresso stake table")
+            .await
+    }
+
+    /// Get the header of the block at `height`.
+    pub async fn get_header(&self, height: u64) -> anyhow::Result<Header> {
+        self.get(
+            &format!("availability/header/{height}"),
+            "getting Espresso header",
+        )
+        .await
+    }
+
+    /// Get the leaf at `height`.
+    pub async fn get_leaf(&self, height: u64) -> anyhow::Result<LeafQueryData<SeqTypes>> {
+        self.get(&format!("availability/leaf/{height}"), "getting leaf")
+            .await
+    }
+
+    /// Get the block at `height`, including its payload.
+    pub async fn get_block(&self, height: u64) -> anyhow::Result<BlockQueryData<SeqTypes>> {
+        self.get(&format!("availability/block/{height}"), "getting block")
             .await
-            .context("getting Espresso transaction count")
+    }
+
+    /// Get the payload of the block at `height`.
+    pub async fn get_payload(&self, height: u64) -> anyhow::Result<PayloadQueryData<SeqTypes>> {
+        self.get(&format!("availability/payload/{height}"), "getting payload")
+            .await
+    }
+
+    /// Get a transaction by its hash, along with the block it is in.
+    ///
+    /// Unlike [`get_transaction_proof`](Self::get_transaction_proof), this does not include a
+    /// proof of inclusion.
+    pub async fn get_transaction(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionQueryData<SeqTypes>> {
+        self.get(
+            &format!("availability/transaction/hash/{hash}"),
+            "getting transaction",
+        )
+        .await
+    }
+
+    /// Get the node's progress in fetching missing data.
+    pub async fn get_sync_status(&self) -> anyhow::Result<SyncStatus> {
+        self.get("node/sync-status", "getting sync status").await
+    }
+
+    /// Get the node's VID share of the block at `height`.
+    pub async fn get_vid_share(&self, height: u64) -> anyhow::Result<VidShare> {
+        self.get(&format!("node/vid/share/{height}"), "getting VID share")
+            .await
+    }
+
+    /// Get the VID common data for the block at `height`.
+    pub async fn get_vid_common(&self, height: u64) -> anyhow::Result<VidCommon> {
+        let res: VidCommonResponse = self
+            .get(
+                &format!("availability/vid/common/{height}"),
+                "getting VID common",
+            )
+            .await?;
+        Ok(res.common)
     }
 
     /// Subscribe to a stream of Block Headers
@@ -50,12 +292,12 @@ impl SequencerClient {
         &self,
         height: u64,
     ) -> anyhow::Result<BoxStream<'static, Result<Header, ClientError>>> {
-        self.0
-            .socket(&format!("availability/stream/headers/{height}"))
-            .subscribe::<Header>()
-            .await
-            .context("subscribing to Espresso headers")
-            .map(|s| s.boxed())
+        self.subscribe::<Header>(
+            &format!("availability/stream/headers/{height}"),
+            "subscribing to Espresso headers",
+        )
+        .await
+        .map(|s| s.boxed())
     }
 
     /// Subscribe to a stream of Block Headers
@@ -63,11 +305,370 @@ impl SequencerClient {
         &self,
         height: u64,
     ) -> anyhow::Result<Connection<Header, Unsupported, ClientError, SequencerApiVersion>> {
-        self.0
-            .socket(&format!("availability/stream/blocks/{height}"))
-            .subscribe()
+        self.subscribe(
+            &format!("availability/stream/blocks/{height}"),
+            "subscribing to Espresso Blocks",
+        )
+        .await
+    }
+
+    /// Get the transactions in namespace `ns` of the block at `height`, along with a proof.
+    ///
+    /// The proof is not checked; use [`fetch_namespace`](Self::fetch_namespace) to get verified
+    /// transactions.
+    pub async fn get_namespace_proof(
+        &self,
+        height: u64,
+        ns: NamespaceId,
+    ) -> anyhow::Result<NamespaceProofQueryData> {
+        self.get(
+            &format!("availability/block/{height}/namespace/{ns}"),
+            "getting namespace proof",
+        )
+        .await
+    }
+
+    /// Get the transactions in several namespaces of the block at `height`, along with a single
+    /// proof for all of them.
+    pub async fn get_namespace_multi_proof(
+        &self,
+        height: u64,
+        namespaces: &[NamespaceId],
+    ) -> anyhow::Result<NamespaceMultiProofQueryData> {
+        self.get(
+            &format!(
+                "availability/block/{height}/namespaces/{}",
+                namespaces.iter().join(",")
+            ),
+            "getting namespace multi-proof",
+        )
+        .await
+    }
+
+    /// Get a proof that namespace `ns` is absent from the block at `height`.
+    pub async fn get_namespace_non_inclusion_proof(
+        &self,
+        height: u64,
+        ns: NamespaceId,
+    ) -> anyhow::Result<NsNonInclusionProof> {
+        self.get(
+            &format!("availability/block/{height}/namespace/{ns}/non-inclusion"),
+            "getting namespace non-inclusion proof",
+        )
+        .await
+    }
+
+    /// Get the transactions in namespace `ns` of each block in `[from, until)`, along with proofs.
+    ///
+    /// The server may return fewer entries than requested; continue from the block after the last
+    /// entry returned to get the rest.
+    pub async fn get_namespace_proof_range(
+        &self,
+        from: u64,
+        until: u64,
+        ns: NamespaceId,
+    ) -> anyhow::Result<Vec<NamespaceProofRangeEntry>> {
+        self.get(
+            &format!("availability/block/{from}/{until}/namespace/{ns}"),
+            "getting namespace proofs",
+        )
+        .await
+    }
+
+    /// Subscribe to the transactions in namespace `ns` of each block, along with proofs, starting
+    /// from `height`.
+    pub async fn subscribe_namespace_proofs(
+        &self,
+        height: u64,
+        ns: NamespaceId,
+    ) -> anyhow::Result<BoxStream<'static, Result<NamespaceProofQueryData, ClientError>>> {
+        self.subscribe::<NamespaceProofQueryData>(
+            &format!("availability/stream/blocks/{height}/namespace/{ns}"),
+            "subscribing to namespace proofs",
+        )
+        .await
+        .map(|s| s.boxed())
+    }
+
+    /// Get a transaction by its hash, along with a proof of its inclusion in a block.
+    pub async fn get_transaction_proof(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionProofQueryData> {
+        self.get(
+            &format!("availability/transaction/hash/{hash}/proof"),
+            "getting transaction proof",
+        )
+        .await
+    }
+
+    /// Get the transactions in namespace `ns` of the block at `height`, verified against its header.
+    ///
+    /// This downloads the header, the namespace proof and the VID common data for the block, and
+    /// checks the proof against the payload commitment and namespace table in the header. If the
+    /// namespace is not present in the block, it checks that it is absent from the namespace table
+    /// and returns no transactions. The header itself is trusted as served by the node; callers who
+    /// need stronger guarantees should check it against a light client first.
+    pub async fn fetch_namespace(
+        &self,
+        height: u64,
+        ns: NamespaceId,
+    ) -> anyhow::Result<Vec<Transaction>> {
+        let (header, ns_proof, vid_common) = try_join!(
+            self.get_header(height),
+            self.get_namespace_proof(height, ns),
+            self.get_vid_common(height),
+        )?;
+        ensure!(
+            header.height() == height,
+            "requested header {height}, got header {}",
+            header.height()
+        );
+
+        // We ignore the transactions sent by the server, and use only the transactions extracted
+        // from the proof.
+        let Some(proof) = ns_proof.proof else {
+            ensure!(
+                header.ns_table().find_ns_id(&ns).is_none(),
+                "no proof for namespace {ns}, but it is present in block {height}"
+            );
+            if let Some(proof) = ns_proof.non_inclusion_proof {
+                proof
+                    .verify(&header.ns_table().commit(), &ns)
+                    .with_context(|| {
+                        format!("invalid non-inclusion proof for namespace {ns} in block {height}")
+                    })?;
+            }
+            return Ok(vec![]);
+        };
+        let (transactions, proof_ns) = proof
+            .verify(header.ns_table(), &header.payload_commitment(), &vid_common)
+            .with_context(|| format!("invalid proof for namespace {ns} in block {height}"))?;
+        ensure!(
+            proof_ns == ns,
+            "requested namespace {ns}, got proof for namespace {proof_ns}"
+        );
+        Ok(transactions)
+    }
+
+    /// Submit a transaction to the preferred node, returning its hash.
+    ///
+    /// The submission is not retried or failed over, since a failed submission may still have
+    /// reached the node. Before resubmitting, callers can check whether the transaction was
+    /// received with [`get_transaction_status`](Self::get_transaction_status).
+    pub async fn submit(&self, tx: &Transaction) -> anyhow::Result<Commitment<Transaction>> {
+        self.post_once("submit/submit", tx, "submitting transaction")
+            .await
+    }
+
+    /// Submit several transactions at once to the preferred node.
+    ///
+    /// Returns, for each transaction in the order given, either its hash or the reason it was
+    /// rejected. Like [`submit`](Self::submit), this is not retried or failed over.
+    pub async fn submit_batch(
+        &self,
+        txs: &[Transaction],
+    ) -> anyhow::Result<Vec<Result<Commitment<Transaction>, SubmitError>>> {
+        self.post_once("submit/batch", &txs, "submitting transactions")
             .await
-            .context("subscribing to Espresso Blocks")
+    }
+
+    /// Submit a signed transfer of fee balance between Espresso accounts.
+    ///
+    /// The node holds the transfer and broadcasts it to the other nodes, so that it can be executed
+    /// in the view it was signed for. Submitting the same transfer more than once has no further
+    /// effect, so unlike [`submit`](Self::submit), this is retried and failed over.
+    pub async fn submit_transfer(&self, tx: &TransferTx) -> anyhow::Result<Commitment<TransferTx>> {
+        self.post("submit/transfer", tx, "submitting transfer")
+            .await
+    }
+
+    /// Get the status of a transaction recently submitted to the node.
+    ///
+    /// Since transactions are tracked by the node they were submitted to, this should be used with
+    /// a client whose preferred node is the one the transaction was submitted to.
+    pub async fn get_transaction_status(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionStatus> {
+        self.get(
+            &format!("submit/status/{hash}"),
+            "getting transaction status",
+        )
+        .await
+    }
+
+    /// Get the state of `account` as of the given block height and view, with a proof.
+    ///
+    /// `height` and `view` must correspond.
+    pub async fn get_account(
+        &self,
+        height: u64,
+        view: ViewNumber,
+        account: FeeAccount,
+    ) -> anyhow::Result<AccountQueryData> {
+        self.get(
+            &format!("catchup/{height}/{}/account/{account}", view.u64()),
+            "getting fee account",
+        )
+        .await
+    }
+
+    /// Get the state of several accounts as of the given block height and view.
+    ///
+    /// Returns a sparse fee Merkle tree containing each of the requested accounts. `height` and
+    /// `view` must correspond.
+    pub async fn get_accounts(
+        &self,
+        height: u64,
+        view: ViewNumber,
+        accounts: &[FeeAccount],
+    ) -> anyhow::Result<FeeMerkleTree> {
+        self.post(
+            &format!("catchup/{height}/{}/accounts", view.u64()),
+            &accounts,
+            "getting fee accounts",
+        )
+        .await
+    }
+
+    /// Get the blocks Merkle tree frontier as of the given block height and view.
+    ///
+    /// `height` and `view` must correspond.
+    pub async fn get_blocks_frontier(
+        &self,
+        height: u64,
+        view: ViewNumber,
+    ) -> anyhow::Result<BlocksFrontier> {
+        self.get(
+            &format!("catchup/{height}/{}/blocks", view.u64()),
+            "getting blocks frontier",
+        )
+        .await
+    }
+
+    /// Get the chain config with the given commitment.
+    pub async fn get_chain_config(
+        &self,
+        commitment: Commitment<ChainConfig>,
+    ) -> anyhow::Result<ChainConfig> {
+        self.get(
+            &format!("catchup/chain-config/{commitment}"),
+            "getting chain config",
+        )
+        .await
+    }
+
+    /// Get the public parts of the node's network configuration.
+    pub async fn get_hotshot_config(&self) -> anyhow::Result<PublicNetworkConfig> {
+        self.get("config/hotshot", "getting HotShot config").await
+    }
+
+    /// Get the public `ESPRESSO_` environment variables of the node, as `KEY=value` strings.
+    pub async fn get_env(&self) -> anyhow::Result<Vec<String>> {
+        self.get("config/env", "getting node environment").await
+    }
+
+    /// Get the node's signature on the light client state after the block at `height`.
+    pub async fn get_state_signature(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<StateSignatureRequestBody> {
+        self.get(
+            &format!("state-signature/block/{height}"),
+            "getting state signature",
+        )
+        .await
+    }
+
+    /// Get the block explorer's details of the block at `height`.
+    pub async fn get_explorer_block_detail(
+        &self,
+        height: u64,
+    ) -> anyhow::Result<BlockDetail<SeqTypes>> {
+        let res: BlockDetailResponse<SeqTypes> = self
+            .get(
+                &format!("explorer/block/{height}"),
+                "getting explorer block detail",
+            )
+            .await?;
+        Ok(res.block_detail)
+    }
+
+    /// Get the block explorer's summaries of up to `limit` blocks, counting down from block `from`,
+    /// or from the latest block if `from` is `None`.
+    pub async fn get_explorer_block_summaries(
+        &self,
+        from: Option<u64>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<BlockSummary<SeqTypes>>> {
+        let route = match from {
+            Some(from) => format!("explorer/blocks/{from}/{limit}"),
+            None => format!("explorer/blocks/latest/{limit}"),
+        };
+        let res: BlockSummaryResponse<SeqTypes> =
+            self.get(&route, "getting explorer block summaries").await?;
+        Ok(res.block_summaries)
+    }
+
+    /// Get the block explorer's details of the transaction with the given hash.
+    pub async fn get_explorer_transaction_detail(
+        &self,
+        hash: Commitment<Transaction>,
+    ) -> anyhow::Result<TransactionDetailResponse<SeqTypes>> {
+        self.get(
+            &format!("explorer/transaction/hash/{hash}"),
+            "getting explorer transaction detail",
+        )
+        .await
+    }
+
+    /// Get the block explorer's summaries of up to `limit` transactions, counting down from the
+    /// transaction at `offset` in block `height`, or from the latest transaction if `from` is
+    /// `None`.
+    pub async fn get_explorer_transaction_summaries(
+        &self,
+        from: Option<(u64, u64)>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<TransactionSummary<SeqTypes>>> {
+        let route = match from {
+            Some((height, offset)) => {
+                format!("explorer/transactions/from/{height}/{offset}/{limit}")
+            }
+            None => format!("explorer/transactions/latest/{limit}"),
+        };
+        let res: TransactionSummariesResponse<SeqTypes> = self
+            .get(&route, "getting explorer transaction summaries")
+            .await?;
+        Ok(res.transaction_summaries)
+    }
+
+    /// Get the block explorer's summary of the chain.
+    pub async fn get_explorer_summary(&self) -> anyhow::Result<ExplorerSummary<SeqTypes>> {
+        let res: ExplorerSummaryResponse<SeqTypes> = self
+            .get("explorer/explorer-summary", "getting explorer summary")
+            .await?;
+        Ok(res.explorer_summary)
+    }
+
+    /// Search the block explorer for blocks and transactions matching `query`.
+    pub async fn explorer_search(&self, query: &str) -> anyhow::Result<SearchResult<SeqTypes>> {
+        let res: SearchResultResponse<SeqTypes> = self
+            .get(&format!("explorer/search/{query}"), "searching explorer")
+            .await?;
+        Ok(res.search_results)
+    }
+
+    /// Get the balance for a given account in the latest fee state the node has.
+    pub async fn get_latest_espresso_balance(&self, address: Address) -> anyhow::Result<FeeAmount> {
+        let balance: Option<FeeAmount> = self
+            .get(
+                &format!("fee-state/fee-balance/latest/{address:#x}"),
+                "getting account balance",
+            )
+            .await?;
+        // If there is no account with this address, the balance is defined to be 0.
+        Ok(balance.unwrap_or(0.into()))
     }
 
     /// Get the balance for a given account at a given block height, defaulting to current balance.
@@ -87,32 +688,17 @@ impl SequencerClient {
         }
         // Block is non-zero, we can safely decrement to query the state as of the previous block.
         block -= 1;
-        // Download the Merkle path for this fee account at the specified block height. Transient errors
-        // are possible (for example, if we are fetching from the latest block, the block height might
-        // get incremented shortly before the state becomes available) so retry a few times.
-        let mut retry = 0;
-        let max_retries = 10;
-        let proof = loop {
-            tracing::debug!(%address, block, retry, "fetching Espresso balance");
-            match self
-                .0
-                .get::<FeeMerkleProof>(&format!("fee-state/{block}/{address:#x}"))
-                .send()
-                .await
-            {
-                Ok(proof) => break proof,
-                Err(err) => {
-                    tracing::warn!("error getting account balance: {err:#}");
-                    retry += 1;
-
-                    if retry == max_retries {
-                        return Err(err).context("getting account balance");
-                    } else {
-                        sleep(Duration::from_millis(200)).await;
-                    }
-                }
-            }
-        };
+        // Download the Merkle path for this fee account at the specified block height. Transient
+        // errors are possible (for example, if we are fetching from the latest block, the block
+        // height might get incremented shortly before the state becomes available), but these are
+        // handled by the retries in `get`.
+        tracing::debug!(%address, block, "fetching Espresso balance");
+        let proof: FeeMerkleProof = self
+            .get(
+                &format!("fee-state/{block}/{address:#x}"),
+                "getting account balance",
+            )
+            .await?;
 
         // If the element in the Merkle path is missing -- there is no account with this address -- the
         // balance is defined to be 0.
@@ -137,4 +723,14 @@ mod tests {
             0.into()
         )
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_retries_exhausted() {
+        let client = SequencerClient::with_failover([
+            "http://dummy-url:3030".parse().unwrap(),
+            "http://dummy-url:3031".parse().unwrap(),
+        ])
+        .with_max_retries(1);
+        client.get_height().await.unwrap_err();
+    }
 }
@@ -0,0 +1,308 @@
+//! Strategies deciding what a reserve builder bids in each auction.
+//!
+//! A reserve builder submits a bid a few views ahead of the view it wants to build for. The
+//! [`BidStrategy`] decides, for every finished view, whether to bid, for which namespaces, for
+//! which view and how much.
+
+use std::collections::{HashMap, VecDeque};
+
+use clap::ValueEnum;
+use espresso_types::{v0_99::SolverAuctionResults, FeeAccount, FeeAmount, NamespaceId};
+use ethers::types::U256;
+use hotshot_types::data::ViewNumber;
+
+/// Default number of views in advance of which bids are submitted.
+pub const DEFAULT_BID_LOOKAHEAD: u64 = 3;
+
+/// Number of auction outcomes kept for strategies to look back on.
+pub const AUCTION_HISTORY_LEN: usize = 10;
+
+/// Selects which [`BidStrategy`] the reserve builder uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BidStrategyKind {
+    /// Bid the same amount for every view.
+    #[default]
+    Fixed,
+    /// Adjust the bid based on pending transactions and recent auction outcomes.
+    Adaptive,
+}
+
+/// The outcome of an auction the builder bid in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuctionOutcome {
+    /// View the auction was held for.
+    pub view: ViewNumber,
+    /// Amount we bid.
+    pub bid: FeeAmount,
+    /// Whether our bid won.
+    pub won: bool,
+    /// Highest amount bid by another builder that won one of our namespaces, if any.
+    pub highest_competing_bid: Option<FeeAmount>,
+}
+
+impl AuctionOutcome {
+    /// Derive the outcome of our bid from the results of an auction.
+    pub fn from_results(
+        results: &SolverAuctionResults,
+        account: FeeAccount,
+        bid: FeeAmount,
+        namespaces: &[NamespaceId],
+    ) -> Self {
+        let won = results
+            .winning_bids()
+            .iter()
+            .any(|winner| winner.account() == account);
+        let highest_competing_bid = results
+            .winning_bids()
+            .iter()
+            .filter(|winner| winner.account() != account)
+            .filter(|winner| winner.namespaces().iter().any(|ns| namespaces.contains(ns)))
+            .map(|winner| winner.amount())
+            .max();
+
+        Self {
+            view: results.view(),
+            bid,
+            won,
+            highest_competing_bid,
+        }
+    }
+}
+
+/// Everything a [`BidStrategy`] may base its decision on.
+#[derive(Clone, Copy, Debug)]
+pub struct BidContext<'a> {
+    /// The view that just finished.
+    pub view_number: ViewNumber,
+    /// Namespaces the builder is configured to build for.
+    pub namespaces: &'a [NamespaceId],
+    /// Fees the builder expects to collect from its pending transactions, per namespace.
+    pub pending_value: &'a HashMap<NamespaceId, FeeAmount>,
+    /// Outcomes of the most recent auctions we bid in, oldest first.
+    pub recent_outcomes: &'a VecDeque<AuctionOutcome>,
+    /// The most the builder is willing to bid for a single view.
+    pub budget: FeeAmount,
+}
+
+/// A bid decided on by a [`BidStrategy`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bid {
+    /// Amount to bid.
+    pub amount: FeeAmount,
+    /// Namespaces to bid for.
+    pub namespaces: Vec<NamespaceId>,
+    /// View to bid for.
+    pub view: ViewNumber,
+}
+
+/// Decides the bid a reserve builder submits after each finished view.
+pub trait BidStrategy: Send + Sync {
+    /// Decide the bid to submit, or `None` to skip bidding this time.
+    fn bid(&self, ctx: &BidContext) -> Option<Bid>;
+}
+
+impl BidStrategyKind {
+    /// Construct the selected strategy.
+    ///
+    /// `amount` is the bid for the fixed strategy and the minimum bid for the adaptive one.
+    pub fn build(self, amount: FeeAmount, lookahead: u64) -> Box<dyn BidStrategy> {
+        match self {
+            Self::Fixed => Box::new(FixedBidStrategy { amount, lookahead }),
+            Self::Adaptive => Box::new(AdaptiveBidStrategy {
+                min_amount: amount,
+                lookahead,
+            }),
+        }
+    }
+}
+
+/// Bid the same amount for all configured namespaces, a fixed number of views ahead.
+#[derive(Clone, Debug)]
+pub struct FixedBidStrategy {
+    /// Amount to bid.
+    pub amount: FeeAmount,
+    /// Number of views in advance to bid.
+    pub lookahead: u64,
+}
+
+impl BidStrategy for FixedBidStrategy {
+    fn bid(&self, ctx: &BidContext) -> Option<Bid> {
+        if ctx.namespaces.is_empty() || self.amount > ctx.budget {
+            return None;
+        }
+        Some(Bid {
+            amount: self.amount,
+            namespaces: ctx.namespaces.to_vec(),
+            view: ctx.view_number + self.lookahead,
+        })
+    }
+}
+
+/// Adjust the bid to what recent auctions suggest it takes to win.
+///
+/// After losing, the strategy outbids the highest competing winner; after winning, it backs off
+/// by 10% to avoid overpaying. The bid never exceeds the fees the builder expects to collect from
+/// its pending transactions (but is always at least `min_amount`), nor the budget.
+#[derive(Clone, Debug)]
+pub struct AdaptiveBidStrategy {
+    /// Minimum amount to bid.
+    pub min_amount: FeeAmount,
+    /// Number of views in advance to bid.
+    pub lookahead: u64,
+}
+
+impl BidStrategy for AdaptiveBidStrategy {
+    fn bid(&self, ctx: &BidContext) -> Option<Bid> {
+        if ctx.namespaces.is_empty() {
+            return None;
+        }
+
+        let target = match ctx.recent_outcomes.back() {
+            Some(outcome) if outcome.won => FeeAmount(outcome.bid.0 * 9u64 / 10u64),
+            Some(outcome) => match outcome.highest_competing_bid {
+                Some(competing) => FeeAmount(competing.0 + U256::one()),
+                None => outcome.bid,
+            },
+            None => self.min_amount,
+        };
+
+        let pending_value = ctx
+            .namespaces
+            .iter()
+            .filter_map(|ns| ctx.pending_value.get(ns))
+            .fold(FeeAmount::default(), |total, value| total + *value);
+
+        let amount = target.min(pending_value).max(self.min_amount);
+        if amount > ctx.budget {
+            return None;
+        }
+
+        Some(Bid {
+            amount,
+            namespaces: ctx.namespaces.to_vec(),
+            view: ctx.view_number + self.lookahead,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use hotshot_types::traits::node_implementation::ConsensusTime;
+
+    use super::*;
+
+    fn outcome(bid: u64, won: bool, highest_competing_bid: Option<u64>) -> AuctionOutcome {
+        AuctionOutcome {
+            view: ViewNumber::genesis(),
+            bid: bid.into(),
+            won,
+            highest_competing_bid: highest_competing_bid.map(FeeAmount::from),
+        }
+    }
+
+    fn bid_amount(
+        strategy: &impl BidStrategy,
+        pending_value: &HashMap<NamespaceId, FeeAmount>,
+        recent_outcomes: &VecDeque<AuctionOutcome>,
+        budget: u64,
+    ) -> Option<FeeAmount> {
+        let bid = strategy.bid(&BidContext {
+            view_number: ViewNumber::new(5),
+            namespaces: &[NamespaceId::from(1u32)],
+            pending_value,
+            recent_outcomes,
+            budget: budget.into(),
+        })?;
+        assert_eq!(bid.view, ViewNumber::new(5 + DEFAULT_BID_LOOKAHEAD));
+        assert_eq!(bid.namespaces, vec![NamespaceId::from(1u32)]);
+        Some(bid.amount)
+    }
+
+    #[test]
+    fn test_fixed_bid_strategy() {
+        let strategy = FixedBidStrategy {
+            amount: 100.into(),
+            lookahead: DEFAULT_BID_LOOKAHEAD,
+        };
+        let pending_value = HashMap::from([(NamespaceId::from(1u32), FeeAmount::from(1000))]);
+        let mut recent_outcomes = VecDeque::new();
+
+        // The same amount is bid regardless of pending transactions and past auctions.
+        assert_eq!(
+            bid_amount(&strategy, &HashMap::new(), &recent_outcomes, 100),
+            Some(100.into())
+        );
+        recent_outcomes.push_back(outcome(100, false, Some(2000)));
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 10_000),
+            Some(100.into())
+        );
+
+        // Skip the auction when the bid would exceed the budget.
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 99),
+            None
+        );
+
+        // Nothing to bid for without namespaces.
+        assert_eq!(
+            strategy.bid(&BidContext {
+                view_number: ViewNumber::new(5),
+                namespaces: &[],
+                pending_value: &pending_value,
+                recent_outcomes: &recent_outcomes,
+                budget: 10_000.into(),
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_adaptive_bid_strategy() {
+        let strategy = AdaptiveBidStrategy {
+            min_amount: 10.into(),
+            lookahead: DEFAULT_BID_LOOKAHEAD,
+        };
+        let pending_value = HashMap::from([(NamespaceId::from(1u32), FeeAmount::from(1000))]);
+        let mut recent_outcomes = VecDeque::new();
+
+        // With no history we start from the minimum.
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 10_000),
+            Some(10.into())
+        );
+
+        // After losing, outbid the winner.
+        recent_outcomes.push_back(outcome(10, false, Some(100)));
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 10_000),
+            Some(101.into())
+        );
+
+        // After winning, back off.
+        recent_outcomes.push_back(outcome(100, true, None));
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 10_000),
+            Some(90.into())
+        );
+
+        // Never bid more than the pending transactions are worth...
+        recent_outcomes.push_back(outcome(100, false, Some(2000)));
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 10_000),
+            Some(1000.into())
+        );
+
+        // ...unless that is below the minimum.
+        assert_eq!(
+            bid_amount(&strategy, &HashMap::new(), &recent_outcomes, 10_000),
+            Some(10.into())
+        );
+
+        // Skip the auction when the bid would exceed the budget.
+        assert_eq!(
+            bid_amount(&strategy, &pending_value, &recent_outcomes, 999),
+            None
+        );
+    }
+}
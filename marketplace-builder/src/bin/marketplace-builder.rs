@@ -2,8 +2,8 @@ use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 
 use clap::Parser;
 use espresso_types::{
-    eth_signature_key::EthKeyPair, parse_duration, FeeAmount, FeeVersion, MarketplaceVersion,
-    NamespaceId, SequencerVersions, V0_0,
+    eth_signature_key::EthKeyPair, parse_duration, FeeAmount, FeeMarketVersion, FeeVersion,
+    MarketplaceVersion, NamespaceId, SequencerVersions, V0_0,
 };
 use futures::future::pending;
 use hotshot::helpers::initialize_logging;
@@ -12,8 +12,9 @@ use hotshot_types::{
     traits::node_implementation::{ConsensusTime, Versions},
 };
 use marketplace_builder::{
+    bid_strategy::BidStrategyKind,
     builder::{build_instance_state, BuilderConfig},
-    hooks::BidConfig,
+    hooks::{BidConfig, FallbackConfig},
 };
 use sequencer::{Genesis, L1Params};
 use url::Url;
@@ -100,13 +101,67 @@ struct NonPermissionedBuilderOptions {
     solver_url: Url,
 
     /// Bid amount in WEI.
-    /// Builder will submit the same bid for every view
+    ///
+    /// With the fixed strategy, the builder will submit the same bid for every view. With the
+    /// adaptive strategy, this is the minimum bid.
     #[clap(
         long,
         env = "ESPRESSO_MARKETPLACE_BUILDER_BID_AMOUNT",
         default_value = "1"
     )]
     bid_amount: FeeAmount,
+
+    /// Strategy used to decide bids.
+    #[clap(
+        long,
+        env = "ESPRESSO_MARKETPLACE_BUILDER_BID_STRATEGY",
+        value_enum,
+        default_value = "fixed"
+    )]
+    bid_strategy: BidStrategyKind,
+
+    /// Maximum amount in WEI to bid for a single view.
+    ///
+    /// Required with the adaptive strategy, whose bids would otherwise never exceed the minimum.
+    /// Defaults to the bid amount with the fixed strategy.
+    #[clap(
+        long,
+        env = "ESPRESSO_MARKETPLACE_BUILDER_BID_BUDGET",
+        required_if_eq("bid_strategy", "adaptive")
+    )]
+    bid_budget: Option<FeeAmount>,
+
+    /// Number of views in advance to submit bids.
+    #[clap(
+        long,
+        env = "ESPRESSO_MARKETPLACE_BUILDER_BID_LOOKAHEAD",
+        default_value = "3"
+    )]
+    bid_lookahead: u64,
+
+    /// How long a fallback builder keeps using the last rollup registrations fetched from the
+    /// solver while the solver cannot be reached.
+    ///
+    /// Once this elapses, the fallback builder stops accepting transactions until the solver is
+    /// reached again.
+    #[clap(
+        long,
+        env = "ESPRESSO_MARKETPLACE_BUILDER_NAMESPACES_STALENESS_WINDOW",
+        default_value = "2m",
+        value_parser = parse_duration
+    )]
+    namespaces_staleness_window: Duration,
+
+    /// Number of views between full resynchronizations of rollup registrations by a fallback
+    /// builder.
+    ///
+    /// In between, only registration changes are fetched from the solver, every view.
+    #[clap(
+        long,
+        env = "ESPRESSO_MARKETPLACE_BUILDER_NAMESPACES_RESYNC_INTERVAL",
+        default_value = "100"
+    )]
+    namespaces_resync_interval: u64,
 }
 
 #[tokio::main]
@@ -125,7 +180,13 @@ async fn main() -> anyhow::Result<()> {
         (FeeVersion::VERSION, MarketplaceVersion::VERSION) => {
             run::<SequencerVersions<FeeVersion, MarketplaceVersion>>(genesis, opt).await
         }
+        (FeeVersion::VERSION, FeeMarketVersion::VERSION) => {
+            run::<SequencerVersions<FeeVersion, FeeMarketVersion>>(genesis, opt).await
+        }
         (FeeVersion::VERSION, _) => run::<SequencerVersions<FeeVersion, V0_0>>(genesis, opt).await,
+        (FeeMarketVersion::VERSION, _) => {
+            run::<SequencerVersions<FeeMarketVersion, V0_0>>(genesis, opt).await
+        }
         (MarketplaceVersion::VERSION, _) => {
             run::<SequencerVersions<MarketplaceVersion, V0_0>>(genesis, opt).await
         }
@@ -149,22 +210,30 @@ async fn run<V: Versions>(
         Some(BidConfig {
             amount: opt.bid_amount,
             namespaces: opt.namespaces.into_iter().map(NamespaceId::from).collect(),
+            strategy: opt.bid_strategy,
+            budget: opt.bid_budget.unwrap_or(opt.bid_amount),
+            lookahead: opt.bid_lookahead,
         })
     } else {
         None
     };
 
+    let fallback_config = FallbackConfig {
+        staleness_window: opt.namespaces_staleness_window,
+        resync_interval: opt.namespaces_resync_interval,
+    };
+
     let builder_key_pair = EthKeyPair::from_mnemonic(&opt.eth_mnemonic, opt.eth_account_index)?;
     let bootstrapped_view = ViewNumber::new(opt.view_number);
 
     let builder_server_url: Url = format!("http://0.0.0.0:{}", opt.port).parse().unwrap();
 
+    let base_fee = genesis.next_base_fee(&opt.state_peers).await;
     let instance_state =
         build_instance_state::<V>(genesis.chain_config, l1_params, opt.state_peers)
             .await
             .unwrap();
 
-    let base_fee = genesis.max_base_fee();
     tracing::info!(?base_fee, "base_fee");
 
     let api_response_timeout_duration = opt.max_api_timeout_duration;
@@ -185,6 +254,7 @@ async fn run<V: Versions>(
         txn_timeout_duration,
         base_fee,
         bid_config,
+        fallback_config,
         opt.solver_url,
     )
     .await?;
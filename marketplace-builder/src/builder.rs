@@ -7,10 +7,9 @@ use async_broadcast::{
 
 use async_lock::RwLock;
 use espresso_types::{
-    eth_signature_key::EthKeyPair,
-    v0_99::{ChainConfig, RollupRegistration},
-    FeeAmount, L1Client, MarketplaceVersion, MockSequencerVersions, NamespaceId, NodeState,
-    Payload, SeqTypes, SequencerVersions, ValidatedState, V0_1,
+    eth_signature_key::EthKeyPair, v0_4::ChainConfig, v0_99::RollupRegistration, FeeAmount,
+    L1Client, MarketplaceVersion, MockSequencerVersions, NamespaceId, NodeState, Payload, SeqTypes,
+    SequencerVersions, ValidatedState, V0_1,
 };
 use ethers::{
     core::k256::ecdsa::SigningKey,
@@ -50,6 +49,7 @@ use vbs::version::{StaticVersion, StaticVersionType};
 
 use crate::hooks::{
     self, fetch_namespaces_to_skip, BidConfig, EspressoFallbackHooks, EspressoReserveHooks,
+    FallbackConfig,
 };
 
 type DynamicHooks = Box<dyn BuilderHooks<SeqTypes>>;
@@ -136,6 +136,7 @@ impl BuilderConfig {
         maximize_txns_count_timeout_duration: Duration,
         base_fee: FeeAmount,
         bid_config: Option<BidConfig>,
+        fallback_config: FallbackConfig,
         solver_base_url: Url,
     ) -> anyhow::Result<Self> {
         tracing::info!(
@@ -149,22 +150,33 @@ impl BuilderConfig {
             "initializing builder",
         );
 
+        let max_fee_per_byte = instance_state.chain_config.max_fee_per_byte(base_fee);
         let hooks: DynamicHooks = if is_reserve {
             let bid_config = bid_config.expect("Missing bid config for the reserve builder.");
             Box::new(hooks::EspressoReserveHooks {
                 namespaces: bid_config.namespaces.into_iter().collect(),
+                auction_results: hooks::auction_results_provider(solver_base_url.clone()),
                 solver_base_url,
                 builder_api_base_url: builder_api_url.clone(),
                 bid_key_pair: builder_key_pair.clone(),
-                bid_amount: bid_config.amount,
+                bid_strategy: bid_config
+                    .strategy
+                    .build(bid_config.amount, bid_config.lookahead)
+                    .into(),
+                bid_budget: bid_config.budget,
+                base_fee: RwLock::new(base_fee).into(),
+                chain_config: instance_state.chain_config,
+                bid_state: Default::default(),
             })
         } else {
-            // Fetch the namespaces upon initialization. It will be fetched every 20 views when
+            // Fetch the namespaces upon initialization. They will be kept up to date when
             // handling events.
-            let namespaces_to_skip = fetch_namespaces_to_skip(solver_base_url.clone()).await;
+            let namespaces_to_skip = fetch_namespaces_to_skip(solver_base_url.clone(), None).await;
             Box::new(hooks::EspressoFallbackHooks {
                 solver_base_url,
                 namespaces_to_skip: RwLock::new(namespaces_to_skip).into(),
+                config: fallback_config,
+                syncing: Default::default(),
             })
         };
 
@@ -175,7 +187,9 @@ impl BuilderConfig {
             maximize_txns_count_timeout_duration,
             Duration::from_secs(60),
             tx_channel_capacity.get(),
-            base_fee.as_u64().expect("Base fee too high"),
+            // The builder pays a flat rate per byte, so pay the highest namespace rate to always
+            // cover the block fee.
+            max_fee_per_byte.as_u64().expect("Base fee too high"),
             hooks,
         );
 
@@ -263,6 +277,7 @@ mod test {
     use vbs::version::StaticVersion;
 
     use super::*;
+    use crate::bid_strategy::{BidStrategyKind, DEFAULT_BID_LOOKAHEAD};
 
     const REGISTERED_NAMESPACE: u64 = 10;
     const UNREGISTERED_NAMESPACE: u64 = 20;
@@ -565,7 +580,11 @@ mod test {
             Some(BidConfig {
                 namespaces: vec![NamespaceId::from(REGISTERED_NAMESPACE)],
                 amount: FeeAmount::from(10),
+                strategy: BidStrategyKind::Fixed,
+                budget: FeeAmount::from(10),
+                lookahead: DEFAULT_BID_LOOKAHEAD,
             }),
+            Default::default(),
             solver_base_url,
         );
         let _ = init.await.unwrap();
@@ -686,6 +705,7 @@ mod test {
             Duration::from_secs(2),
             base_fee,
             None,
+            Default::default(),
             solver_base_url,
         );
         let _ = init.await.unwrap();
@@ -1,16 +1,21 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use async_lock::RwLock;
+use async_lock::{Mutex, RwLock};
 use async_trait::async_trait;
 use espresso_types::v0_99::BidTxBody;
 use tokio::{spawn, time::sleep};
 
-use espresso_types::v0_99::RollupRegistration;
+use espresso_types::v0_4::ChainConfig;
+use espresso_types::v0_99::{
+    RollupRegistrationBody, RollupRegistrationChanges, RollupRegistrationsSnapshot,
+};
 
 use espresso_types::MarketplaceVersion;
 use espresso_types::SeqTypes;
+use espresso_types::SolverAuctionResultsProvider;
 use hotshot::types::EventType;
 
 use hotshot::types::Event;
@@ -22,8 +27,11 @@ use espresso_types::FeeAmount;
 
 use espresso_types::eth_signature_key::EthKeyPair;
 
-use espresso_types::NamespaceId;
+use espresso_types::{NamespaceId, NsTableBuilder};
 
+use hotshot_types::data::ViewNumber;
+use hotshot_types::traits::auction_results_provider::AuctionResultsProvider;
+use hotshot_types::traits::block_contents::Transaction as _;
 use hotshot_types::traits::node_implementation::NodeType;
 
 use marketplace_solver::SolverError;
@@ -35,49 +43,162 @@ use tide_disco::Url;
 use tracing::error;
 use tracing::info;
 
+use crate::bid_strategy::{
+    AuctionOutcome, BidContext, BidStrategy, BidStrategyKind, AUCTION_HISTORY_LEN,
+};
+
 /// Configurations for bid submission.
 pub struct BidConfig {
     /// Namespace IDs to filter and bid for.
     pub namespaces: Vec<NamespaceId>,
-    /// Amount to bid.
+    /// Amount to bid with the fixed strategy, or the minimum bid with the adaptive one.
     pub amount: FeeAmount,
+    /// Strategy deciding the bid for each view.
+    pub strategy: BidStrategyKind,
+    /// Maximum amount to bid for a single view.
+    pub budget: FeeAmount,
+    /// Number of views in advance to bid.
+    pub lookahead: u64,
 }
 
 pub fn connect_to_solver(solver_base_url: Url) -> Client<SolverError, MarketplaceVersion> {
     Client::<SolverError, MarketplaceVersion>::new(solver_base_url.join(SOLVER_API_PATH).unwrap())
 }
 
-/// Fetch registered namespaces from the solver and construct the list of namespaces to skip.
+/// Auction results provider for the solver at `solver_base_url`.
+pub fn auction_results_provider(solver_base_url: Url) -> SolverAuctionResultsProvider {
+    SolverAuctionResultsProvider::new(
+        solver_base_url,
+        format!("{SOLVER_API_PATH}/"),
+        "auction_results/".into(),
+    )
+}
+
+/// Default time for which the fallback builder keeps using the last namespaces fetched from the
+/// solver while it cannot be reached.
+pub const DEFAULT_NAMESPACES_STALENESS_WINDOW: Duration = Duration::from_secs(120);
+
+/// Default number of views between full resynchronizations of the namespaces to skip.
+pub const DEFAULT_NAMESPACES_RESYNC_INTERVAL: u64 = 100;
+
+/// Configurations for the fallback builder.
+#[derive(Clone, Copy, Debug)]
+pub struct FallbackConfig {
+    /// How long the last namespaces fetched from the solver are used while it cannot be reached.
+    pub staleness_window: Duration,
+    /// Number of views between full resynchronizations of the namespaces to skip.
+    ///
+    /// In between, only the registration changes made since the last query are fetched.
+    pub resync_interval: u64,
+}
+
+impl Default for FallbackConfig {
+    fn default() -> Self {
+        Self {
+            staleness_window: DEFAULT_NAMESPACES_STALENESS_WINDOW,
+            resync_interval: DEFAULT_NAMESPACES_RESYNC_INTERVAL,
+        }
+    }
+}
+
+/// Namespaces the fallback builder skips, as last fetched from the solver.
+#[derive(Clone, Debug)]
+pub struct NamespacesToSkip {
+    /// Namespaces registered with a reserve builder or inactive.
+    pub namespaces: HashSet<NamespaceId>,
+    /// Position in the solver's registration change log the namespaces are up to date with.
+    pub cursor: u64,
+    /// When the solver was last reached.
+    pub refreshed_at: Instant,
+}
+
+impl NamespacesToSkip {
+    fn from_snapshot(snapshot: RollupRegistrationsSnapshot) -> Self {
+        let namespaces = snapshot
+            .registrations
+            .into_iter()
+            .filter(|registration| skip_namespace(&registration.body))
+            .map(|registration| registration.body.namespace_id)
+            .collect();
+        Self {
+            namespaces,
+            cursor: snapshot.cursor,
+            refreshed_at: Instant::now(),
+        }
+    }
+
+    fn apply(&mut self, changes: RollupRegistrationChanges) {
+        for entry in changes.changes {
+            let body = entry.registration.body;
+            if skip_namespace(&body) {
+                self.namespaces.insert(body.namespace_id);
+            } else {
+                self.namespaces.remove(&body.namespace_id);
+            }
+        }
+        self.cursor = changes.cursor;
+    }
+}
+
+/// Whether the fallback builder skips transactions for a registered rollup.
+fn skip_namespace(registration: &RollupRegistrationBody) -> bool {
+    registration.reserve_url.is_some() || !registration.active
+}
+
+/// Fetch registration changes from the solver and update the list of namespaces to skip.
+///
+/// Only the changes made since `namespaces_to_skip` was last updated are fetched. If it is `None`,
+/// the list is rebuilt from a snapshot of the current registrations.
 ///
 /// # Returns
 /// - `Some` namespaces if the fetching succeeds, even if the list is empty.
 /// - `None` if the fetching fails.
-pub async fn fetch_namespaces_to_skip(solver_base_url: Url) -> Option<HashSet<NamespaceId>> {
+pub async fn fetch_namespaces_to_skip(
+    solver_base_url: Url,
+    namespaces_to_skip: Option<NamespacesToSkip>,
+) -> Option<NamespacesToSkip> {
     let solver_client = connect_to_solver(solver_base_url);
-    match solver_client
-        .get::<Vec<RollupRegistration>>("rollup_registrations")
-        .send()
-        .await
-    {
-        Ok(registrations) => {
-            let mut namespaces_to_skip = HashSet::new();
-            for registration in registrations {
-                if registration.body.reserve_url.is_some() || !registration.body.active {
-                    namespaces_to_skip.insert(registration.body.namespace_id);
-                }
+    let mut namespaces_to_skip = match namespaces_to_skip {
+        Some(namespaces_to_skip) => namespaces_to_skip,
+        None => match solver_client
+            .get::<RollupRegistrationsSnapshot>("rollup_registrations_snapshot")
+            .send()
+            .await
+        {
+            Ok(snapshot) => NamespacesToSkip::from_snapshot(snapshot),
+            Err(e) => {
+                error!("Failed to get the rollup registrations: {:?}.", e);
+                return None;
+            }
+        },
+    };
+
+    loop {
+        match solver_client
+            .get::<RollupRegistrationChanges>(&format!(
+                "rollup_registration_changes/{}",
+                namespaces_to_skip.cursor
+            ))
+            .send()
+            .await
+        {
+            Ok(changes) if changes.changes.is_empty() => break,
+            Ok(changes) => namespaces_to_skip.apply(changes),
+            Err(e) => {
+                error!("Failed to get the rollup registration changes: {:?}.", e);
+                return None;
             }
-            Some(namespaces_to_skip)
-        }
-        Err(e) => {
-            error!("Failed to get the registered rollups: {:?}.", e);
-            None
         }
     }
+
+    namespaces_to_skip.refreshed_at = Instant::now();
+    Some(namespaces_to_skip)
 }
 
 /// Reserve builder hooks for espresso sequencer.
 ///
 /// Provides bidding and transaction filtering on top of base builder functionality.
+#[derive(Clone)]
 pub(crate) struct EspressoReserveHooks {
     /// IDs of namespaces to filter and bid for
     pub(crate) namespaces: HashSet<NamespaceId>,
@@ -87,8 +208,40 @@ pub(crate) struct EspressoReserveHooks {
     pub(crate) builder_api_base_url: Url,
     /// Keys for bidding
     pub(crate) bid_key_pair: EthKeyPair,
-    /// Bid amount
-    pub(crate) bid_amount: FeeAmount,
+    /// Strategy deciding the bid for each view
+    pub(crate) bid_strategy: Arc<dyn BidStrategy>,
+    /// Maximum amount to bid for a single view
+    pub(crate) bid_budget: FeeAmount,
+    /// Base fee of the next block, used to value pending transactions
+    pub(crate) base_fee: Arc<RwLock<FeeAmount>>,
+    /// Chain config used to compute the base fee of the next block, unless a decided header
+    /// includes the full chain config
+    pub(crate) chain_config: ChainConfig,
+    /// Bidding state shared with the tasks submitting bids
+    pub(crate) bid_state: Arc<Mutex<BidState>>,
+    /// Results of the auctions, as pushed by the solver when each closes
+    pub(crate) auction_results: SolverAuctionResultsProvider,
+}
+
+/// Information the reserve builder gathers to inform its bids.
+#[derive(Debug, Default)]
+pub(crate) struct BidState {
+    /// Fees offered by transactions received since the last bid, per namespace
+    pub(crate) pending_value: HashMap<NamespaceId, FeeAmount>,
+    /// Amounts of submitted bids whose auctions have not been checked yet, by view
+    pub(crate) submitted_bids: BTreeMap<ViewNumber, FeeAmount>,
+    /// Outcomes of the most recent auctions we bid in, oldest first
+    pub(crate) recent_outcomes: VecDeque<AuctionOutcome>,
+}
+
+impl BidState {
+    /// Add back the value of pending transactions taken by a bid which was not submitted.
+    fn restore_pending_value(&mut self, pending_value: HashMap<NamespaceId, FeeAmount>) {
+        for (ns, value) in pending_value {
+            let total = self.pending_value.entry(ns).or_default();
+            *total = *total + value;
+        }
+    }
 }
 
 #[async_trait]
@@ -99,64 +252,155 @@ impl BuilderHooks<SeqTypes> for EspressoReserveHooks {
         mut transactions: Vec<<SeqTypes as NodeType>::Transaction>,
     ) -> Vec<<SeqTypes as NodeType>::Transaction> {
         transactions.retain(|txn| self.namespaces.contains(&txn.namespace()));
+
+        let base_fee = *self.base_fee.read().await;
+        let mut bid_state = self.bid_state.lock().await;
+        for txn in &transactions {
+            // Value each transaction at what it would pay in a block of its own, so that namespace
+            // fee multipliers are taken into account.
+            let size = txn.minimum_block_size();
+            let mut ns_table = NsTableBuilder::new();
+            ns_table.append_entry(txn.namespace(), size as usize);
+            let fee = self
+                .chain_config
+                .block_fee(base_fee, &ns_table.into_ns_table(), size as u32);
+            let value = bid_state.pending_value.entry(txn.namespace()).or_default();
+            *value = *value + fee;
+        }
+        drop(bid_state);
+
         transactions
     }
 
     #[inline(always)]
     async fn handle_hotshot_event(&self, event: &Event<SeqTypes>) {
-        let EventType::ViewFinished { view_number } = event.event else {
-            return;
-        };
+        match &event.event {
+            EventType::ViewFinished { view_number } => {
+                let view_number = *view_number;
+                let hooks = self.clone();
+                spawn(async move { hooks.bid_after_view(view_number).await });
+            }
+            EventType::Decide { leaf_chain, .. } => {
+                // Leaves in a decide event are ordered from newest to oldest, so the first one is
+                // the parent of the next block.
+                if let Some(info) = leaf_chain.first() {
+                    let parent = info.leaf.block_header();
+                    let chain_config = parent.chain_config().resolve().unwrap_or(self.chain_config);
+                    *self.base_fee.write().await = parent.next_base_fee(&chain_config);
+                }
+            }
+            _ => {}
+        }
+    }
+}
 
+impl EspressoReserveHooks {
+    /// Learn from the auction for the view that just finished, then bid for an upcoming view.
+    ///
+    /// The bid state is only locked to read or update it, never while waiting on the solver.
+    pub(crate) async fn bid_after_view(&self, view_number: ViewNumber) {
         let fee_account = self.bid_key_pair.fee_account();
-        let bid_amount = self.bid_amount;
-        let namespaces = self.namespaces.iter().cloned().collect();
-        let builder_api_base_url = self.builder_api_base_url.clone();
-        let bid_key_pair = self.bid_key_pair.clone();
-        let solver_base_url = self.solver_base_url.clone();
+        let mut namespaces: Vec<_> = self.namespaces.iter().cloned().collect();
+        namespaces.sort();
 
-        spawn(async move {
-            let bid_tx = match BidTxBody::new(
-                fee_account,
-                bid_amount,
-                view_number + 3, // We submit a bid 3 views in advance.
-                namespaces,
-                builder_api_base_url,
-                Default::default(),
-            )
-            .signed(&bid_key_pair)
-            {
-                Ok(bid) => bid,
+        // Learn from the auction for the view that just finished, if we bid in it.
+        let submitted = {
+            let mut bid_state = self.bid_state.lock().await;
+            bid_state
+                .submitted_bids
+                .retain(|view, _| *view >= view_number);
+            bid_state.submitted_bids.remove(&view_number)
+        };
+        if let Some(amount) = submitted {
+            match self.auction_results.fetch_auction_result(view_number).await {
+                Ok(results) => {
+                    let outcome =
+                        AuctionOutcome::from_results(&results, fee_account, amount, &namespaces);
+                    let mut bid_state = self.bid_state.lock().await;
+                    bid_state.recent_outcomes.push_back(outcome);
+                    if bid_state.recent_outcomes.len() > AUCTION_HISTORY_LEN {
+                        bid_state.recent_outcomes.pop_front();
+                    }
+                }
                 Err(e) => {
-                    error!("Failed to sign the bid txn: {:?}.", e);
-                    return;
+                    error!("Failed to get the auction results: {:?}.", e);
                 }
+            }
+        }
+
+        // The bid accounts for the transactions received so far, so it takes their value.
+        let (bid, pending_value) = {
+            let mut bid_state = self.bid_state.lock().await;
+            let Some(bid) = self.bid_strategy.bid(&BidContext {
+                view_number,
+                namespaces: &namespaces,
+                pending_value: &bid_state.pending_value,
+                recent_outcomes: &bid_state.recent_outcomes,
+                budget: self.bid_budget,
+            }) else {
+                info!("Not bidding after view {}", *view_number);
+                return;
             };
+            (bid, std::mem::take(&mut bid_state.pending_value))
+        };
 
-            let solver_client = connect_to_solver(solver_base_url);
-            if let Err(e) = solver_client
-                .post::<()>("submit_bid")
-                .body_json(&bid_tx)
-                .unwrap()
-                .send()
-                .await
-            {
-                error!("Failed to submit the bid: {:?}.", e);
+        let bid_tx = match BidTxBody::new(
+            fee_account,
+            bid.amount,
+            bid.view,
+            bid.namespaces,
+            self.builder_api_base_url.clone(),
+            Default::default(),
+        )
+        .signed(&self.bid_key_pair)
+        {
+            Ok(bid) => bid,
+            Err(e) => {
+                error!("Failed to sign the bid txn: {:?}.", e);
+                self.bid_state
+                    .lock()
+                    .await
+                    .restore_pending_value(pending_value);
                 return;
             }
+        };
 
-            info!("Submitted bid for view {}", *view_number);
-        });
+        if let Err(e) = connect_to_solver(self.solver_base_url.clone())
+            .post::<()>("submit_bid")
+            .body_json(&bid_tx)
+            .unwrap()
+            .send()
+            .await
+        {
+            error!("Failed to submit the bid: {:?}.", e);
+            self.bid_state
+                .lock()
+                .await
+                .restore_pending_value(pending_value);
+            return;
+        }
+        self.bid_state
+            .lock()
+            .await
+            .submitted_bids
+            .insert(bid.view, bid.amount);
+
+        info!("Submitted bid of {} for view {}", bid.amount, *bid.view);
     }
 }
 
 /// Fallback builder hooks for espresso sequencer.
 ///
 /// Provides transaction filtering on top of base builder functionality for unregistered rollups.
+#[derive(Clone)]
 pub(crate) struct EspressoFallbackHooks {
     /// Base URL to contact the solver.
     pub(crate) solver_base_url: Url,
-    pub(crate) namespaces_to_skip: Arc<RwLock<Option<HashSet<NamespaceId>>>>,
+    /// Last namespaces to skip fetched from the solver, if it has ever been reached.
+    pub(crate) namespaces_to_skip: Arc<RwLock<Option<NamespacesToSkip>>>,
+    pub(crate) config: FallbackConfig,
+    /// Whether a query to the solver is in progress.
+    pub(crate) syncing: Arc<AtomicBool>,
 }
 
 #[async_trait]
@@ -169,13 +413,25 @@ impl BuilderHooks<SeqTypes> for EspressoFallbackHooks {
         let namespaces_to_skip = self.namespaces_to_skip.read().await;
 
         match namespaces_to_skip.as_ref() {
-            Some(namespaces_to_skip) => {
-                transactions.retain(|txn| !namespaces_to_skip.contains(&txn.namespace()));
+            // Keep building with the last known namespaces through short solver outages
+            Some(namespaces_to_skip)
+                if namespaces_to_skip.refreshed_at.elapsed() <= self.config.staleness_window =>
+            {
+                transactions
+                    .retain(|txn| !namespaces_to_skip.namespaces.contains(&txn.namespace()));
                 transactions
             }
-            // Solver connection has failed and we don't have up-to-date information on this
+            // Solver connection has failed for too long and we don't have up-to-date information
+            Some(namespaces_to_skip) => {
+                error!(
+                    "Not accepting transactions due to outdated information, last refreshed {:?} ago",
+                    namespaces_to_skip.refreshed_at.elapsed()
+                );
+                Vec::new()
+            }
+            // Solver connection has never succeeded
             None => {
-                error!("Not accepting transactions due to outdated information");
+                error!("Not accepting transactions due to missing information");
                 Vec::new()
             }
         }
@@ -187,17 +443,303 @@ impl BuilderHooks<SeqTypes> for EspressoFallbackHooks {
             return;
         };
 
-        // Re-query the solver every 20 views
-        if view_number.rem_euclid(20) != 0 {
+        // Don't pile up queries while the solver is slow to respond
+        if self.syncing.swap(true, Ordering::AcqRel) {
             return;
         }
 
-        let solver_base_url = self.solver_base_url.clone();
-        let namespaces_to_skip_lock = Arc::clone(&self.namespaces_to_skip);
-
+        let hooks = self.clone();
         spawn(async move {
-            let namespaces_to_skip = fetch_namespaces_to_skip(solver_base_url).await;
-            *namespaces_to_skip_lock.write().await = namespaces_to_skip;
+            hooks.refresh_namespaces_to_skip(view_number).await;
+            hooks.syncing.store(false, Ordering::Release);
         });
     }
 }
+
+impl EspressoFallbackHooks {
+    /// Update the namespaces to skip after `view_number` finished.
+    ///
+    /// On failure, the last known namespaces are kept until they become stale.
+    pub(crate) async fn refresh_namespaces_to_skip(&self, view_number: ViewNumber) {
+        // Fetch the registration changes every view, and rebuild the list from a snapshot of the
+        // registrations periodically in case a change was missed.
+        let resync = self.config.resync_interval == 0
+            || view_number.rem_euclid(self.config.resync_interval) == 0;
+        let namespaces_to_skip = if resync {
+            None
+        } else {
+            self.namespaces_to_skip.read().await.clone()
+        };
+
+        if let Some(namespaces_to_skip) =
+            fetch_namespaces_to_skip(self.solver_base_url.clone(), namespaces_to_skip).await
+        {
+            *self.namespaces_to_skip.write().await = Some(namespaces_to_skip);
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "embedded-db")))]
+mod test {
+    use committable::Committable;
+    use espresso_types::{
+        v0_99::{RollupRegistration, RollupRegistrationBody},
+        Transaction,
+    };
+    use hotshot::{
+        rand,
+        types::{BLSPubKey, SignatureKey},
+    };
+    use hotshot_types::traits::node_implementation::ConsensusTime;
+    use marketplace_solver::testing::MockSolver;
+    use portpicker::pick_unused_port;
+    use sequencer_utils::test_utils::setup_test;
+
+    use super::*;
+    use crate::bid_strategy::{FixedBidStrategy, DEFAULT_BID_LOOKAHEAD};
+
+    const NAMESPACE: u64 = 10;
+    const RESERVE_PRICE: u64 = 200;
+
+    /// Register an active rollup with the solver.
+    async fn register_rollup(solver_base_url: Url, namespace: u64, reserve_url: Option<&str>) {
+        let private_key =
+            <BLSPubKey as SignatureKey>::PrivateKey::generate(&mut rand::thread_rng());
+        let signature_key = BLSPubKey::from_private(&private_key);
+        let body = RollupRegistrationBody {
+            namespace_id: namespace.into(),
+            reserve_url: reserve_url.map(|url| url.parse().unwrap()),
+            reserve_price: RESERVE_PRICE.into(),
+            active: true,
+            signature_keys: vec![signature_key],
+            text: "test".to_string(),
+            signature_key,
+        };
+        let signature = BLSPubKey::sign(&private_key, body.commit().as_ref()).unwrap();
+
+        let _: RollupRegistration = connect_to_solver(solver_base_url)
+            .post("register_rollup")
+            .body_json(&RollupRegistration { body, signature })
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+    }
+
+    fn reserve_hooks(solver_base_url: Url) -> EspressoReserveHooks {
+        EspressoReserveHooks {
+            namespaces: [NamespaceId::from(NAMESPACE)].into(),
+            auction_results: auction_results_provider(solver_base_url.clone()),
+            solver_base_url,
+            builder_api_base_url: "http://builder".parse().unwrap(),
+            bid_key_pair: EthKeyPair::random(),
+            bid_strategy: Arc::new(FixedBidStrategy {
+                amount: RESERVE_PRICE.into(),
+                lookahead: DEFAULT_BID_LOOKAHEAD,
+            }),
+            bid_budget: RESERVE_PRICE.into(),
+            base_fee: RwLock::new(1.into()).into(),
+            chain_config: Default::default(),
+            bid_state: Default::default(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reserve_hooks_bid() {
+        setup_test();
+
+        let solver = MockSolver::init().await;
+        register_rollup(
+            solver.solver_url.clone(),
+            NAMESPACE,
+            Some("http://localhost"),
+        )
+        .await;
+        let hooks = reserve_hooks(solver.solver_url.clone());
+
+        // Only transactions for our namespaces are kept, and they count towards the next bid.
+        let transactions = hooks
+            .process_transactions(vec![
+                Transaction::new(NAMESPACE.into(), vec![1, 2, 3]),
+                Transaction::new((NAMESPACE + 1).into(), vec![1, 2, 3]),
+            ])
+            .await;
+        assert_eq!(transactions.len(), 1);
+        assert!(!hooks.bid_state.lock().await.pending_value.is_empty());
+
+        // Bid after the latest view finished.
+        let view = solver.state().read().await.solver().latest_view;
+        hooks.bid_after_view(view).await;
+
+        let bid_view = view + DEFAULT_BID_LOOKAHEAD;
+        {
+            let bid_state = hooks.bid_state.lock().await;
+            assert!(bid_state.pending_value.is_empty());
+            assert_eq!(
+                bid_state.submitted_bids,
+                BTreeMap::from([(bid_view, FeeAmount::from(RESERVE_PRICE))])
+            );
+        }
+        {
+            let state = solver.state();
+            let state = state.read().await;
+            let bid = &state.solver().bid_txs[&bid_view][&hooks.bid_key_pair.fee_account()];
+            assert_eq!(bid.amount(), RESERVE_PRICE.into());
+        }
+
+        // Once the view we bid for finishes, we learn that we won.
+        hooks.bid_after_view(bid_view).await;
+
+        let bid_state = hooks.bid_state.lock().await;
+        assert_eq!(
+            bid_state.recent_outcomes,
+            [AuctionOutcome {
+                view: bid_view,
+                bid: RESERVE_PRICE.into(),
+                won: true,
+                highest_competing_bid: None,
+            }]
+        );
+        assert!(!bid_state.submitted_bids.contains_key(&bid_view));
+        assert!(bid_state
+            .submitted_bids
+            .contains_key(&(bid_view + DEFAULT_BID_LOOKAHEAD)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reserve_hooks_solver_unreachable() {
+        setup_test();
+
+        let port = pick_unused_port().expect("no free port");
+        let hooks = reserve_hooks(format!("http://localhost:{port}").parse().unwrap());
+
+        hooks
+            .process_transactions(vec![Transaction::new(NAMESPACE.into(), vec![1, 2, 3])])
+            .await;
+        let pending_value = hooks.bid_state.lock().await.pending_value.clone();
+
+        // A bid which cannot be submitted leaves the pending value for the next one.
+        hooks.bid_after_view(ViewNumber::new(1)).await;
+
+        let bid_state = hooks.bid_state.lock().await;
+        assert_eq!(bid_state.pending_value, pending_value);
+        assert!(bid_state.submitted_bids.is_empty());
+    }
+
+    fn fallback_hooks(
+        solver_base_url: Url,
+        namespaces_to_skip: Option<NamespacesToSkip>,
+    ) -> EspressoFallbackHooks {
+        EspressoFallbackHooks {
+            solver_base_url,
+            namespaces_to_skip: Arc::new(RwLock::new(namespaces_to_skip)),
+            config: FallbackConfig {
+                staleness_window: Duration::from_secs(60),
+                resync_interval: 10,
+            },
+            syncing: Default::default(),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fallback_hooks_namespaces_to_skip() {
+        setup_test();
+
+        let solver = MockSolver::init().await;
+        register_rollup(
+            solver.solver_url.clone(),
+            NAMESPACE,
+            Some("http://localhost"),
+        )
+        .await;
+        let hooks = fallback_hooks(solver.solver_url.clone(), None);
+
+        // Resynchronizing starts from a snapshot of the registrations.
+        hooks.refresh_namespaces_to_skip(ViewNumber::new(10)).await;
+        let namespaces_to_skip = hooks.namespaces_to_skip.read().await.clone().unwrap();
+        assert_eq!(
+            namespaces_to_skip.namespaces,
+            [NamespaceId::from(NAMESPACE)].into()
+        );
+        assert_eq!(namespaces_to_skip.cursor, 1);
+
+        // In between, only the changes are fetched.
+        register_rollup(solver.solver_url.clone(), NAMESPACE + 1, None).await;
+        register_rollup(
+            solver.solver_url.clone(),
+            NAMESPACE + 2,
+            Some("http://localhost"),
+        )
+        .await;
+        hooks.refresh_namespaces_to_skip(ViewNumber::new(11)).await;
+        let namespaces_to_skip = hooks.namespaces_to_skip.read().await.clone().unwrap();
+        assert_eq!(
+            namespaces_to_skip.namespaces,
+            [
+                NamespaceId::from(NAMESPACE),
+                NamespaceId::from(NAMESPACE + 2)
+            ]
+            .into()
+        );
+        assert_eq!(namespaces_to_skip.cursor, 3);
+
+        // Both paths agree.
+        hooks.refresh_namespaces_to_skip(ViewNumber::new(20)).await;
+        let resynced = hooks.namespaces_to_skip.read().await.clone().unwrap();
+        assert_eq!(resynced.namespaces, namespaces_to_skip.namespaces);
+        assert_eq!(resynced.cursor, namespaces_to_skip.cursor);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fallback_hooks_solver_unreachable() {
+        setup_test();
+
+        let port = pick_unused_port().expect("no free port");
+        let solver_base_url: Url = format!("http://localhost:{port}").parse().unwrap();
+        let registered = Transaction::new(NAMESPACE.into(), vec![1, 2, 3]);
+        let unregistered = Transaction::new((NAMESPACE + 1).into(), vec![1, 2, 3]);
+
+        // Without ever reaching the solver, no transactions are accepted.
+        let hooks = fallback_hooks(solver_base_url.clone(), None);
+        hooks.refresh_namespaces_to_skip(ViewNumber::new(10)).await;
+        assert!(hooks.namespaces_to_skip.read().await.is_none());
+        assert!(hooks
+            .process_transactions(vec![registered.clone(), unregistered.clone()])
+            .await
+            .is_empty());
+
+        // The last known namespaces are kept while the solver cannot be reached...
+        let hooks = fallback_hooks(
+            solver_base_url,
+            Some(NamespacesToSkip {
+                namespaces: [NamespaceId::from(NAMESPACE)].into(),
+                cursor: 1,
+                refreshed_at: Instant::now(),
+            }),
+        );
+        for view in [10, 11] {
+            hooks
+                .refresh_namespaces_to_skip(ViewNumber::new(view))
+                .await;
+            assert_eq!(
+                hooks
+                    .process_transactions(vec![registered.clone(), unregistered.clone()])
+                    .await,
+                vec![unregistered.clone()]
+            );
+        }
+
+        // ...until they become stale.
+        hooks
+            .namespaces_to_skip
+            .write()
+            .await
+            .as_mut()
+            .unwrap()
+            .refreshed_at = Instant::now() - hooks.config.staleness_window - Duration::from_secs(1);
+        assert!(hooks
+            .process_transactions(vec![registered, unregistered])
+            .await
+            .is_empty());
+    }
+}
@@ -71,6 +71,8 @@ use tide_disco::{app, method::ReadState, App, Url};
 use tracing::error;
 use vbs::version::{StaticVersion, StaticVersionType};
 
+pub mod bid_strategy;
+
 pub mod builder;
 
 pub mod hooks;
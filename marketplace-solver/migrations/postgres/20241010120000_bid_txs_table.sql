@@ -0,0 +1,6 @@
+CREATE TABLE bid_txs (
+    view_number BIGINT NOT NULL,
+    account BYTEA NOT NULL,
+    data BYTEA NOT NULL,
+    PRIMARY KEY (view_number, account)
+);
@@ -0,0 +1,9 @@
+ALTER TABLE rollup_registrations ADD COLUMN nonce BIGINT NOT NULL DEFAULT 0;
+
+CREATE TABLE rollup_registration_history (
+    id BIGSERIAL PRIMARY KEY,
+    namespace_id BIGINT NOT NULL,
+    data BYTEA NOT NULL
+);
+
+CREATE INDEX rollup_registration_history_namespace_id ON rollup_registration_history (namespace_id, id);
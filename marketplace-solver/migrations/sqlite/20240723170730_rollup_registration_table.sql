@@ -0,0 +1,4 @@
+CREATE TABLE rollup_registrations (
+    namespace_id INTEGER PRIMARY KEY,
+    data BLOB NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE bid_txs (
+    view_number INTEGER NOT NULL,
+    account BLOB NOT NULL,
+    data BLOB NOT NULL,
+    PRIMARY KEY (view_number, account)
+);
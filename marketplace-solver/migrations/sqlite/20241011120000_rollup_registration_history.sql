@@ -0,0 +1,9 @@
+ALTER TABLE rollup_registrations ADD COLUMN nonce INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE rollup_registration_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    namespace_id INTEGER NOT NULL,
+    data BLOB NOT NULL
+);
+
+CREATE INDEX rollup_registration_history_namespace_id ON rollup_registration_history (namespace_id, id);
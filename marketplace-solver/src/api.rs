@@ -5,9 +5,9 @@ use std::{
 
 use espresso_types::{
     v0_99::{BidTx, RollupRegistration, RollupUpdate},
-    NamespaceId,
+    FeeAmount, NamespaceId,
 };
-use futures::FutureExt;
+use futures::{FutureExt, StreamExt, TryFutureExt};
 use hotshot_types::{data::ViewNumber, traits::node_implementation::ConsensusTime};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -19,7 +19,7 @@ use tide_disco::{
 use toml::{map::Entry, Value};
 use vbs::version::StaticVersionType;
 
-use crate::state::UpdateSolverState;
+use crate::state::{fee_balance, UpdateSolverState};
 
 #[derive(Debug, Error, Serialize, Deserialize)]
 pub enum SolverError {
@@ -31,6 +31,37 @@ pub enum SolverError {
     SignatureKeysMismatch(String),
     #[error("Signature key {0} does not match signatures in the database")]
     SignatureDatabaseKeysMismatch(String),
+    #[error("Signer is not in the stake table: {0}")]
+    SignerNotInStakeTable(String),
+    #[error("update nonce {nonce} must be greater than the current nonce {current}")]
+    StaleNonce { nonce: u64, current: u64 },
+    #[error("bid is for view {view} whose auction has closed (latest view {latest})")]
+    BidViewPassed { view: u64, latest: u64 },
+    #[error("bid is for view {view} which is too far ahead (maximum view {max})")]
+    BidViewTooFarAhead { view: u64, max: u64 },
+    #[error("bid does not include any namespaces")]
+    BidNoNamespaces,
+    #[error("bid namespace is not registered: {0}")]
+    BidNamespaceNotRegistered(NamespaceId),
+    #[error("bid namespace is not active: {0}")]
+    BidNamespaceInactive(NamespaceId),
+    #[error("bid amount {amount} is below the reserve price {reserve_price}")]
+    BidBelowReservePrice {
+        amount: FeeAmount,
+        reserve_price: FeeAmount,
+    },
+    #[error("total reserve price of the bid namespaces overflows")]
+    BidReservePriceOverflow,
+    #[error("bid amount {amount} plus gas price {gas_price} overflows")]
+    BidAmountOverflow {
+        amount: FeeAmount,
+        gas_price: FeeAmount,
+    },
+    #[error("fee account balance {balance} cannot pay the bid and gas price {required}")]
+    BidInsufficientBalance {
+        balance: FeeAmount,
+        required: FeeAmount,
+    },
     #[error("bincode err: {0}")]
     BincodeError(String),
     #[error("database err: {0}")]
@@ -64,6 +95,7 @@ impl tide_disco::Error for SolverError {
     fn status(&self) -> StatusCode {
         match self {
             Self::Custom { status, .. } => *status,
+            Self::SignerNotInStakeTable(_) => StatusCode::UNAUTHORIZED,
             _ => StatusCode::BAD_REQUEST,
         }
     }
@@ -94,10 +126,20 @@ where
         options.extensions.clone(),
     )?;
 
-    api.post("submit_bid", |req, state| {
+    api.at("submit_bid", |req, state| {
         async move {
             let bid = req.body_json::<BidTx>()?;
-            state.submit_bid_tx(bid).await
+
+            // Fetching the balance is a round trip to a sequencer node, so do it before locking
+            // the state, so that a slow node does not hold up every other solver request.
+            let fee_balances = state
+                .read(|state| async move { state.fee_balances() }.boxed())
+                .await;
+            let balance = fee_balance(&*fee_balances, bid.account()).await?;
+
+            state
+                .write(|state| state.submit_bid_tx(bid, balance).boxed())
+                .await
         }
         .boxed()
     })?
@@ -113,13 +155,28 @@ where
     .get("auction_results_permissioned", |req, state| {
         async move {
             let view_num: u64 = req.integer_param("view_number")?;
+            let signer = req.blob_param("signer")?;
             let signature = req.blob_param("signature")?;
             state
-                .calculate_auction_results_permissioned(ViewNumber::new(view_num), signature)
+                .calculate_auction_results_permissioned(
+                    ViewNumber::new(view_num),
+                    signer,
+                    signature,
+                )
                 .await
         }
         .boxed()
     })?
+    .stream("auction_results_stream", |_req, state| {
+        async move {
+            let results = state
+                .read(|state| async move { state.subscribe_auction_results() }.boxed())
+                .await;
+            Ok(results.map(Ok))
+        }
+        .try_flatten_stream()
+        .boxed()
+    })?
     .post("register_rollup", |req, state| {
         async move {
             let body = req.body_json::<RollupRegistration>()?;
@@ -136,6 +193,25 @@ where
     })?
     .get("rollup_registrations", |_req, state| {
         async move { state.get_all_rollup_registrations().await }.boxed()
+    })?
+    .get("rollup_registration_history", |req, state| {
+        async move {
+            let namespace_id: u64 = req.integer_param("namespace_id")?;
+            state
+                .get_rollup_registration_history(namespace_id.into())
+                .await
+        }
+        .boxed()
+    })?
+    .get("rollup_registration_changes", |req, state| {
+        async move {
+            let cursor: u64 = req.integer_param("cursor")?;
+            state.get_rollup_registration_changes(cursor).await
+        }
+        .boxed()
+    })?
+    .get("rollup_registrations_snapshot", |_req, state| {
+        async move { state.get_rollup_registrations_snapshot().await }.boxed()
     })?;
     Ok(api)
 }
@@ -1,19 +1,32 @@
 use anyhow::Context;
-use sqlx::{
-    pool::PoolConnection,
-    postgres::{PgConnectOptions, PgPoolOptions, PgSslMode},
-    ConnectOptions, Error, PgPool, Postgres,
-};
-use tide_disco::Url;
+use sqlx::{pool::PoolConnection, Error, Pool};
 
 use crate::{DatabaseOptions, SolverError};
 
-// PgPool is wrapped in an Arc internally so cloning here increments the reference count
+/// The database backend used by the solver.
+///
+/// This is Postgres by default, or SQLite when the `embedded-db` feature is enabled.
+#[cfg(not(feature = "embedded-db"))]
+pub type Db = sqlx::Postgres;
+#[cfg(feature = "embedded-db")]
+pub type Db = sqlx::Sqlite;
+
+pub type DbPool = Pool<Db>;
+pub type DbConnection = <Db as sqlx::Database>::Connection;
+
+// Pool is wrapped in an Arc internally so cloning here increments the reference count
 #[derive(Clone)]
-pub struct PostgresClient(PgPool);
+pub struct DatabaseClient(DbPool);
 
-impl PostgresClient {
+impl DatabaseClient {
+    #[cfg(not(feature = "embedded-db"))]
     pub async fn connect(opts: DatabaseOptions) -> anyhow::Result<Self> {
+        use sqlx::{
+            postgres::{PgConnectOptions, PgPoolOptions, PgSslMode},
+            ConnectOptions,
+        };
+        use tide_disco::Url;
+
         let DatabaseOptions {
             url,
             host,
@@ -85,17 +98,80 @@ impl PostgresClient {
         }
 
         if migrations {
-            sqlx::migrate!("./migrations").run(&connection).await?;
+            sqlx::migrate!("./migrations/postgres")
+                .run(&connection)
+                .await?;
         }
 
         Ok(Self(connection))
     }
 
-    pub fn pool(&self) -> &PgPool {
+    #[cfg(feature = "embedded-db")]
+    pub async fn connect(opts: DatabaseOptions) -> anyhow::Result<Self> {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+
+        let DatabaseOptions {
+            url,
+            path,
+            max_connections,
+            acquire_timeout,
+            migrations,
+            reset,
+        } = opts;
+
+        let mut options = SqlitePoolOptions::new();
+
+        let connect_opts: SqliteConnectOptions = match url {
+            Some(url) => url.parse()?,
+            None => {
+                let path = path.context("sqlite path not provided")?;
+                SqliteConnectOptions::new().filename(path)
+            }
+        };
+
+        if reset {
+            // In WAL mode, recent writes live in `-wal` and `-shm` files next to the database.
+            // Left behind, they would be replayed into the new database.
+            let path = connect_opts.get_filename();
+            for suffix in ["", "-wal", "-shm"] {
+                let mut file = path.as_os_str().to_owned();
+                file.push(suffix);
+                let file = std::path::Path::new(&file);
+                if file.exists() {
+                    std::fs::remove_file(file)
+                        .with_context(|| format!("failed to remove database file {file:?}"))?;
+                }
+            }
+        }
+
+        let connect_opts = connect_opts
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+
+        if let Some(max_connections) = max_connections {
+            options = options.max_connections(max_connections);
+        }
+
+        if let Some(acquire_timeout) = acquire_timeout {
+            options = options.acquire_timeout(acquire_timeout);
+        }
+
+        let connection = options.connect_with(connect_opts).await?;
+
+        if migrations {
+            sqlx::migrate!("./migrations/sqlite")
+                .run(&connection)
+                .await?;
+        }
+
+        Ok(Self(connection))
+    }
+
+    pub fn pool(&self) -> &DbPool {
         &self.0
     }
 
-    pub async fn acquire(&self) -> Result<PoolConnection<Postgres>, Error> {
+    pub async fn acquire(&self) -> Result<PoolConnection<Db>, Error> {
         self.0.acquire().await
     }
 }
@@ -106,26 +182,20 @@ impl From<sqlx::Error> for SolverError {
     }
 }
 
-#[cfg(all(
-    any(test, feature = "testing"),
-    not(target_os = "windows"),
-    not(feature = "embedded-db")
-))]
+#[cfg(all(any(test, feature = "testing"), not(target_os = "windows")))]
 pub mod mock {
     use hotshot_query_service::data_source::sql::testing::TmpDb;
 
-    use super::PostgresClient;
+    use super::DatabaseClient;
     use crate::DatabaseOptions;
 
-    pub async fn setup_mock_database() -> (TmpDb, PostgresClient) {
-        let db: TmpDb = TmpDb::init().await;
-        let host = db.host();
-        let port = db.port();
-
-        let opts = DatabaseOptions {
+    /// Options for connecting to a temporary database.
+    #[cfg(not(feature = "embedded-db"))]
+    pub fn mock_database_options(db: &TmpDb) -> DatabaseOptions {
+        DatabaseOptions {
             url: None,
-            host: Some(host),
-            port: Some(port),
+            host: Some(db.host()),
+            port: Some(db.port()),
             db_name: None,
             username: Some("postgres".to_string()),
             password: Some("password".to_string()),
@@ -134,20 +204,39 @@ pub mod mock {
             require_ssl: false,
             migrations: true,
             reset: false,
-        };
+        }
+    }
+
+    /// Options for connecting to a temporary database.
+    #[cfg(feature = "embedded-db")]
+    pub fn mock_database_options(db: &TmpDb) -> DatabaseOptions {
+        DatabaseOptions {
+            url: None,
+            path: Some(db.path()),
+            max_connections: Some(10),
+            acquire_timeout: None,
+            migrations: true,
+            reset: false,
+        }
+    }
 
-        // TmpDb will be dropped, which will cause the Docker container to be killed.
+    pub async fn setup_mock_database() -> (TmpDb, DatabaseClient) {
+        let db: TmpDb = TmpDb::init().await;
+        let opts = mock_database_options(&db);
+
+        // TmpDb will be dropped, which will cause the Docker container to be killed
+        // (or the SQLite file to be removed).
         // Therefore, it is returned and kept in scope until needed.
         (
             db,
-            PostgresClient::connect(opts)
+            DatabaseClient::connect(opts)
                 .await
                 .expect("failed to connect to database"),
         )
     }
 }
 
-#[cfg(all(test, not(target_os = "windows"), not(feature = "embedded-db")))]
+#[cfg(all(test, not(target_os = "windows")))]
 mod test {
     use crate::database::mock::setup_mock_database;
     use hotshot::helpers::initialize_logging;
@@ -163,4 +252,34 @@ mod test {
 
         drop(tmpdb);
     }
+
+    #[cfg(feature = "embedded-db")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_database_reset() {
+        use crate::database::{mock::mock_database_options, DatabaseClient};
+
+        initialize_logging();
+
+        let (tmpdb, client) = setup_mock_database().await;
+        sqlx::query("INSERT INTO bid_txs VALUES ($1, $2, $3);")
+            .bind(1_i64)
+            .bind(vec![0_u8; 20])
+            .bind(vec![0_u8])
+            .execute(client.pool())
+            .await
+            .unwrap();
+
+        // Reset while the old connections are still open, so the write is still in the WAL.
+        let reset = DatabaseClient::connect(mock_database_options(&tmpdb).reset())
+            .await
+            .unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bid_txs;")
+            .fetch_one(reset.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+
+        drop(client);
+        drop(tmpdb);
+    }
 }
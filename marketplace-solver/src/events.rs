@@ -47,7 +47,7 @@ impl EventsServiceClient {
 
 pub async fn handle_events(
     mut stream: Pin<Box<dyn Stream<Item = Result<Event<SeqTypes>, events::Error>> + Send>>,
-    _state: Arc<RwLock<GlobalState>>,
+    state: Arc<RwLock<GlobalState>>,
 ) -> anyhow::Result<()> {
     while let Some(event) = stream.next().await {
         let event = event?;
@@ -58,7 +58,10 @@ pub async fn handle_events(
         #[allow(clippy::single_match)]
         match event.event {
             hotshot::types::EventType::ViewFinished { view_number } => {
-                tracing::debug!("received view finished event {view_number:?}")
+                tracing::debug!("received view finished event {view_number:?}");
+                if let Err(err) = state.write().await.view_finished(view_number).await {
+                    tracing::warn!("failed to prune bids for view {view_number:?}: {err}");
+                }
             }
             _ => (),
         }
@@ -5,9 +5,10 @@ use async_lock::RwLock;
 use clap::Parser;
 use espresso_types::MarketplaceVersion;
 use hotshot::helpers::initialize_logging;
+use hotshot_types::{data::ViewNumber, traits::node_implementation::ConsensusTime};
 use marketplace_solver::{
     define_api, handle_events,
-    state::{GlobalState, SolverState, StakeTable},
+    state::{FeeStateClient, GlobalState, SolverState, StakeTable},
     EventsServiceClient, Options, SolverError,
 };
 use tide_disco::App;
@@ -22,6 +23,10 @@ async fn main() -> anyhow::Result<()> {
     let Options {
         solver_api_port,
         events_api_url,
+        sequencer_url,
+        chain_id,
+        public_url,
+        bid_retention_views,
         database_options,
     } = args;
 
@@ -49,9 +54,18 @@ async fn main() -> anyhow::Result<()> {
             known_nodes_with_stake: startup_info.known_node_with_stake,
         },
         bid_txs: Default::default(),
+        latest_view: ViewNumber::genesis(),
+        bid_retention_views,
+        fee_balances: Arc::new(FeeStateClient::new(sequencer_url)),
+        chain_id,
+        solver_url: public_url,
     };
 
-    let global_state = Arc::new(RwLock::new(GlobalState::new(database, solver_state)?));
+    let global_state = Arc::new(RwLock::new(
+        GlobalState::new(database, solver_state)
+            .await
+            .context("failed to restore solver state")?,
+    ));
 
     let event_handler = spawn(handle_events(event_stream, global_state.clone()));
 
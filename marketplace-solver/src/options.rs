@@ -1,10 +1,12 @@
+#[cfg(feature = "embedded-db")]
+use std::path::PathBuf;
 use std::time::Duration;
 
 use clap::Parser;
-use espresso_types::parse_duration;
+use espresso_types::{parse_duration, ChainId};
 use tide_disco::Url;
 
-use crate::database::PostgresClient;
+use crate::{database::DatabaseClient, state::DEFAULT_BID_RETENTION_VIEWS};
 
 #[derive(Parser)]
 pub struct Options {
@@ -16,38 +18,86 @@ pub struct Options {
     #[clap(short, long, env = "ESPRESSO_SEQUENCER_HOTSHOT_EVENT_API_URL")]
     pub events_api_url: Url,
 
+    /// URL of a sequencer node serving the fee state API.
+    ///
+    /// Bids are rejected if this node reports that the bidding account cannot pay for them.
+    #[clap(long, env = "ESPRESSO_MARKETPLACE_SOLVER_SEQUENCER_URL")]
+    pub sequencer_url: Url,
+
+    /// The chain this solver runs auctions for.
+    #[clap(long, env = "ESPRESSO_MARKETPLACE_SOLVER_CHAIN_ID")]
+    pub chain_id: ChainId,
+
+    /// The base URL at which HotShot nodes reach this solver.
+    ///
+    /// Requests for permissioned auction results must be signed for this URL.
+    #[clap(long, env = "ESPRESSO_MARKETPLACE_SOLVER_PUBLIC_URL")]
+    pub public_url: Url,
+
+    /// Number of finished views for which submitted bids are retained before being pruned.
+    #[clap(
+        long,
+        env = "ESPRESSO_MARKETPLACE_SOLVER_BID_RETENTION_VIEWS",
+        default_value_t = DEFAULT_BID_RETENTION_VIEWS
+    )]
+    pub bid_retention_views: u64,
+
     #[clap(flatten)]
     pub database_options: DatabaseOptions,
 }
 
 /// Arguments for establishing a database connection
+///
+/// The solver uses Postgres by default, or SQLite when built with the `embedded-db` feature.
 #[derive(Clone, Debug, Parser)]
 pub struct DatabaseOptions {
     // Postgres URL connection string
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_URL")]
     pub url: Option<String>,
 
+    // SQLite URL connection string, e.g. `sqlite://path/to/solver.db`
+    #[cfg(feature = "embedded-db")]
+    #[clap(long, env = "MARKETPLACE_SOLVER_SQLITE_URL")]
+    pub url: Option<String>,
+
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_HOST")]
     pub host: Option<String>,
 
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_PORT")]
     pub port: Option<u16>,
 
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_DATABASE_NAME")]
     pub db_name: Option<String>,
 
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_USER")]
     pub username: Option<String>,
 
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_PASSWORD")]
     pub password: Option<String>,
 
+    // Path of the SQLite database file, created if it does not exist
+    #[cfg(feature = "embedded-db")]
+    #[clap(long, env = "MARKETPLACE_SOLVER_SQLITE_PATH")]
+    pub path: Option<PathBuf>,
+
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(long, env = "MARKETPLACE_SOLVER_POSTGRES_MAX_CONNECTIONS")]
     pub max_connections: Option<u32>,
 
+    #[cfg(feature = "embedded-db")]
+    #[clap(long, env = "MARKETPLACE_SOLVER_SQLITE_MAX_CONNECTIONS")]
+    pub max_connections: Option<u32>,
+
     #[clap(long,value_parser = parse_duration, env = "MARKETPLACE_SOLVER_DATABASE_ACQUIRE_TIMEOUT")]
     pub acquire_timeout: Option<Duration>,
 
+    #[cfg(not(feature = "embedded-db"))]
     #[clap(
         long,
         env = "MARKETPLACE_SOLVER_DATABASE_REQUIRE_SSL",
@@ -71,8 +121,8 @@ pub struct DatabaseOptions {
 }
 
 impl DatabaseOptions {
-    pub async fn connect(self) -> anyhow::Result<PostgresClient> {
-        PostgresClient::connect(self).await
+    pub async fn connect(self) -> anyhow::Result<DatabaseClient> {
+        DatabaseClient::connect(self).await
     }
 
     pub fn reset(mut self) -> Self {
@@ -1,26 +1,46 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
+use async_broadcast::{broadcast, InactiveReceiver, Receiver, Sender};
 use async_trait::async_trait;
 use committable::Committable;
 use espresso_types::{
     v0_99::{
-        BidTx, RollupRegistration, RollupRegistrationBody, RollupUpdate, RollupUpdatebody,
-        SolverAuctionResults,
+        AuctionResultsRequest, BidTx, RollupRegistration, RollupRegistrationBody,
+        RollupRegistrationChange, RollupRegistrationChanges, RollupRegistrationHistoryEntry,
+        RollupRegistrationsSnapshot, RollupUpdate, RollupUpdatebody, SolverAuctionResults,
     },
-    PubKey, SeqTypes,
+    ChainId, FeeAccount, FeeAmount, NamespaceId, PubKey, SeqTypes,
     Update::Set,
 };
 use hotshot::types::SignatureKey;
-use hotshot_types::{data::ViewNumber, traits::node_implementation::NodeType, PeerConfig};
+use hotshot_types::{
+    data::ViewNumber,
+    traits::node_implementation::{ConsensusTime, NodeType},
+    PeerConfig,
+};
+use num_traits::CheckedAdd;
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, PgPool};
-
-use crate::{database::PostgresClient, overflow_err, SolverError, SolverResult};
+use sqlx::FromRow;
+use surf_disco::Client;
+use tide_disco::{error::ServerError, StatusCode, Url};
+use vbs::version::StaticVersion;
+
+use crate::{
+    database::{DatabaseClient, DbConnection, DbPool},
+    overflow_err, SolverError, SolverResult,
+};
 
 // TODO ED: Implement a shared solver state with the HotShot events received
 pub struct GlobalState {
     solver: SolverState,
-    database: PostgresClient,
+    database: DatabaseClient,
+    // Auction results are broadcast to subscribers as each view's auction closes
+    auction_results: Sender<SolverAuctionResults>,
+    // Kept so that the channel stays open while there are no subscribers
+    auction_results_receiver: InactiveReceiver<SolverAuctionResults>,
 }
 
 impl GlobalState {
@@ -28,23 +48,164 @@ impl GlobalState {
         &self.solver
     }
 
-    pub fn database(&self) -> &PgPool {
+    pub fn solver_mut(&mut self) -> &mut SolverState {
+        &mut self.solver
+    }
+
+    pub fn database(&self) -> &DbPool {
         self.database.pool()
     }
+
+    /// Record that HotShot has finished `view_number`.
+    ///
+    /// This closes the auctions for the next view and any views skipped since the previous
+    /// finished view, whose results are broadcast to subscribers.
+    /// Bids for views more than `bid_retention_views` behind the latest finished view are
+    /// removed from memory and from the database.
+    pub async fn view_finished(&mut self, view_number: ViewNumber) -> SolverResult<()> {
+        if view_number <= self.solver.latest_view {
+            return Ok(());
+        }
+        let previous = self.solver.latest_view;
+        self.solver.latest_view = view_number;
+
+        let cutoff = ViewNumber::new(view_number.saturating_sub(self.solver.bid_retention_views));
+        self.solver.bid_txs.retain(|view, _| *view >= cutoff);
+
+        sqlx::query("DELETE FROM bid_txs WHERE view_number < $1;")
+            .bind::<i64>((*cutoff).try_into().map_err(overflow_err)?)
+            .execute(self.database())
+            .await
+            .map_err(SolverError::from)?;
+
+        if self.auction_results.receiver_count() > 0 {
+            // Finishing several views at once closes the auctions for all the views skipped, but
+            // after a long gap only the most recent ones can still be of interest.
+            let first =
+                (*previous + 2).max((*view_number + 1).saturating_sub(MAX_BID_VIEW_LOOKAHEAD));
+            let rollups = self.get_all_rollup_registrations().await?;
+            for view in (first..=*view_number + 1).map(ViewNumber::new) {
+                let bids = self
+                    .solver
+                    .bid_txs
+                    .get(&view)
+                    .map(|bids| bids.values().cloned().collect())
+                    .unwrap_or_default();
+                let results = run_auction(view, rollups.clone(), bids);
+                // The channel overflows rather than blocking, so this only fails if all
+                // subscribers have disconnected in the meantime.
+                let _ = self.auction_results.try_broadcast(results);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl GlobalState {
-    pub fn new(db: PostgresClient, state: SolverState) -> anyhow::Result<Self> {
+    /// Create the global state, restoring any bids persisted by a previous solver instance.
+    pub async fn new(db: DatabaseClient, mut state: SolverState) -> anyhow::Result<Self> {
+        let rows: Vec<BidTxResult> = sqlx::query_as("SELECT * FROM bid_txs;")
+            .fetch_all(db.pool())
+            .await?;
+
+        for row in rows {
+            let bid_tx = bincode::deserialize::<BidTx>(&row.data)?;
+            state
+                .bid_txs
+                .entry(bid_tx.view())
+                .or_default()
+                .insert(bid_tx.account(), bid_tx);
+        }
+
+        let (auction_results, auction_results_receiver) = auction_results_channel();
+
         Ok(Self {
             solver: state,
             database: db,
+            auction_results,
+            auction_results_receiver,
         })
     }
 }
 
+/// How many auction results are buffered for a subscriber before the oldest are dropped.
+const AUCTION_RESULTS_CHANNEL_CAPACITY: usize = 100;
+
+fn auction_results_channel() -> (
+    Sender<SolverAuctionResults>,
+    InactiveReceiver<SolverAuctionResults>,
+) {
+    let (mut sender, receiver) = broadcast(AUCTION_RESULTS_CHANNEL_CAPACITY);
+    sender.set_overflow(true);
+    (sender, receiver.deactivate())
+}
+
+/// How many views past the latest finished view a bid may be submitted for.
+pub const MAX_BID_VIEW_LOOKAHEAD: u64 = 20;
+
+/// Default number of finished views for which bids are retained.
+pub const DEFAULT_BID_RETENTION_VIEWS: u64 = 100;
+
+/// Maximum number of registration changes returned by a single change log query.
+pub const MAX_REGISTRATION_CHANGES: i64 = 100;
+
 pub struct SolverState {
     pub stake_table: StakeTable,
     pub bid_txs: HashMap<ViewNumber, HashMap<<SeqTypes as NodeType>::BuilderSignatureKey, BidTx>>,
+    /// The latest view HotShot has finished, as seen in `ViewFinished` events.
+    pub latest_view: ViewNumber,
+    /// Number of views behind `latest_view` for which bids are kept before being pruned.
+    pub bid_retention_views: u64,
+    /// Balances of the fee accounts bids are paid from.
+    pub fee_balances: Arc<dyn FeeBalances>,
+    /// The chain this solver runs auctions for.
+    pub chain_id: ChainId,
+    /// The base URL at which HotShot nodes reach this solver.
+    pub solver_url: Url,
+}
+
+/// Source of the fee account balances that bids are charged to.
+#[async_trait]
+pub trait FeeBalances: Send + Sync {
+    /// The latest balance of `account`.
+    async fn balance(&self, account: FeeAccount) -> anyhow::Result<FeeAmount>;
+}
+
+/// Fee balances fetched from the fee state API of a sequencer node.
+pub struct FeeStateClient(Client<ServerError, StaticVersion<0, 1>>);
+
+impl FeeStateClient {
+    pub fn new(url: Url) -> Self {
+        Self(Client::new(url))
+    }
+}
+
+#[async_trait]
+impl FeeBalances for FeeStateClient {
+    async fn balance(&self, account: FeeAccount) -> anyhow::Result<FeeAmount> {
+        let balance: Option<FeeAmount> = self
+            .0
+            .get(&format!("fee-state/fee-balance/latest/{:#x}", account.0))
+            .send()
+            .await?;
+        // An account which is not in the fee state has no balance.
+        Ok(balance.unwrap_or_default())
+    }
+}
+
+/// Fetch the balance of `account` from `fee_balances`.
+pub async fn fee_balance(
+    fee_balances: &dyn FeeBalances,
+    account: FeeAccount,
+) -> SolverResult<FeeAmount> {
+    fee_balances
+        .balance(account)
+        .await
+        .map_err(|err| SolverError::Custom {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            message: format!("failed to fetch fee balance: {err:#}"),
+        })
 }
 
 pub struct StakeTable {
@@ -53,7 +214,14 @@ pub struct StakeTable {
 
 #[async_trait]
 pub trait UpdateSolverState {
-    async fn submit_bid_tx(&mut self, bid_tx: BidTx) -> SolverResult<()>;
+    /// Accept a bid, if it could win the auction for the view it is for.
+    ///
+    /// `balance` is the balance of the fee account the bid is paid from. It is fetched from a
+    /// sequencer node, which may be slow, so it should be fetched before locking the state.
+    async fn submit_bid_tx(&mut self, bid_tx: BidTx, balance: FeeAmount) -> SolverResult<()>;
+
+    /// The balances of the fee accounts bids are paid from.
+    fn fee_balances(&self) -> Arc<dyn FeeBalances>;
 
     async fn register_rollup(
         &self,
@@ -66,6 +234,23 @@ pub trait UpdateSolverState {
 
     async fn get_all_rollup_registrations(&self) -> SolverResult<Vec<RollupRegistration>>;
 
+    async fn get_rollup_registration_history(
+        &self,
+        namespace_id: NamespaceId,
+    ) -> SolverResult<Vec<RollupRegistrationHistoryEntry>>;
+
+    /// Get the registration changes of all rollups after `cursor` in the change log.
+    ///
+    /// At most [`MAX_REGISTRATION_CHANGES`] changes are returned at once.
+    async fn get_rollup_registration_changes(
+        &self,
+        cursor: u64,
+    ) -> SolverResult<RollupRegistrationChanges>;
+
+    /// Get the current registrations of all rollups, along with the position in the change log
+    /// they are up to date with.
+    async fn get_rollup_registrations_snapshot(&self) -> SolverResult<RollupRegistrationsSnapshot>;
+
     async fn calculate_auction_results_permissionless(
         &self,
         view_number: ViewNumber,
@@ -74,16 +259,35 @@ pub trait UpdateSolverState {
     async fn calculate_auction_results_permissioned(
         &self,
         view_number: ViewNumber,
-        _signauture: <SeqTypes as NodeType>::SignatureKey,
+        signer: <SeqTypes as NodeType>::SignatureKey,
+        signature: <<SeqTypes as NodeType>::SignatureKey as SignatureKey>::PureAssembledSignatureType,
     ) -> SolverResult<SolverAuctionResults>;
+
+    /// Subscribe to the results of each auction as it closes.
+    fn subscribe_auction_results(&self) -> Receiver<SolverAuctionResults>;
 }
 
 #[async_trait]
 impl UpdateSolverState for GlobalState {
-    async fn submit_bid_tx(&mut self, bid_tx: BidTx) -> SolverResult<()> {
+    async fn submit_bid_tx(&mut self, bid_tx: BidTx, balance: FeeAmount) -> SolverResult<()> {
+        self.validate_bid_tx(&bid_tx, balance).await?;
+
         let view = bid_tx.view();
         let builder_key = bid_tx.account();
 
+        let bytes = bincode::serialize(&bid_tx)?;
+
+        sqlx::query(
+            "INSERT INTO bid_txs VALUES ($1, $2, $3)
+             ON CONFLICT (view_number, account) DO UPDATE SET data = excluded.data;",
+        )
+        .bind::<i64>((*view).try_into().map_err(overflow_err)?)
+        .bind(builder_key.0.as_bytes())
+        .bind(&bytes)
+        .execute(self.database())
+        .await
+        .map_err(SolverError::from)?;
+
         let bid_txs = &mut self.solver.bid_txs;
         bid_txs.entry(view).or_default().insert(builder_key, bid_tx);
         Ok(())
@@ -119,13 +323,13 @@ impl UpdateSolverState for GlobalState {
             return Err(SolverError::InvalidSignature(signature.to_string()));
         }
 
-        let db = self.database();
+        let mut tx = self.database().begin().await?;
 
         let exists: bool = sqlx::query_scalar(
             "SELECT EXISTS(SELECT 1 FROM rollup_registrations where namespace_id = $1);",
         )
         .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
-        .fetch_one(db)
+        .fetch_one(&mut *tx)
         .await
         .map_err(SolverError::from)?;
 
@@ -135,12 +339,13 @@ impl UpdateSolverState for GlobalState {
 
         let bytes = bincode::serialize(&registration)?;
 
-        let result = sqlx::query("INSERT INTO rollup_registrations VALUES ($1, $2);")
-            .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
-            .bind(&bytes)
-            .execute(db)
-            .await
-            .map_err(SolverError::from)?;
+        let result =
+            sqlx::query("INSERT INTO rollup_registrations (namespace_id, data) VALUES ($1, $2);")
+                .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
+                .bind(&bytes)
+                .execute(&mut *tx)
+                .await
+                .map_err(SolverError::from)?;
 
         if result.rows_affected() != 1 {
             return Err(SolverError::Database(format!(
@@ -149,6 +354,17 @@ impl UpdateSolverState for GlobalState {
             )));
         }
 
+        insert_registration_history(
+            &mut tx,
+            &RollupRegistrationHistoryEntry {
+                change: RollupRegistrationChange::Register(registration.clone()),
+                registration: registration.clone(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
         Ok(registration)
     }
 
@@ -156,9 +372,7 @@ impl UpdateSolverState for GlobalState {
         &self,
         update: RollupUpdate,
     ) -> SolverResult<RollupRegistration> {
-        let db = self.database();
-
-        let RollupUpdate { body, signature } = update;
+        let RollupUpdate { body, signature } = update.clone();
 
         let commit = body.commit();
 
@@ -170,6 +384,7 @@ impl UpdateSolverState for GlobalState {
             signature_keys,
             signature_key,
             text,
+            nonce,
         } = body;
 
         let valid_signature = <SeqTypes as NodeType>::SignatureKey::validate(
@@ -182,12 +397,18 @@ impl UpdateSolverState for GlobalState {
             return Err(SolverError::InvalidSignature(signature.to_string()));
         }
 
-        let result: RollupRegistrationResult =
-            sqlx::query_as("SELECT * from rollup_registrations where namespace_id = $1;")
-                .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
-                .fetch_one(db)
-                .await
-                .map_err(SolverError::from)?;
+        let mut tx = self.database().begin().await?;
+
+        // Lock the row so that concurrent updates observe each other's nonce.
+        // SQLite has no row locks, but fails the transaction on commit if a concurrent
+        // update wrote the row after it was read.
+        let result: RollupRegistrationResult = sqlx::query_as(&format!(
+            "SELECT * from rollup_registrations where namespace_id = $1{FOR_UPDATE};"
+        ))
+        .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(SolverError::from)?;
 
         let mut registration = bincode::deserialize::<RollupRegistration>(&result.data)?;
 
@@ -225,15 +446,23 @@ impl UpdateSolverState for GlobalState {
             registration.body.signature_keys = keys;
         }
 
+        // Reject replayed or out of order updates
+        let current = u64::try_from(result.nonce).map_err(overflow_err)?;
+        if nonce <= current {
+            return Err(SolverError::StaleNonce { nonce, current });
+        }
+
         let bytes = bincode::serialize(&registration)?;
 
-        let result =
-            sqlx::query("UPDATE rollup_registrations SET data = $1  WHERE namespace_id = $2;")
-                .bind(&bytes)
-                .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
-                .execute(db)
-                .await
-                .map_err(SolverError::from)?;
+        let result = sqlx::query(
+            "UPDATE rollup_registrations SET data = $1, nonce = $2 WHERE namespace_id = $3;",
+        )
+        .bind(&bytes)
+        .bind::<i64>(nonce.try_into().map_err(overflow_err)?)
+        .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
+        .execute(&mut *tx)
+        .await
+        .map_err(SolverError::from)?;
 
         if result.rows_affected() != 1 {
             return Err(SolverError::Database(format!(
@@ -242,9 +471,79 @@ impl UpdateSolverState for GlobalState {
             )));
         }
 
+        insert_registration_history(
+            &mut tx,
+            &RollupRegistrationHistoryEntry {
+                change: RollupRegistrationChange::Update(update),
+                registration: registration.clone(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+
         Ok(registration)
     }
 
+    async fn get_rollup_registration_history(
+        &self,
+        namespace_id: NamespaceId,
+    ) -> SolverResult<Vec<RollupRegistrationHistoryEntry>> {
+        let db = self.database();
+
+        let rows: Vec<RollupRegistrationHistoryResult> = sqlx::query_as(
+            "SELECT * from rollup_registration_history where namespace_id = $1 ORDER BY id;",
+        )
+        .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
+        .fetch_all(db)
+        .await
+        .map_err(SolverError::from)?;
+
+        rows.iter()
+            .map(|r| bincode::deserialize(&r.data).map_err(SolverError::from))
+            .collect::<SolverResult<Vec<RollupRegistrationHistoryEntry>>>()
+    }
+
+    async fn get_rollup_registration_changes(
+        &self,
+        cursor: u64,
+    ) -> SolverResult<RollupRegistrationChanges> {
+        let db = self.database();
+
+        let rows: Vec<RollupRegistrationHistoryResult> = sqlx::query_as(
+            "SELECT * from rollup_registration_history where id > $1 ORDER BY id LIMIT $2;",
+        )
+        .bind::<i64>(cursor.try_into().map_err(overflow_err)?)
+        .bind::<i64>(MAX_REGISTRATION_CHANGES)
+        .fetch_all(db)
+        .await
+        .map_err(SolverError::from)?;
+
+        let cursor = rows.last().map_or(cursor, |r| r.id as u64);
+        let changes = rows
+            .iter()
+            .map(|r| bincode::deserialize(&r.data).map_err(SolverError::from))
+            .collect::<SolverResult<Vec<RollupRegistrationHistoryEntry>>>()?;
+
+        Ok(RollupRegistrationChanges { changes, cursor })
+    }
+
+    async fn get_rollup_registrations_snapshot(&self) -> SolverResult<RollupRegistrationsSnapshot> {
+        // Registrations are only changed while the state is locked for writing, so they cannot
+        // change between these queries.
+        let cursor: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(id) FROM rollup_registration_history;")
+                .fetch_one(self.database())
+                .await
+                .map_err(SolverError::from)?;
+        let registrations = self.get_all_rollup_registrations().await?;
+
+        Ok(RollupRegistrationsSnapshot {
+            registrations,
+            cursor: cursor.map_or(0, |id| id as u64),
+        })
+    }
+
     async fn get_all_rollup_registrations(&self) -> SolverResult<Vec<RollupRegistration>> {
         let db = self.database();
 
@@ -263,84 +562,266 @@ impl UpdateSolverState for GlobalState {
         &self,
         view_number: ViewNumber,
     ) -> SolverResult<SolverAuctionResults> {
-        // todo (ab): actual logic needs to implemented
-        // for we, we just return some default results
-
-        let rollups = self.get_all_rollup_registrations().await?;
+        self.calculate_auction_results(view_number).await
+    }
+    async fn calculate_auction_results_permissioned(
+        &self,
+        view_number: ViewNumber,
+        signer: <SeqTypes as NodeType>::SignatureKey,
+        signature: <<SeqTypes as NodeType>::SignatureKey as SignatureKey>::PureAssembledSignatureType,
+    ) -> SolverResult<SolverAuctionResults> {
+        // Only nodes in the HotShot stake table may fetch results before they are finalized
+        let staked = self
+            .solver
+            .stake_table
+            .known_nodes_with_stake
+            .iter()
+            .any(|peer| PubKey::public_key(&peer.stake_table_entry) == signer);
+
+        if !staked {
+            return Err(SolverError::SignerNotInStakeTable(signer.to_string()));
+        }
 
-        let results = SolverAuctionResults::new(
+        // The request is bound to this solver and chain, so it cannot be replayed elsewhere
+        let request = AuctionResultsRequest::new(
+            self.solver.chain_id,
+            self.solver.solver_url.clone(),
             view_number,
-            Vec::new(),
-            rollups
-                .into_iter()
-                .filter_map(|r| Some((r.body.namespace_id, r.body.reserve_url?)))
-                .collect(),
         );
+        if !signer.validate(&signature, request.commit().as_ref()) {
+            return Err(SolverError::InvalidSignature(signature.to_string()));
+        }
 
-        Ok(results)
+        self.calculate_auction_results(view_number).await
     }
-    async fn calculate_auction_results_permissioned(
+
+    fn subscribe_auction_results(&self) -> Receiver<SolverAuctionResults> {
+        self.auction_results_receiver.activate_cloned()
+    }
+
+    fn fee_balances(&self) -> Arc<dyn FeeBalances> {
+        self.solver.fee_balances.clone()
+    }
+}
+
+impl GlobalState {
+    /// Check that a bid could win the auction for the view it is for.
+    ///
+    /// This mirrors the checks done by `BidTx::verify` and `BidTx::charge` when the bid is
+    /// executed, using the registrations and views known to the solver and the `balance` of the
+    /// fee account the bid is paid from.
+    async fn validate_bid_tx(&self, bid_tx: &BidTx, balance: FeeAmount) -> SolverResult<()> {
+        bid_tx
+            .verify()
+            .map_err(|_| SolverError::InvalidSignature(bid_tx.signature().to_string()))?;
+
+        // The auction for a view closes once the previous view has finished
+        let view = bid_tx.view();
+        let latest = self.solver.latest_view;
+        if view <= latest + 1 {
+            return Err(SolverError::BidViewPassed {
+                view: *view,
+                latest: *latest,
+            });
+        }
+
+        let max = *latest + MAX_BID_VIEW_LOOKAHEAD;
+        if *view > max {
+            return Err(SolverError::BidViewTooFarAhead { view: *view, max });
+        }
+
+        if bid_tx.namespaces().is_empty() {
+            return Err(SolverError::BidNoNamespaces);
+        }
+
+        let rollups: HashMap<NamespaceId, RollupRegistrationBody> = self
+            .get_all_rollup_registrations()
+            .await?
+            .into_iter()
+            .map(|r| (r.body.namespace_id, r.body))
+            .collect();
+
+        let mut reserve_price = FeeAmount::default();
+        for ns in bid_tx.namespaces().iter().collect::<HashSet<_>>() {
+            let rollup = rollups
+                .get(ns)
+                .ok_or(SolverError::BidNamespaceNotRegistered(*ns))?;
+
+            if !rollup.active {
+                return Err(SolverError::BidNamespaceInactive(*ns));
+            }
+
+            reserve_price = reserve_price
+                .checked_add(&rollup.reserve_price)
+                .ok_or(SolverError::BidReservePriceOverflow)?;
+        }
+
+        if bid_tx.amount() < reserve_price {
+            return Err(SolverError::BidBelowReservePrice {
+                amount: bid_tx.amount(),
+                reserve_price,
+            });
+        }
+
+        // Executing the bid charges both the bid amount and the gas price
+        let required = bid_tx.amount().checked_add(&bid_tx.gas_price()).ok_or(
+            SolverError::BidAmountOverflow {
+                amount: bid_tx.amount(),
+                gas_price: bid_tx.gas_price(),
+            },
+        )?;
+        if balance < required {
+            return Err(SolverError::BidInsufficientBalance { balance, required });
+        }
+
+        Ok(())
+    }
+
+    async fn calculate_auction_results(
         &self,
         view_number: ViewNumber,
-        _signauture: <SeqTypes as NodeType>::SignatureKey,
     ) -> SolverResult<SolverAuctionResults> {
-        // todo (ab): actual logic needs to implemented
-        // for we, we just return some default results
-
         let rollups = self.get_all_rollup_registrations().await?;
+        let bids = self
+            .solver
+            .bid_txs
+            .get(&view_number)
+            .map(|bids| bids.values().cloned().collect())
+            .unwrap_or_default();
+
+        Ok(run_auction(view_number, rollups, bids))
+    }
+}
 
-        let results = SolverAuctionResults::new(
-            view_number,
-            Vec::new(),
-            rollups
-                .into_iter()
-                .filter_map(|r| Some((r.body.namespace_id, r.body.reserve_url?)))
-                .collect(),
-        );
+/// Select the winning bids for `view_number`.
+///
+/// Bids are considered from highest to lowest amount. A bid wins if every namespace it covers
+/// belongs to an active rollup, none of those namespaces has already been won by a higher bid,
+/// and the bid amount meets the sum of the reserve prices of its namespaces. Any active rollup
+/// left without a winning bid falls back to its reserve builder, if it has one.
+pub fn run_auction(
+    view_number: ViewNumber,
+    rollups: Vec<RollupRegistration>,
+    mut bids: Vec<BidTx>,
+) -> SolverAuctionResults {
+    let rollups: HashMap<NamespaceId, RollupRegistrationBody> = rollups
+        .into_iter()
+        .filter(|r| r.body.active)
+        .map(|r| (r.body.namespace_id, r.body))
+        .collect();
+
+    // Highest bid first. Ties are broken by account so that every solver instance computes the
+    // same results for the same set of bids.
+    bids.sort_by(|a, b| {
+        b.amount()
+            .cmp(&a.amount())
+            .then_with(|| a.account().cmp(&b.account()))
+    });
+
+    let mut won = HashSet::new();
+    let mut winning_bids = Vec::new();
+
+    for bid in bids {
+        if bid.view() != view_number {
+            continue;
+        }
+
+        let namespaces: HashSet<NamespaceId> = bid.namespaces().iter().copied().collect();
+        if namespaces.is_empty() {
+            continue;
+        }
 
-        Ok(results)
+        // The total reserve price of the namespaces, if none of them is taken. A total which
+        // overflows cannot be met by any bid.
+        let reserve_price = namespaces
+            .iter()
+            .try_fold(FeeAmount::default(), |total, ns| {
+                let rollup = rollups.get(ns).filter(|_| !won.contains(ns))?;
+                total.checked_add(&rollup.reserve_price)
+            });
+
+        if reserve_price.is_some_and(|reserve_price| bid.amount() >= reserve_price) {
+            won.extend(namespaces);
+            winning_bids.push(bid);
+        }
     }
+
+    let mut reserve_bids: Vec<(NamespaceId, Url)> = rollups
+        .into_iter()
+        .filter(|(ns, _)| !won.contains(ns))
+        .filter_map(|(ns, body)| Some((ns, body.reserve_url?)))
+        .collect();
+    reserve_bids.sort_by_key(|(ns, _)| *ns);
+
+    SolverAuctionResults::new(view_number, winning_bids, reserve_bids)
+}
+
+/// Append an entry to the change log of a rollup registration.
+async fn insert_registration_history(
+    conn: &mut DbConnection,
+    entry: &RollupRegistrationHistoryEntry,
+) -> SolverResult<()> {
+    let namespace_id = entry.registration.body.namespace_id;
+    let bytes = bincode::serialize(entry)?;
+
+    sqlx::query("INSERT INTO rollup_registration_history (namespace_id, data) VALUES ($1, $2);")
+        .bind::<i64>(u64::from(namespace_id).try_into().map_err(overflow_err)?)
+        .bind(&bytes)
+        .execute(conn)
+        .await
+        .map_err(SolverError::from)?;
+
+    Ok(())
 }
 
+#[cfg(not(feature = "embedded-db"))]
+const FOR_UPDATE: &str = " FOR UPDATE";
+#[cfg(feature = "embedded-db")]
+const FOR_UPDATE: &str = "";
+
 #[derive(Debug, Deserialize, Serialize, FromRow)]
 struct RollupRegistrationResult {
     namespace_id: i64,
     data: Vec<u8>,
+    nonce: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, FromRow)]
+struct RollupRegistrationHistoryResult {
+    id: i64,
+    namespace_id: i64,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Serialize, FromRow)]
+struct BidTxResult {
+    view_number: i64,
+    account: Vec<u8>,
+    data: Vec<u8>,
 }
 
-#[cfg(all(any(test, feature = "testing"), not(feature = "embedded-db")))]
+#[cfg(all(any(test, feature = "testing"), not(target_os = "windows")))]
 impl GlobalState {
     pub async fn mock() -> Self {
         let db = hotshot_query_service::data_source::sql::testing::TmpDb::init().await;
-        let host = db.host();
-        let port = db.port();
-
-        let opts = crate::DatabaseOptions {
-            url: None,
-            host: Some(host),
-            port: Some(port),
-            db_name: None,
-            username: Some("postgres".to_string()),
-            password: Some("password".to_string()),
-            max_connections: Some(100),
-            acquire_timeout: None,
-            require_ssl: false,
-            migrations: true,
-            reset: false,
-        };
+        let opts = crate::database::mock::mock_database_options(&db);
 
-        let client = PostgresClient::connect(opts)
+        let client = DatabaseClient::connect(opts)
             .await
             .expect("failed to connect to database");
 
+        let (auction_results, auction_results_receiver) = auction_results_channel();
+
         Self {
             solver: SolverState::mock(),
             database: client,
+            auction_results,
+            auction_results_receiver,
         }
     }
 }
 
-#[cfg(all(any(test, feature = "testing"), not(feature = "embedded-db")))]
+#[cfg(any(test, feature = "testing"))]
 impl SolverState {
     pub fn mock() -> Self {
         Self {
@@ -348,6 +829,40 @@ impl SolverState {
                 known_nodes_with_stake: crate::mock::generate_stake_table(),
             },
             bid_txs: Default::default(),
+            latest_view: ViewNumber::genesis(),
+            bid_retention_views: DEFAULT_BID_RETENTION_VIEWS,
+            fee_balances: Arc::new(MockFeeBalances::default()),
+            chain_id: Default::default(),
+            solver_url: "http://localhost".parse().unwrap(),
         }
     }
 }
+
+/// Fee balances for tests.
+///
+/// Accounts without an explicit balance are funded with `u64::MAX`, so that tests which are not
+/// about balances can bid from fresh accounts.
+#[cfg(any(test, feature = "testing"))]
+#[derive(Debug, Default)]
+pub struct MockFeeBalances(std::sync::RwLock<HashMap<FeeAccount, FeeAmount>>);
+
+#[cfg(any(test, feature = "testing"))]
+impl MockFeeBalances {
+    pub fn set_balance(&self, account: FeeAccount, amount: FeeAmount) {
+        self.0.write().unwrap().insert(account, amount);
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+#[async_trait]
+impl FeeBalances for MockFeeBalances {
+    async fn balance(&self, account: FeeAccount) -> anyhow::Result<FeeAmount> {
+        Ok(self
+            .0
+            .read()
+            .unwrap()
+            .get(&account)
+            .copied()
+            .unwrap_or(u64::MAX.into()))
+    }
+}
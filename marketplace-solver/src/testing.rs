@@ -1,24 +1,21 @@
-#![cfg(all(
-    any(test, feature = "testing"),
-    not(target_os = "windows"),
-    not(feature = "embedded-db")
-))]
+#![cfg(all(any(test, feature = "testing"), not(target_os = "windows")))]
 #![allow(dead_code)]
 use std::sync::Arc;
 
 use async_lock::RwLock;
 use espresso_types::MarketplaceVersion;
 use hotshot_query_service::data_source::sql::testing::TmpDb;
+use hotshot_types::{data::ViewNumber, traits::node_implementation::ConsensusTime};
 use portpicker::pick_unused_port;
 use tide_disco::{App, Url};
 use tokio::{spawn, task::JoinHandle};
 use vbs::version::StaticVersionType;
 
 use crate::{
-    database::{mock::setup_mock_database, PostgresClient},
+    database::{mock::setup_mock_database, DatabaseClient},
     define_api, handle_events,
     mock::run_mock_event_service,
-    state::{GlobalState, SolverState, StakeTable},
+    state::{GlobalState, MockFeeBalances, SolverState, StakeTable, DEFAULT_BID_RETENTION_VIEWS},
     EventsServiceClient, SolverError, SOLVER_API_PATH,
 };
 
@@ -27,7 +24,8 @@ pub struct MockSolver {
     /// Solver base URL, forming the path to the solver API, with `SOLVER_API_PATH`.
     pub solver_url: Url,
     pub state: Arc<RwLock<GlobalState>>,
-    pub database: PostgresClient,
+    pub fee_balances: Arc<MockFeeBalances>,
+    pub database: DatabaseClient,
     pub handles: Vec<JoinHandle<()>>,
     pub tmp_db: Arc<TmpDb>,
 }
@@ -58,22 +56,33 @@ impl MockSolver {
         Self::with_db((Arc::new(tmp_db), database)).await
     }
 
-    pub async fn with_db((tmp_db, database): (Arc<TmpDb>, PostgresClient)) -> Self {
+    pub async fn with_db((tmp_db, database): (Arc<TmpDb>, DatabaseClient)) -> Self {
         let (events_url, event_api_handle, generate_events_handle) = run_mock_event_service();
 
         let client = EventsServiceClient::new(events_url.clone()).await;
         let startup_info = client.get_startup_info().await.unwrap();
         let stream = client.get_event_stream().await.unwrap();
 
+        let solver_api_port = pick_unused_port().expect("no free port");
+        let solver_url: Url = Url::parse(&format!("http://localhost:{solver_api_port}")).unwrap();
+
+        let fee_balances = Arc::new(MockFeeBalances::default());
         let solver_state = SolverState {
             stake_table: StakeTable {
                 known_nodes_with_stake: startup_info.known_node_with_stake,
             },
             bid_txs: Default::default(),
+            latest_view: ViewNumber::genesis(),
+            bid_retention_views: DEFAULT_BID_RETENTION_VIEWS,
+            fee_balances: fee_balances.clone(),
+            chain_id: Default::default(),
+            solver_url: solver_url.clone(),
         };
 
         let state = Arc::new(RwLock::new(
-            GlobalState::new(database.clone(), solver_state).unwrap(),
+            GlobalState::new(database.clone(), solver_state)
+                .await
+                .unwrap(),
         ));
 
         let event_handler_handle = spawn({
@@ -92,9 +101,6 @@ impl MockSolver {
         app.register_module::<SolverError, MarketplaceVersion>(SOLVER_API_PATH, api)
             .unwrap();
 
-        let solver_api_port = pick_unused_port().expect("no free port");
-        let solver_url: Url = Url::parse(&format!("http://localhost:{solver_api_port}")).unwrap();
-
         let solver_api_handle = spawn({
             let solver_url = solver_url.clone();
             async move {
@@ -113,6 +119,7 @@ impl MockSolver {
             events_url,
             solver_url,
             state,
+            fee_balances,
             database,
             tmp_db,
             handles,
@@ -120,22 +127,38 @@ impl MockSolver {
     }
 }
 
-#[cfg(all(test, not(feature = "embedded-db")))]
+#[cfg(test)]
 mod test {
     use committable::Committable;
     use espresso_types::{
+        eth_signature_key::EthKeyPair,
         v0_99::{
-            BidTx, RollupRegistration, RollupRegistrationBody, RollupUpdate, RollupUpdatebody,
+            AuctionResultsRequest, BidTx, BidTxBody, RollupRegistration, RollupRegistrationBody,
+            RollupRegistrationChange, RollupRegistrationChanges, RollupRegistrationHistoryEntry,
+            RollupUpdate, RollupUpdatebody, SolverAuctionResults,
         },
-        FeeAccount, MarketplaceVersion, SeqTypes,
+        FeeAccount, FeeAmount, MarketplaceVersion, NamespaceId, SeqTypes,
         Update::{Set, Skip},
     };
+    use futures::StreamExt;
     use hotshot::types::{BLSPubKey, SignatureKey};
-    use hotshot_types::traits::node_implementation::NodeType;
+    use hotshot_types::{
+        data::ViewNumber,
+        light_client::StateKeyPair,
+        traits::node_implementation::{ConsensusTime, NodeType},
+        PeerConfig,
+    };
     use std::{str::FromStr, time::Duration};
     use tide_disco::Url;
 
-    use crate::{testing::MockSolver, SolverError};
+    use crate::{
+        state::{
+            run_auction, GlobalState, UpdateSolverState, DEFAULT_BID_RETENTION_VIEWS,
+            MAX_BID_VIEW_LOOKAHEAD,
+        },
+        testing::MockSolver,
+        SolverError,
+    };
 
     async fn register_rollup_helper(
         namespace_id: u64,
@@ -303,6 +326,7 @@ mod test {
             signature_keys: Skip,
             signature_key: BLSPubKey::from_private(&privkey),
             text: Skip,
+            nonce: 1,
         };
 
         let signature =
@@ -335,6 +359,55 @@ mod test {
             client.get("rollup_registrations").send().await.unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], reg_ns_1);
+
+        // Replaying the same update should fail as its nonce has already been used
+        let err = client
+            .post::<RollupRegistration>("update_rollup")
+            .body_json(&update_rolup)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+
+        match err {
+            SolverError::StaleNonce { nonce, current } => {
+                assert_eq!(nonce, 1);
+                assert_eq!(current, 1);
+            }
+            _ => panic!("err {err:?}"),
+        }
+
+        // The change log should contain the registration and the accepted update only
+        let history: Vec<RollupRegistrationHistoryEntry> = client
+            .get("rollup_registration_history/1")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history[0].change,
+            RollupRegistrationChange::Register(history[0].registration.clone())
+        );
+        assert_eq!(
+            history[1].change,
+            RollupRegistrationChange::Update(update_rolup)
+        );
+        assert_eq!(history[1].registration, reg_ns_1);
+
+        // The solver-wide change log can be followed with a cursor
+        let changes: RollupRegistrationChanges = client
+            .get("rollup_registration_changes/0")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(changes.changes, history);
+
+        let changes: RollupRegistrationChanges = client
+            .get(&format!("rollup_registration_changes/{}", changes.cursor))
+            .send()
+            .await
+            .unwrap();
+        assert!(changes.changes.is_empty());
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -356,6 +429,7 @@ mod test {
             signature_keys: Skip,
             text: Skip,
             signature_key: pubkey,
+            nonce: 1,
         };
         let signature =
             <SeqTypes as NodeType>::SignatureKey::sign(&private_key, update_body.commit().as_ref())
@@ -422,6 +496,7 @@ mod test {
             signature_keys: Set(signature_keys.clone()),
             signature_key,
             text: Skip,
+            nonce: 1,
         };
 
         let signature =
@@ -508,12 +583,196 @@ mod test {
         let client = surf_disco::Client::<SolverError, MarketplaceVersion>::new(solver_api);
         client.connect(None).await;
 
-        let key = FeeAccount::test_key_pair();
-        let tx = BidTx::mock(key);
+        let (reg_ns_1, _, _) =
+            register_rollup_helper(1, Some("http://localhost"), 200, true, "test").await;
+        let _: RollupRegistration = client
+            .post("register_rollup")
+            .body_json(&reg_ns_1)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        let (reg_ns_2, _, _) =
+            register_rollup_helper(2, Some("http://localhost"), 200, false, "test").await;
+        let _: RollupRegistration = client
+            .post("register_rollup")
+            .body_json(&reg_ns_2)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        let latest = *mock_solver.state().read().await.solver().latest_view;
+        let view = latest + MAX_BID_VIEW_LOOKAHEAD / 2;
+
+        client
+            .post::<()>("submit_bid")
+            .body_json(&bid_helper(view, 200, vec![1], "http://builder"))
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        // A bid signed by a different key than its account
+        let forged = BidTxBody::new(
+            FeeAccount::test_key_pair().fee_account(),
+            FeeAmount::from(200),
+            ViewNumber::new(view),
+            vec![1_u64.into()],
+            Url::from_str("http://builder").unwrap(),
+            FeeAmount::default(),
+        )
+        .signed(&EthKeyPair::random())
+        .unwrap();
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&forged)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SolverError::InvalidSignature(_)), "{err:?}");
+
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&BidTx::mock(FeeAccount::test_key_pair()))
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SolverError::BidViewPassed { .. }), "{err:?}");
+
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&bid_helper(
+                view + 10 * MAX_BID_VIEW_LOOKAHEAD,
+                200,
+                vec![1],
+                "http://builder",
+            ))
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, SolverError::BidViewTooFarAhead { .. }),
+            "{err:?}"
+        );
+
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&bid_helper(view, 200, vec![], "http://builder"))
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SolverError::BidNoNamespaces), "{err:?}");
+
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&bid_helper(view, 200, vec![3], "http://builder"))
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        match err {
+            SolverError::BidNamespaceNotRegistered(ns) if ns == 3_u64.into() => (),
+            _ => panic!("err {err:?}"),
+        }
+
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&bid_helper(view, 200, vec![2], "http://builder"))
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        match err {
+            SolverError::BidNamespaceInactive(ns) if ns == 2_u64.into() => (),
+            _ => panic!("err {err:?}"),
+        }
+
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&bid_helper(view, 199, vec![1], "http://builder"))
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        match err {
+            SolverError::BidBelowReservePrice {
+                amount,
+                reserve_price,
+            } => {
+                assert_eq!(amount, 199.into());
+                assert_eq!(reserve_price, 200.into());
+            }
+            _ => panic!("err {err:?}"),
+        }
+
+        // An account which cannot pay the bid amount plus the gas price
+        let key = EthKeyPair::random();
+        let underfunded = BidTxBody::new(
+            key.fee_account(),
+            FeeAmount::from(200),
+            ViewNumber::new(view),
+            vec![1_u64.into()],
+            Url::from_str("http://builder").unwrap(),
+            FeeAmount::from(10),
+        )
+        .signed(&key)
+        .unwrap();
+        mock_solver
+            .fee_balances
+            .set_balance(key.fee_account(), FeeAmount::from(209));
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&underfunded)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        match err {
+            SolverError::BidInsufficientBalance { balance, required } => {
+                assert_eq!(balance, 209.into());
+                assert_eq!(required, 210.into());
+            }
+            _ => panic!("err {err:?}"),
+        }
 
+        // A bid whose amount and gas price add up to more than any balance
+        let overflow_key = EthKeyPair::random();
+        let huge = FeeAmount::from(u64::MAX) * u64::MAX * u64::MAX * u64::MAX;
+        let overflowing = BidTxBody::new(
+            overflow_key.fee_account(),
+            huge,
+            ViewNumber::new(view),
+            vec![1_u64.into()],
+            Url::from_str("http://builder").unwrap(),
+            huge,
+        )
+        .signed(&overflow_key)
+        .unwrap();
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&overflowing)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, SolverError::BidAmountOverflow { .. }),
+            "{err:?}"
+        );
+
+        // The same underfunded bid is accepted once the account can pay for it
+        mock_solver
+            .fee_balances
+            .set_balance(key.fee_account(), FeeAmount::from(210));
         client
             .post::<()>("submit_bid")
-            .body_json(&tx)
+            .body_json(&underfunded)
             .unwrap()
             .send()
             .await
@@ -587,4 +846,382 @@ mod test {
         assert_eq!(result[0], reg_ns_1);
         assert_eq!(result[1], reg_ns_2);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_bid_persistence_and_pruning() {
+        let mut mock_solver = MockSolver::init().await;
+        let solver_api = mock_solver.solver_api();
+        let client = surf_disco::Client::<SolverError, MarketplaceVersion>::new(solver_api);
+        client.connect(None).await;
+
+        let (reg_ns_1, _, _) =
+            register_rollup_helper(1, Some("http://localhost"), 200, true, "test").await;
+        let _: RollupRegistration = client
+            .post("register_rollup")
+            .body_json(&reg_ns_1)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        let view =
+            *mock_solver.state().read().await.solver().latest_view + MAX_BID_VIEW_LOOKAHEAD / 2;
+        let bid = bid_helper(view, 200, vec![1], "http://builder");
+        client
+            .post::<()>("submit_bid")
+            .body_json(&bid)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        // Restart the solver with the same database, the bid should be restored
+        while let Some(handle) = mock_solver.handles.pop() {
+            handle.abort();
+        }
+        let mock_solver =
+            MockSolver::with_db((mock_solver.tmp_db.clone(), mock_solver.database.clone())).await;
+
+        let state = mock_solver.state();
+        assert_eq!(
+            state.read().await.solver().bid_txs[&ViewNumber::new(view)][&bid.account()],
+            bid
+        );
+
+        // Once the view falls out of the retention window, the bid is pruned
+        state
+            .write()
+            .await
+            .view_finished(ViewNumber::new(view + DEFAULT_BID_RETENTION_VIEWS + 1))
+            .await
+            .unwrap();
+        assert!(!state
+            .read()
+            .await
+            .solver()
+            .bid_txs
+            .contains_key(&ViewNumber::new(view)));
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bid_txs;")
+            .fetch_one(mock_solver.database.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_permissioned_auction_results() {
+        let mock_solver = MockSolver::init().await;
+        let solver_api = mock_solver.solver_api();
+        let client = surf_disco::Client::<SolverError, MarketplaceVersion>::new(solver_api);
+        client.connect(None).await;
+
+        let (reg_ns_1, _, _) =
+            register_rollup_helper(1, Some("http://localhost"), 200, true, "test").await;
+        let _: RollupRegistration = client
+            .post("register_rollup")
+            .body_json(&reg_ns_1)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        // Add a key we control to the stake table
+        let staked_private_key =
+            <BLSPubKey as SignatureKey>::PrivateKey::generate(&mut rand::thread_rng());
+        let staked_key = BLSPubKey::from_private(&staked_private_key);
+        mock_solver
+            .state()
+            .write()
+            .await
+            .solver_mut()
+            .stake_table
+            .known_nodes_with_stake
+            .push(PeerConfig {
+                stake_table_entry: staked_key.stake_table_entry(1),
+                state_ver_key: StateKeyPair::generate().ver_key(),
+            });
+
+        let view = ViewNumber::new(5);
+        let request =
+            AuctionResultsRequest::new(Default::default(), mock_solver.solver_url.clone(), view);
+        let signature = BLSPubKey::sign(&staked_private_key, request.commit().as_ref()).unwrap();
+
+        let result: SolverAuctionResults = client
+            .get(&format!(
+                "auction_results_permissioned/{}/{staked_key}/{signature}",
+                *view
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(result.view(), view);
+        assert_eq!(result.reserve_bids().len(), 1);
+
+        // A signature over a different view is rejected
+        let err = client
+            .get::<SolverAuctionResults>(&format!(
+                "auction_results_permissioned/{}/{staked_key}/{signature}",
+                *view + 1
+            ))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SolverError::InvalidSignature(_)), "{err:?}");
+
+        // Signatures for another solver or another chain are rejected
+        for request in [
+            AuctionResultsRequest::new(
+                Default::default(),
+                "http://other-solver".parse().unwrap(),
+                view,
+            ),
+            AuctionResultsRequest::new(1.into(), mock_solver.solver_url.clone(), view),
+        ] {
+            let signature =
+                BLSPubKey::sign(&staked_private_key, request.commit().as_ref()).unwrap();
+            let err = client
+                .get::<SolverAuctionResults>(&format!(
+                    "auction_results_permissioned/{}/{staked_key}/{signature}",
+                    *view
+                ))
+                .send()
+                .await
+                .unwrap_err();
+            assert!(matches!(err, SolverError::InvalidSignature(_)), "{err:?}");
+        }
+
+        // A valid signature from a key outside the stake table is rejected
+        let private_key =
+            <BLSPubKey as SignatureKey>::PrivateKey::generate(&mut rand::thread_rng());
+        let key = BLSPubKey::from_private(&private_key);
+        let signature = BLSPubKey::sign(&private_key, request.commit().as_ref()).unwrap();
+
+        let err = client
+            .get::<SolverAuctionResults>(&format!(
+                "auction_results_permissioned/{}/{key}/{signature}",
+                *view
+            ))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, SolverError::SignerNotInStakeTable(_)),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_auction_results_stream() {
+        let mock_solver = MockSolver::init().await;
+        let solver_api = mock_solver.solver_api();
+        let client = surf_disco::Client::<SolverError, MarketplaceVersion>::new(solver_api);
+        client.connect(None).await;
+
+        let (reg_ns_1, _, _) =
+            register_rollup_helper(1, Some("http://localhost"), 200, true, "test").await;
+        let _: RollupRegistration = client
+            .post("register_rollup")
+            .body_json(&reg_ns_1)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        let mut results = client
+            .socket("auction_results_stream")
+            .subscribe::<SolverAuctionResults>()
+            .await
+            .unwrap();
+
+        let view =
+            *mock_solver.state().read().await.solver().latest_view + MAX_BID_VIEW_LOOKAHEAD / 2;
+        let bid = bid_helper(view, 200, vec![1], "http://builder");
+        client
+            .post::<()>("submit_bid")
+            .body_json(&bid)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        // Results are pushed for every view until the one we bid for closes
+        let mut last_view = None;
+        loop {
+            let result = results.next().await.unwrap().unwrap();
+            if let Some(last_view) = last_view {
+                assert!(result.view() > last_view);
+            }
+            last_view = Some(result.view());
+
+            if result.view() == ViewNumber::new(view) {
+                assert_eq!(result.winning_bids(), &[bid]);
+                assert!(result.reserve_bids().is_empty());
+                break;
+            }
+            assert!(result.view() < ViewNumber::new(view));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_auction_results_stream_skipped_views() {
+        let mut state = GlobalState::mock().await;
+        let mut results = state.subscribe_auction_results();
+
+        // Finishing view 1 closes the auction for view 2
+        state.view_finished(ViewNumber::new(1)).await.unwrap();
+        assert_eq!(results.next().await.unwrap().view(), ViewNumber::new(2));
+
+        // Skipping to view 4 closes the auctions for views 3 to 5
+        state.view_finished(ViewNumber::new(4)).await.unwrap();
+        for view in 3..=5 {
+            assert_eq!(results.next().await.unwrap().view(), ViewNumber::new(view));
+        }
+
+        // After a long gap, only the most recent auctions are pushed
+        let view = 100 + MAX_BID_VIEW_LOOKAHEAD;
+        state.view_finished(ViewNumber::new(view)).await.unwrap();
+        for view in view + 1 - MAX_BID_VIEW_LOOKAHEAD..=view + 1 {
+            assert_eq!(results.next().await.unwrap().view(), ViewNumber::new(view));
+        }
+        assert!(results.try_recv().is_err());
+    }
+
+    fn bid_helper(view: u64, amount: u64, namespaces: Vec<u64>, url: &str) -> BidTx {
+        let key = EthKeyPair::random();
+        BidTxBody::new(
+            key.fee_account(),
+            FeeAmount::from(amount),
+            ViewNumber::new(view),
+            namespaces.into_iter().map(NamespaceId::from).collect(),
+            Url::from_str(url).unwrap(),
+            FeeAmount::default(),
+        )
+        .signed(&key)
+        .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_auction_results() {
+        let mock_solver = MockSolver::init().await;
+        let solver_api = mock_solver.solver_api();
+        let client = surf_disco::Client::<SolverError, MarketplaceVersion>::new(solver_api);
+        client.connect(None).await;
+
+        // Register three rollups, each with a reserve price of 200
+        for ns in 1..=3 {
+            let (reg, _, _) =
+                register_rollup_helper(ns, Some("http://reserve"), 200, true, "test").await;
+            let _: RollupRegistration = client
+                .post("register_rollup")
+                .body_json(&reg)
+                .unwrap()
+                .send()
+                .await
+                .unwrap();
+        }
+
+        let view =
+            *mock_solver.state().read().await.solver().latest_view + MAX_BID_VIEW_LOOKAHEAD / 2;
+        // Highest bid, covering namespaces 1 and 2 and meeting both reserve prices
+        let multi_ns_bid = bid_helper(view, 500, vec![1, 2], "http://builder-a");
+        // Loses namespace 1 to the multi namespace bid
+        let single_ns_bid = bid_helper(view, 300, vec![1], "http://builder-b");
+        // Below the reserve price of namespace 3
+        let low_bid = bid_helper(view, 100, vec![3], "http://builder-c");
+        // Bids for an unregistered namespace never win
+        let unregistered_bid = bid_helper(view, 1000, vec![4], "http://builder-d");
+
+        for bid in [&multi_ns_bid, &single_ns_bid] {
+            client
+                .post::<()>("submit_bid")
+                .body_json(bid)
+                .unwrap()
+                .send()
+                .await
+                .unwrap();
+        }
+
+        // The solver rejects bids which cannot win...
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&low_bid)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, SolverError::BidBelowReservePrice { .. }),
+            "{err:?}"
+        );
+        let err = client
+            .post::<()>("submit_bid")
+            .body_json(&unregistered_bid)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        match err {
+            SolverError::BidNamespaceNotRegistered(ns) if ns == 4_u64.into() => (),
+            _ => panic!("err {err:?}"),
+        }
+
+        // ...but the auction must not select them even if they get into the state
+        {
+            let state = mock_solver.state();
+            let mut state = state.write().await;
+            let bids = state
+                .solver_mut()
+                .bid_txs
+                .entry(ViewNumber::new(view))
+                .or_default();
+            for bid in [&low_bid, &unregistered_bid] {
+                bids.insert(bid.account(), bid.clone());
+            }
+        }
+
+        let result: SolverAuctionResults = client
+            .get(&format!("auction_results/{view}"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(result.view(), ViewNumber::new(view));
+        assert_eq!(result.winning_bids(), &[multi_ns_bid]);
+        assert_eq!(
+            result.reserve_bids(),
+            &[(3_u64.into(), Url::from_str("http://reserve").unwrap())]
+        );
+
+        // A view without bids falls back to the reserve builders of every rollup
+        let result: SolverAuctionResults = client
+            .get(&format!("auction_results/{}", view + 1))
+            .send()
+            .await
+            .unwrap();
+
+        assert!(result.winning_bids().is_empty());
+        assert_eq!(result.reserve_bids().len(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_auction_reserve_price_overflow() {
+        // Two rollups whose reserve prices add up to more than a fee amount can hold
+        let huge = FeeAmount::from(u64::MAX) * u64::MAX * u64::MAX * u64::MAX;
+        let mut rollups = vec![];
+        for ns in 1..=2 {
+            let (mut reg, _, _) =
+                register_rollup_helper(ns, Some("http://reserve"), 0, true, "test").await;
+            reg.body.reserve_price = huge;
+            rollups.push(reg);
+        }
+
+        // A bid for both namespaces cannot meet their reserve price, but does not stop the
+        // auction
+        let view = 10;
+        let multi_ns_bid = bid_helper(view, u64::MAX, vec![1, 2], "http://builder-a");
+        let results = run_auction(ViewNumber::new(view), rollups, vec![multi_ns_bid]);
+        assert!(results.winning_bids().is_empty());
+        assert_eq!(results.reserve_bids().len(), 2);
+    }
 }
@@ -4,16 +4,16 @@ use anyhow::{bail, Context};
 use async_lock::RwLock;
 use async_once_cell::Lazy;
 use async_trait::async_trait;
-use committable::Commitment;
+use committable::{Commitment, Committable};
 use data_source::{CatchupDataSource, StakeTableDataSource, SubmitDataSource};
 use derivative::Derivative;
 use espresso_types::{
-    retain_accounts, v0::traits::SequencerPersistence, v0_99::ChainConfig, AccountQueryData,
-    BlockMerkleTree, FeeAccount, FeeAccountProof, FeeMerkleTree, NodeState, PubKey, Transaction,
-    ValidatedState,
+    retain_accounts, v0::traits::SequencerPersistence, v0_4::ChainConfig, v0_99::TransferTx,
+    AccountQueryData, FeeAccount, FeeAccountProof, FeeMerkleTree, NodeState, PubKey, SubmitError,
+    Transaction, TransactionStatus, ValidatedState,
 };
 use futures::{
-    future::{BoxFuture, Future, FutureExt},
+    future::{join_all, BoxFuture, Future, FutureExt},
     stream::BoxStream,
 };
 use hotshot_events_service::events_source::{
@@ -36,12 +36,15 @@ use hotshot_types::{stake_table::StakeTableEntry, traits::election::Membership};
 use jf_merkle_tree::MerkleTreeScheme;
 use std::sync::Arc;
 
-use self::data_source::{
-    HotShotConfigDataSource, NodeStateDataSource, PublicNetworkConfig, StateSignatureDataSource,
+use self::{
+    data_source::{
+        HotShotConfigDataSource, NodeStateDataSource, PublicNetworkConfig, StateSignatureDataSource,
+    },
+    tx_status::TxStatusTracker,
 };
 use crate::{
-    catchup::CatchupStorage, context::Consensus, state_signature::StateSigner, SeqTypes,
-    SequencerApiVersion, SequencerContext,
+    catchup::CatchupStorage, context::Consensus, external_event_handler::TransferSubmitter,
+    state_signature::StateSigner, SeqTypes, SequencerApiVersion, SequencerContext,
 };
 
 pub mod data_source;
@@ -49,11 +52,12 @@ pub mod endpoints;
 pub mod fs;
 pub mod options;
 pub mod sql;
+mod tx_status;
 mod update;
 
 pub use options::Options;
 
-pub type BlocksFrontier = <BlockMerkleTree as MerkleTreeScheme>::MembershipProof;
+pub use espresso_types::BlocksFrontier;
 
 type BoxLazy<T> = Pin<Arc<Lazy<T, BoxFuture<'static, T>>>>;
 
@@ -64,6 +68,7 @@ struct ConsensusState<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: V
     event_streamer: Arc<RwLock<EventsStreamer<SeqTypes>>>,
     node_state: NodeState,
     network_config: NetworkConfig<PubKey>,
+    transfer_submitter: TransferSubmitter,
 
     #[derivative(Debug = "ignore")]
     handle: Arc<RwLock<Consensus<N, P, V>>>,
@@ -78,6 +83,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions>
             event_streamer: ctx.event_streamer(),
             node_state: ctx.node_state(),
             network_config: ctx.network_config(),
+            transfer_submitter: ctx.transfer_submitter(),
             handle: ctx.consensus(),
         }
     }
@@ -92,12 +98,14 @@ struct ApiState<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Version
     // without waiting.
     #[derivative(Debug = "ignore")]
     consensus: BoxLazy<ConsensusState<N, P, V>>,
+    tx_status: Arc<RwLock<TxStatusTracker>>,
 }
 
 impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> ApiState<N, P, V> {
     fn new(init: impl Future<Output = ConsensusState<N, P, V>> + Send + 'static) -> Self {
         Self {
             consensus: Arc::pin(Lazy::from_future(init.boxed())),
+            tx_status: Default::default(),
         }
     }
 
@@ -109,6 +117,16 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> ApiState
         &self.consensus.as_ref().get().await.get_ref().event_streamer
     }
 
+    async fn transfer_submitter(&self) -> &TransferSubmitter {
+        &self
+            .consensus
+            .as_ref()
+            .get()
+            .await
+            .get_ref()
+            .transfer_submitter
+    }
+
     async fn consensus(&self) -> Arc<RwLock<Consensus<N, P, V>>> {
         Arc::clone(&self.consensus.as_ref().get().await.get_ref().handle)
     }
@@ -159,6 +177,18 @@ impl<N: ConnectedNetwork<PubKey>, D: Send + Sync, V: Versions, P: SequencerPersi
     async fn submit(&self, tx: Transaction) -> anyhow::Result<()> {
         self.as_ref().submit(tx).await
     }
+
+    async fn submit_batch(&self, txs: Vec<Transaction>) -> Vec<Result<(), SubmitError>> {
+        self.as_ref().submit_batch(txs).await
+    }
+
+    async fn transaction_status(&self, hash: Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.as_ref().transaction_status(hash).await
+    }
+
+    async fn submit_transfer(&self, tx: TransferTx) -> anyhow::Result<()> {
+        self.as_ref().submit_transfer(tx).await
+    }
 }
 
 impl<N: ConnectedNetwork<PubKey>, D: Sync, V: Versions, P: SequencerPersistence>
@@ -197,22 +227,13 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence>
     }
 }
 
-impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> SubmitDataSource<N, P>
-    for ApiState<N, P, V>
-{
-    async fn submit(&self, tx: Transaction) -> anyhow::Result<()> {
-        let handle = self.consensus().await;
-
-        let consensus_read_lock = handle.read().await;
-
+impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> ApiState<N, P, V> {
+    /// The maximum size of a block under the current chain config.
+    async fn max_block_size(&self, consensus: &Consensus<N, P, V>) -> u64 {
         // Fetch full chain config from the validated state, if present.
         // This is necessary because we support chain config upgrades,
         // so the updated chain config is found in the validated state.
-        let cf = consensus_read_lock
-            .decided_state()
-            .await
-            .chain_config
-            .resolve();
+        let cf = consensus.decided_state().await.chain_config.resolve();
 
         // Use the chain config from the validated state if available,
         // otherwise, use the node state's chain config
@@ -221,18 +242,58 @@ impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> SubmitDa
             Some(cf) => cf,
             None => self.node_state().await.chain_config,
         };
+        cf.max_block_size.into()
+    }
+}
 
-        let max_block_size: u64 = cf.max_block_size.into();
-        let txn_size = tx.payload().len() as u64;
+impl<N: ConnectedNetwork<PubKey>, V: Versions, P: SequencerPersistence> SubmitDataSource<N, P>
+    for ApiState<N, P, V>
+{
+    async fn submit(&self, tx: Transaction) -> anyhow::Result<()> {
+        let handle = self.consensus().await;
+        let consensus_read_lock = handle.read().await;
 
         // reject transaction bigger than block size
-        if txn_size > max_block_size {
-            bail!("transaction size ({txn_size}) is greater than max_block_size ({max_block_size})")
-        }
+        tx.validate_size(self.max_block_size(&consensus_read_lock).await)?;
 
+        let hash = tx.commit();
         consensus_read_lock.submit_transaction(tx).await?;
+        self.tx_status.write().await.submitted(hash);
         Ok(())
     }
+
+    async fn submit_batch(&self, txs: Vec<Transaction>) -> Vec<Result<(), SubmitError>> {
+        let handle = self.consensus().await;
+        let consensus_read_lock = handle.read().await;
+        let max_block_size = self.max_block_size(&consensus_read_lock).await;
+
+        // HotShot takes transactions one at a time, so hand over the whole batch at once rather
+        // than waiting for each transaction in turn.
+        join_all(txs.into_iter().map(|tx| {
+            let consensus_read_lock = &consensus_read_lock;
+            async move {
+                // reject transaction bigger than block size
+                tx.validate_size(max_block_size)?;
+
+                let hash = tx.commit();
+                consensus_read_lock
+                    .submit_transaction(tx)
+                    .await
+                    .map_err(|err| SubmitError::Failed(format!("{err:#}")))?;
+                self.tx_status.write().await.submitted(hash);
+                Ok::<_, SubmitError>(())
+            }
+        }))
+        .await
+    }
+
+    async fn transaction_status(&self, hash: Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.tx_status.read().await.status(&hash)
+    }
+
+    async fn submit_transfer(&self, tx: TransferTx) -> anyhow::Result<()> {
+        self.transfer_submitter().await.submit(tx).await
+    }
 }
 
 impl<N, P, D, V> NodeStateDataSource for StorageState<N, P, D, V>
@@ -499,7 +560,7 @@ pub mod test_helpers {
     use espresso_types::MockSequencerVersions;
     use espresso_types::{
         v0::traits::{NullEventConsumer, PersistenceOptions, StateCatchup},
-        MarketplaceVersion, NamespaceId, ValidatedState,
+        BlockMerkleTree, MarketplaceVersion, NamespaceId, ValidatedState,
     };
     use ethers::{prelude::Address, utils::Anvil};
     use futures::{
@@ -854,8 +915,46 @@ pub mod test_helpers {
             .unwrap();
         assert_eq!(txn.commit(), hash);
 
+        // The node tracks the status of the transaction until it is included.
+        let status: TransactionStatus = client
+            .get(&format!("submit/status/{hash}"))
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            matches!(
+                status,
+                TransactionStatus::Pending | TransactionStatus::Included { .. }
+            ),
+            "{status:?}"
+        );
+
         // Wait for a Decide event containing transaction matching the one we sent
-        wait_for_decide_on_handle(&mut events, &txn).await;
+        let block_height = wait_for_decide_on_handle(&mut events, &txn).await;
+
+        // The API may process the decide event slightly after the handle stream.
+        let status = loop {
+            let status: TransactionStatus = client
+                .get(&format!("submit/status/{hash}"))
+                .send()
+                .await
+                .unwrap();
+            if status != TransactionStatus::Pending {
+                break status;
+            }
+            sleep(Duration::from_millis(100)).await;
+        };
+        match status {
+            TransactionStatus::Included {
+                block_height: height,
+                namespace,
+                ..
+            } => {
+                assert_eq!(height, block_height);
+                assert_eq!(namespace, txn.namespace());
+            }
+            _ => panic!("unexpected status {status:?}"),
+        }
     }
 
     /// Test the state signature API.
@@ -1052,12 +1151,16 @@ pub mod test_helpers {
 mod api_tests {
     use committable::Committable;
     use data_source::testing::TestableSequencerDataSource;
-    use endpoints::NamespaceProofQueryData;
+    use endpoints::{
+        NamespaceMultiProofQueryData, NamespaceProofQueryData, NamespaceProofRangeEntry,
+        TransactionProofQueryData,
+    };
 
+    use client::SequencerClient;
     use espresso_types::MockSequencerVersions;
     use espresso_types::{
         traits::{EventConsumer, PersistenceOptions},
-        Header, Leaf, Leaf2, NamespaceId,
+        Header, Leaf, Leaf2, NamespaceId, NsNonInclusionProof,
     };
     use ethers::utils::Anvil;
     use futures::{future, stream::StreamExt};
@@ -1136,6 +1239,8 @@ mod api_tests {
         let client: Client<ServerError, StaticVersion<0, 1>> =
             Client::new(format!("http://localhost:{port}").parse().unwrap());
         client.connect(None).await;
+        let sequencer_client =
+            SequencerClient::new(format!("http://localhost:{port}").parse().unwrap());
 
         let hash = client
             .post("submit/submit")
@@ -1163,6 +1268,7 @@ mod api_tests {
 
         let mut found_txn = false;
         let mut found_empty_block = false;
+        let mut expected_stream = vec![];
         for block_num in 0..=block_height {
             let header: Header = client
                 .get(&format!("availability/header/{block_num}"))
@@ -1175,6 +1281,11 @@ mod api_tests {
                 .await
                 .unwrap();
 
+            expected_stream.push((
+                ns_query_res.proof.is_some(),
+                ns_query_res.transactions.clone(),
+            ));
+
             // Verify namespace proof if present
             if let Some(ns_proof) = ns_query_res.proof {
                 let vid_common: VidCommonQueryData<SeqTypes> = client
@@ -1194,8 +1305,35 @@ mod api_tests {
                 // Namespace proof should be present if ns_id exists in ns_table
                 assert!(header.ns_table().find_ns_id(&ns_id).is_none());
                 assert!(ns_query_res.transactions.is_empty());
+
+                // Absence of the namespace is proven instead.
+                ns_query_res
+                    .non_inclusion_proof
+                    .as_ref()
+                    .unwrap()
+                    .verify(&header.ns_table().commit(), &ns_id)
+                    .unwrap();
+                let non_inclusion: NsNonInclusionProof = client
+                    .get(&format!(
+                        "availability/block/{block_num}/namespace/{ns_id}/non-inclusion"
+                    ))
+                    .send()
+                    .await
+                    .unwrap();
+                non_inclusion
+                    .verify(&header.ns_table().commit(), &ns_id)
+                    .unwrap();
             }
 
+            // The client should get the same transactions, after verifying them itself.
+            assert_eq!(
+                sequencer_client
+                    .fetch_namespace(block_num as u64, ns_id)
+                    .await
+                    .unwrap(),
+                ns_query_res.transactions
+            );
+
             found_empty_block = found_empty_block || ns_query_res.transactions.is_empty();
 
             for txn in ns_query_res.transactions {
@@ -1207,6 +1345,95 @@ mod api_tests {
         }
         assert!(found_txn);
         assert!(found_empty_block);
+
+        // The namespace stream yields the same data for every block, including those where the
+        // namespace is absent.
+        let streamed: Vec<_> = client
+            .socket(&format!("availability/stream/blocks/0/namespace/{ns_id}"))
+            .subscribe::<NamespaceProofQueryData>()
+            .await
+            .unwrap()
+            .take(block_height + 1)
+            .map(|res| {
+                let res = res.unwrap();
+                (res.proof.is_some(), res.transactions)
+            })
+            .collect()
+            .await;
+        assert_eq!(streamed, expected_stream);
+
+        // The range endpoint returns the same data, along with the header of each block.
+        let range: Vec<NamespaceProofRangeEntry> = client
+            .get(&format!(
+                "availability/block/0/{}/namespace/{ns_id}",
+                block_height + 1
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(range.len(), block_height + 1);
+        for (block_num, entry) in range.into_iter().enumerate() {
+            assert_eq!(entry.header.height(), block_num as u64);
+            if let Some(ns_proof) = &entry.proof {
+                let vid_common: VidCommonQueryData<SeqTypes> = client
+                    .get(&format!("availability/vid/common/{block_num}"))
+                    .send()
+                    .await
+                    .unwrap();
+                ns_proof
+                    .verify(
+                        entry.header.ns_table(),
+                        &entry.header.payload_commitment(),
+                        vid_common.common(),
+                    )
+                    .unwrap();
+            }
+            assert_eq!(
+                (entry.proof.is_some(), entry.transactions),
+                expected_stream[block_num]
+            );
+        }
+
+        // Prove the inclusion of just the transaction we submitted.
+        let tx_proof: TransactionProofQueryData = client
+            .get(&format!("availability/transaction/hash/{hash}/proof"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(tx_proof.header.height(), block_height as u64);
+        assert_eq!(tx_proof.transaction, txn);
+        assert!(tx_proof
+            .proof
+            .verify(
+                tx_proof.header.ns_table(),
+                &tx_proof.transaction,
+                &tx_proof.header.payload_commitment(),
+                &tx_proof.vid_common,
+            )
+            .unwrap());
+
+        // Prove several namespaces of that block at once. Namespaces absent from the block are left
+        // out.
+        let multi_proof: NamespaceMultiProofQueryData = client
+            .get(&format!(
+                "availability/block/{block_height}/namespaces/{ns_id},{}",
+                u32::MAX
+            ))
+            .send()
+            .await
+            .unwrap();
+        let namespaces = multi_proof
+            .proof
+            .verify(
+                tx_proof.header.ns_table(),
+                &tx_proof.header.payload_commitment(),
+                &tx_proof.vid_common,
+            )
+            .unwrap();
+        assert_eq!(namespaces, multi_proof.namespaces);
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].0, ns_id);
+        assert!(namespaces[0].1.contains(&txn));
     }
 
     #[tokio::test(flavor = "multi_thread")]
@@ -1509,6 +1736,7 @@ mod api_tests {
 
 #[cfg(test)]
 mod test {
+    use client::SequencerClient;
     use committable::{Commitment, Committable};
     use std::{collections::BTreeMap, time::Duration};
     use tokio::time::sleep;
@@ -1516,8 +1744,9 @@ mod test {
     use espresso_types::{
         traits::NullEventConsumer,
         v0_1::{UpgradeMode, ViewBasedUpgrade},
-        BackoffParams, FeeAccount, FeeAmount, FeeVersion, Header, MarketplaceVersion,
-        MockSequencerVersions, SequencerVersions, TimeBasedUpgrade, Timestamp, Upgrade,
+        BackoffParams, BlockMerkleTree, FeeAccount, FeeAmount, FeeVersion, Header,
+        MarketplaceVersion, MockSequencerVersions, NamespaceId, SequencerApiError,
+        SequencerVersions, TimeBasedUpgrade, Timestamp, TransactionValidationError, Upgrade,
         UpgradeType, ValidatedState,
     };
     use ethers::utils::Anvil;
@@ -1535,7 +1764,10 @@ mod test {
         traits::{metrics::NoMetrics, node_implementation::ConsensusTime},
         ValidatorConfig,
     };
-    use jf_merkle_tree::prelude::{MerkleProof, Sha3Node};
+    use jf_merkle_tree::{
+        prelude::{MerkleProof, Sha3Node},
+        MerkleCommitment,
+    };
     use portpicker::pick_unused_port;
     use sequencer_utils::{ser::FromStringOrInteger, test_utils::setup_test};
     use surf_disco::Client;
@@ -1556,7 +1788,7 @@ mod test {
     use crate::{
         catchup::{NullStateCatchup, StatePeers},
         persistence::no_storage,
-        testing::{TestConfig, TestConfigBuilder},
+        testing::{wait_for_decide_on_handle, TestConfig, TestConfigBuilder},
     };
 
     #[tokio::test(flavor = "multi_thread")]
@@ -1591,6 +1823,218 @@ mod test {
         submit_test_helper(|opt| opt).await
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_submit_validation() {
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let url = format!("http://localhost:{port}").parse().unwrap();
+        let client: Client<SequencerApiError, StaticVersion<0, 1>> = Client::new(url);
+        let options = Options::with_port(port).submit(options::Submit {
+            namespace_max_tx_size: vec![(NamespaceId::from(2_u32), 4)],
+            ..Default::default()
+        });
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint().parse().unwrap();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(options)
+            .network_config(network_config)
+            .build();
+        let _network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+
+        client.connect(None).await;
+
+        // Rejections carry the reason the transaction is invalid.
+        let submit_err = |txn: Transaction| {
+            let client = client.clone();
+            async move {
+                let err = client
+                    .post::<Commitment<Transaction>>("submit/submit")
+                    .body_json(&txn)
+                    .unwrap()
+                    .send()
+                    .await
+                    .unwrap_err();
+                let SequencerApiError::InvalidTransaction(err) = err else {
+                    panic!("unexpected error {err:?}");
+                };
+                err
+            }
+        };
+
+        // A transaction which cannot fit in a block is rejected.
+        let max_block_size: u64 = ChainConfig::default().max_block_size.into();
+        let txn = Transaction::of_size(max_block_size as usize);
+        assert_eq!(
+            submit_err(txn.clone()).await,
+            TransactionValidationError::TooLarge {
+                size: txn.size_in_block(true),
+                max_block_size,
+            }
+        );
+
+        // A transaction exceeding the limit of its namespace is rejected.
+        let txn = Transaction::new(NamespaceId::from(2_u32), vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            submit_err(txn).await,
+            TransactionValidationError::NamespaceSizeLimitExceeded {
+                namespace: NamespaceId::from(2_u32),
+                size: 5,
+                limit: 4,
+            }
+        );
+
+        // The same payload is accepted in a namespace without a limit.
+        let txn = Transaction::new(NamespaceId::from(1_u32), vec![1, 2, 3, 4, 5]);
+        let hash = client
+            .post("submit/submit")
+            .body_json(&txn)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(txn.commit(), hash);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_submit_transfer() {
+        use espresso_types::v0_99::{TransferTx, TransferTxBody};
+        use hotshot_types::traits::signature_key::BuilderSignatureKey;
+        use tide_disco::{Error as _, StatusCode};
+
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let url = format!("http://localhost:{port}").parse().unwrap();
+        let client: Client<ServerError, StaticVersion<0, 1>> = Client::new(url);
+        let options = Options::with_port(port).submit(Default::default());
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint().parse().unwrap();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(options)
+            .network_config(network_config)
+            .build();
+        let network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+
+        client.connect(None).await;
+
+        // A view far enough ahead that no node proposes in it during the test.
+        let view = ViewNumber::new(1_000_000);
+        let key = FeeAccount::test_key_pair();
+        let recipient = FeeAccount::generated_from_seed_indexed([1; 32], 0).0;
+        let tx = TransferTxBody::new(key.fee_account(), recipient, 1.into(), view)
+            .signed(&key)
+            .unwrap();
+        let hash: Commitment<TransferTx> = client
+            .post("submit/transfer")
+            .body_json(&tx)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(hash, tx.commit());
+
+        // The transfer reaches every node, so whichever leads its view can include it.
+        for node in std::iter::once(&network.server).chain(&network.peers) {
+            let pending = &node.node_state().pending_transfers;
+            let mut retries = 0;
+            let txs = loop {
+                let txs = pending.take(view).await;
+                if !txs.is_empty() || retries == 50 {
+                    break txs;
+                }
+                retries += 1;
+                sleep(Duration::from_millis(100)).await;
+            };
+            assert_eq!(txs, vec![tx.clone()]);
+        }
+
+        // A transfer not signed by the sending account is rejected.
+        let forged = TransferTxBody::new(recipient, key.fee_account(), 1.into(), view)
+            .signed(&key)
+            .unwrap();
+        let err = client
+            .post::<Commitment<TransferTx>>("submit/transfer")
+            .body_json(&forged)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_submit_batch() {
+        use tide_disco::{Error as _, StatusCode};
+
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let url = format!("http://localhost:{port}").parse().unwrap();
+        let client: Client<ServerError, StaticVersion<0, 1>> = Client::new(url);
+        let options = Options::with_port(port).submit(options::Submit {
+            max_batch_size: 3,
+            ..Default::default()
+        });
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint().parse().unwrap();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(options)
+            .network_config(network_config)
+            .build();
+        let network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+        let mut events = network.server.event_stream().await;
+
+        client.connect(None).await;
+
+        let max_block_size: u64 = ChainConfig::default().max_block_size.into();
+        let txs = vec![
+            Transaction::new(NamespaceId::from(1_u32), vec![1, 2, 3, 4]),
+            Transaction::of_size(max_block_size as usize),
+            Transaction::new(NamespaceId::from(2_u32), vec![5, 6, 7, 8]),
+        ];
+        let results: Vec<Result<Commitment<Transaction>, SubmitError>> = client
+            .post("submit/batch")
+            .body_json(&txs)
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        // The oversized transaction is rejected without affecting the rest of the batch.
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(txs[0].commit()));
+        assert!(
+            matches!(
+                results[1],
+                Err(SubmitError::Invalid(
+                    TransactionValidationError::TooLarge { .. }
+                ))
+            ),
+            "{:?}",
+            results[1]
+        );
+        assert_eq!(results[2], Ok(txs[2].commit()));
+
+        // Batches larger than the limit are rejected as a whole.
+        let too_many = (0..4)
+            .map(|i| Transaction::new(NamespaceId::from(3_u32), vec![i]))
+            .collect::<Vec<_>>();
+        let err = client
+            .post::<Vec<Result<Commitment<Transaction>, SubmitError>>>("submit/batch")
+            .body_json(&too_many)
+            .unwrap()
+            .send()
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+
+        wait_for_decide_on_handle(&mut events, &txs[0]).await;
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn state_signature_test_without_query_module() {
         state_signature_test_helper(|opt| opt).await
@@ -2406,6 +2850,234 @@ mod test {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_sequencer_client() {
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let url: surf_disco::Url = format!("http://localhost:{port}").parse().unwrap();
+
+        let storage = SqlDataSource::create_storage().await;
+        let options = SqlDataSource::options(
+            &storage,
+            Options::with_port(port)
+                .state(Default::default())
+                .submit(Default::default())
+                .catchup(Default::default())
+                .config(Default::default())
+                .explorer(Default::default()),
+        );
+        let anvil = Anvil::new().spawn();
+        let l1 = anvil.endpoint().parse().unwrap();
+        let network_config = TestConfigBuilder::default().l1_url(l1).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(options)
+            .network_config(network_config)
+            .build();
+        let network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+        let mut events = network.server.event_stream().await;
+
+        // The first provider is bogus, so every request has to fail over to the real node.
+        let client = SequencerClient::with_failover([
+            "https://notarealnode.network".parse().unwrap(),
+            url.clone(),
+        ]);
+
+        // Submissions are only sent to the preferred node.
+        let ns_id = NamespaceId::from(42_u32);
+        let txn = Transaction::new(ns_id, vec![1, 2, 3, 4]);
+        client.submit(&txn).await.unwrap_err();
+        let submitter = SequencerClient::new(url);
+
+        // Submit a transaction and wait for it to be sequenced.
+        let hash = submitter.submit(&txn).await.unwrap();
+        assert_eq!(hash, txn.commit());
+        let block_height = wait_for_decide_on_handle(&mut events, &txn).await;
+        tracing::info!(block_height, "transaction sequenced");
+
+        // Wait for the query service to update to this block height.
+        client
+            .subscribe_headers(block_height)
+            .await
+            .unwrap()
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Submit API.
+        let status = loop {
+            match client.get_transaction_status(hash).await.unwrap() {
+                TransactionStatus::Pending => sleep(Duration::from_millis(100)).await,
+                status => break status,
+            }
+        };
+        assert_eq!(
+            status,
+            TransactionStatus::Included {
+                block_height,
+                namespace: ns_id,
+                namespace_index: client
+                    .get_header(block_height)
+                    .await
+                    .unwrap()
+                    .ns_table()
+                    .find_ns_id(&ns_id)
+                    .unwrap(),
+            }
+        );
+        let batch = vec![
+            Transaction::new(ns_id, vec![5]),
+            Transaction::new(ns_id, vec![6]),
+        ];
+        assert_eq!(
+            submitter.submit_batch(&batch).await.unwrap(),
+            batch.iter().map(|tx| Ok(tx.commit())).collect::<Vec<_>>()
+        );
+
+        // Availability API.
+        let txs = client.fetch_namespace(block_height, ns_id).await.unwrap();
+        assert!(txs.contains(&txn));
+        let multi = client
+            .get_namespace_multi_proof(block_height, &[ns_id])
+            .await
+            .unwrap();
+        assert_eq!(multi.namespaces, vec![(ns_id, txs.clone())]);
+        let range = client
+            .get_namespace_proof_range(block_height, block_height + 1, ns_id)
+            .await
+            .unwrap();
+        assert_eq!(range.len(), 1);
+        assert_eq!(range[0].transactions, txs);
+        let mut stream = client
+            .subscribe_namespace_proofs(block_height, ns_id)
+            .await
+            .unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap().transactions, txs);
+
+        let proof = client.get_transaction_proof(hash).await.unwrap();
+        assert_eq!(proof.transaction, txn);
+        assert!(proof
+            .proof
+            .verify(
+                proof.header.ns_table(),
+                &txn,
+                &proof.header.payload_commitment(),
+                &proof.vid_common
+            )
+            .unwrap());
+
+        let absent = NamespaceId::from(43_u32);
+        client
+            .get_namespace_non_inclusion_proof(block_height, absent)
+            .await
+            .unwrap()
+            .verify(&proof.header.ns_table().commit(), &absent)
+            .unwrap();
+
+        let leaf = client.get_leaf(block_height).await.unwrap();
+        assert_eq!(leaf.height(), block_height);
+        let block = client.get_block(block_height).await.unwrap();
+        assert_eq!(block.hash(), leaf.block_hash());
+        let payload = client.get_payload(block_height).await.unwrap();
+        assert_eq!(payload.data(), block.payload());
+        let tx = client.get_transaction(hash).await.unwrap();
+        assert_eq!(tx.block_height(), block_height);
+        assert_eq!(tx.transaction(), &txn);
+
+        // Node API.
+        assert!(client.get_height().await.unwrap() > block_height);
+        assert!(client.get_transaction_count().await.unwrap() >= 1);
+        assert!(!client.get_current_stake_table().await.unwrap().is_empty());
+        client.get_sync_status().await.unwrap();
+        client.get_vid_share(block_height).await.unwrap();
+
+        // Explorer API.
+        let detail = client
+            .get_explorer_block_detail(block_height)
+            .await
+            .unwrap();
+        assert_eq!(detail.height, block_height);
+        let summaries = client
+            .get_explorer_block_summaries(Some(block_height), 1)
+            .await
+            .unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].height, block_height);
+        client.get_explorer_transaction_detail(hash).await.unwrap();
+        assert!(!client
+            .get_explorer_transaction_summaries(None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        client.get_explorer_summary().await.unwrap();
+        client.explorer_search(&hash.to_string()).await.unwrap();
+
+        // Merklized state API.
+        let builder = TestConfig::<5>::builder_key().fee_account();
+        assert!(
+            client
+                .get_latest_espresso_balance(builder.address())
+                .await
+                .unwrap()
+                > 0.into()
+        );
+
+        // Config API.
+        let validator = ValidatorConfig::generated_from_seed_indexed([0; 32], 1, 1, false);
+        let config = client
+            .get_hotshot_config()
+            .await
+            .unwrap()
+            .into_network_config(validator)
+            .unwrap();
+        assert_eq!(config.node_index, 1);
+        assert!(!client.get_env().await.unwrap().is_empty());
+
+        // State signature API.
+        client.get_state_signature(block_height).await.unwrap();
+
+        // Catchup API. Stop consensus so we can query a consistent undecided state.
+        network.server.shutdown_consensus().await;
+        let leaf = network.server.decided_leaf().await;
+        let height = leaf.height() + 1;
+        let view = leaf.view_number() + 1;
+        let state = network.server.state(view).await.unwrap();
+
+        let account = client
+            .get_account(height, view, FeeAccount::default())
+            .await
+            .unwrap();
+        assert_eq!(account.balance, 0.into());
+        account
+            .proof
+            .verify(&state.fee_merkle_tree.commitment())
+            .unwrap();
+        let accounts = client.get_accounts(height, view, &[builder]).await.unwrap();
+        assert_eq!(accounts.commitment(), state.fee_merkle_tree.commitment());
+
+        let frontier = client.get_blocks_frontier(height, view).await.unwrap();
+        let root = state.block_merkle_tree.commitment();
+        BlockMerkleTree::verify(root.digest(), root.size() - 1, frontier)
+            .unwrap()
+            .unwrap();
+
+        let chain_config = network
+            .server
+            .decided_state()
+            .await
+            .chain_config
+            .resolve()
+            .unwrap();
+        assert_eq!(
+            client
+                .get_chain_config(chain_config.commit())
+                .await
+                .unwrap(),
+            chain_config
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_hotshot_event_streaming() {
         setup_test();
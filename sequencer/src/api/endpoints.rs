@@ -3,14 +3,27 @@
 use std::{
     collections::{BTreeSet, HashMap},
     env,
+    sync::Arc,
+    time::Duration,
 };
 
 use anyhow::Result;
-use committable::Committable;
-use espresso_types::{FeeAccount, FeeMerkleTree, NamespaceId, NsProof, PubKey, Transaction};
-use futures::{try_join, FutureExt};
+use committable::{Commitment, Committable};
+use espresso_types::{
+    v0_99::TransferTx, ExecutionError, FeeAccount, FeeMerkleTree, NamespaceId, NsMultiProof,
+    NsNonInclusionProof, NsProof, PubKey, SequencerApiError, SubmitError, Transaction,
+    TransactionValidationError, TxProof,
+};
+pub use espresso_types::{
+    NamespaceMultiProofQueryData, NamespaceProofQueryData, NamespaceProofRangeEntry,
+    TransactionProofQueryData,
+};
+use futures::{try_join, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use hotshot_query_service::{
-    availability::{self, AvailabilityDataSource, CustomSnafu, FetchBlockSnafu},
+    availability::{
+        self, AvailabilityDataSource, BlockQueryData, CustomSnafu, FetchBlockSnafu, FetchLeafSnafu,
+        FetchTransactionSnafu, QueryablePayload, VidCommonQueryData,
+    },
     explorer::{self, ExplorerDataSource},
     merklized_state::{
         self, MerklizedState, MerklizedStateDataSource, MerklizedStateHeightPersistence,
@@ -21,12 +34,13 @@ use hotshot_query_service::{merklized_state::Snapshot, node::NodeDataSource};
 use hotshot_types::{
     data::{EpochNumber, ViewNumber},
     traits::{
+        block_contents::BlockHeader,
         network::ConnectedNetwork,
         node_implementation::{ConsensusTime, Versions},
     },
 };
 use jf_merkle_tree::MerkleTreeScheme;
-use serde::{de::Error as _, Deserialize, Serialize};
+use serde::de::Error as _;
 use snafu::OptionExt;
 use tagged_base64::TaggedBase64;
 use tide_disco::{method::ReadState, Api, Error as _, StatusCode};
@@ -37,16 +51,10 @@ use super::{
         CatchupDataSource, HotShotConfigDataSource, NodeStateDataSource, SequencerDataSource,
         StakeTableDataSource, StateSignatureDataSource, SubmitDataSource,
     },
-    StorageState,
+    options, StorageState,
 };
 use crate::{SeqTypes, SequencerApiVersion, SequencerPersistence};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct NamespaceProofQueryData {
-    pub proof: Option<NsProof>,
-    pub transactions: Vec<Transaction>,
-}
-
 pub(super) fn get_balance<State, Ver>() -> Result<Api<State, merklized_state::Error, Ver>>
 where
     State: 'static + Send + Sync + ReadState,
@@ -100,6 +108,9 @@ where
     let extension = toml::from_str(include_str!("../../api/availability.toml"))?;
     options.extensions.push(extension);
     let timeout = options.fetch_timeout;
+    // Each namespace proof carries a block's worth of transactions in the worst case, so we cap
+    // range queries the same way the query service caps ranges of blocks.
+    let range_limit = options.large_object_range_limit;
 
     let mut api = availability::define_api::<AvailState<N, P, D, _>, SeqTypes, _>(
         &options,
@@ -110,55 +121,248 @@ where
         async move {
             let height: usize = req.integer_param("height")?;
             let ns_id = NamespaceId::from(req.integer_param::<_, u32>("namespace")?);
-            let (block, common) = try_join!(
-                async move {
-                    state
-                        .get_block(height)
-                        .await
-                        .with_timeout(timeout)
-                        .await
-                        .context(FetchBlockSnafu {
-                            resource: height.to_string(),
-                        })
+            let (block, common) = fetch_block_and_vid_common(state, height, timeout).await?;
+
+            namespace_proof(&block, &common, ns_id)
+        }
+        .boxed()
+    })?
+    .get("getnamespacemultiproof", move |req, state| {
+        async move {
+            let height: usize = req.integer_param("height")?;
+            let ns_ids = req
+                .string_param("namespaces")?
+                .split(',')
+                .map(|ns| ns.trim().parse::<u32>().map(NamespaceId::from))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| availability::Error::Custom {
+                    message: format!("invalid namespace list: {err}"),
+                    status: StatusCode::BAD_REQUEST,
+                })?;
+            let (block, common) = fetch_block_and_vid_common(state, height, timeout).await?;
+
+            // Namespaces absent from the block are left out of the proof.
+            let ns_table = block.payload().ns_table();
+            let indices: Vec<_> = ns_ids
+                .iter()
+                .filter_map(|ns_id| ns_table.find_ns_id(ns_id))
+                .collect();
+            let proof = NsMultiProof::new(block.payload(), &indices, common.common()).context(
+                CustomSnafu {
+                    message: format!("failed to make proof for namespaces {ns_ids:?}"),
+                    status: StatusCode::NOT_FOUND,
                 },
-                async move {
-                    state
-                        .get_vid_common(height)
-                        .await
-                        .with_timeout(timeout)
-                        .await
-                        .context(FetchBlockSnafu {
-                            resource: height.to_string(),
-                        })
-                }
             )?;
 
-            if let Some(ns_index) = block.payload().ns_table().find_ns_id(&ns_id) {
-                let proof = NsProof::new(block.payload(), &ns_index, common.common()).context(
-                    CustomSnafu {
-                        message: format!("failed to make proof for namespace {ns_id}"),
-                        status: StatusCode::NOT_FOUND,
-                    },
-                )?;
+            Ok(NamespaceMultiProofQueryData {
+                namespaces: proof.export_all_txs(ns_table),
+                proof,
+            })
+        }
+        .boxed()
+    })?
+    .get("getnamespacenoninclusionproof", move |req, state| {
+        async move {
+            let height: usize = req.integer_param("height")?;
+            let ns_id = NamespaceId::from(req.integer_param::<_, u32>("namespace")?);
+            let leaf = state
+                .get_leaf(height)
+                .await
+                .with_timeout(timeout)
+                .await
+                .context(FetchLeafSnafu {
+                    resource: height.to_string(),
+                })?;
+            NsNonInclusionProof::new(leaf.header().ns_table(), &ns_id).context(CustomSnafu {
+                message: format!("namespace {ns_id} is present in block {height}"),
+                status: StatusCode::NOT_FOUND,
+            })
+        }
+        .boxed()
+    })?
+    .get("gettransactionproof", move |req, state| {
+        async move {
+            let hash = req.blob_param("hash")?;
+            let tx = state
+                .get_transaction(hash)
+                .await
+                .with_timeout(timeout)
+                .await
+                .context(FetchTransactionSnafu {
+                    resource: hash.to_string(),
+                })?;
+            let height = tx.block_height() as usize;
+            let (block, common) = fetch_block_and_vid_common(state, height, timeout).await?;
+
+            let payload = block.payload();
+            let index = payload
+                .iter(block.header().metadata())
+                .find(|index| {
+                    payload
+                        .transaction(index)
+                        .is_some_and(|tx| tx.commit() == hash)
+                })
+                .context(CustomSnafu {
+                    message: format!("transaction {hash} not found in block {height}"),
+                    status: StatusCode::NOT_FOUND,
+                })?;
+            let (transaction, proof) =
+                TxProof::new(&index, payload, common.common()).context(CustomSnafu {
+                    message: format!("failed to make proof for transaction {hash}"),
+                    status: StatusCode::NOT_FOUND,
+                })?;
+
+            Ok(TransactionProofQueryData {
+                header: block.header().clone(),
+                ns_index: index.ns().clone(),
+                tx_index: index.tx().clone(),
+                transaction,
+                proof,
+                vid_common: common.common().clone(),
+            })
+        }
+        .boxed()
+    })?
+    .get("getnamespaceproof_range", move |req, state| {
+        async move {
+            let from: usize = req.integer_param("from")?;
+            let until: usize = req.integer_param("until")?;
+            let ns_id = NamespaceId::from(req.integer_param::<_, u32>("namespace")?);
 
-                Ok(NamespaceProofQueryData {
-                    transactions: proof.export_all_txs(&ns_id),
-                    proof: Some(proof),
+            // Return at most `range_limit` entries; clients page through longer ranges by
+            // continuing from the block after the last entry returned.
+            let until = until.clamp(from, from.saturating_add(range_limit));
+
+            let (blocks, common) = try_join!(
+                state
+                    .get_block_range(from..until)
+                    .await
+                    .enumerate()
+                    .then(|(i, fetch)| async move {
+                        fetch.with_timeout(timeout).await.context(FetchBlockSnafu {
+                            resource: (from + i).to_string(),
+                        })
+                    })
+                    .try_collect::<Vec<_>>(),
+                state
+                    .get_vid_common_range(from..until)
+                    .await
+                    .enumerate()
+                    .then(|(i, fetch)| async move {
+                        fetch.with_timeout(timeout).await.context(FetchBlockSnafu {
+                            resource: (from + i).to_string(),
+                        })
+                    })
+                    .try_collect::<Vec<_>>()
+            )?;
+
+            blocks
+                .iter()
+                .zip(&common)
+                .map(|(block, common)| {
+                    let NamespaceProofQueryData {
+                        proof,
+                        transactions,
+                        non_inclusion_proof,
+                    } = namespace_proof(block, common, ns_id)?;
+                    Ok(NamespaceProofRangeEntry {
+                        header: block.header().clone(),
+                        proof,
+                        transactions,
+                        non_inclusion_proof,
+                    })
                 })
-            } else {
-                // ns_id not found in ns_table
-                Ok(NamespaceProofQueryData {
-                    proof: None,
-                    transactions: Vec::new(),
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .boxed()
+    })?
+    .stream("stream_namespace_proofs", move |req, state| {
+        async move {
+            let height: usize = req.integer_param("height")?;
+            let ns_id = NamespaceId::from(req.integer_param::<_, u32>("namespace")?);
+            state
+                .read(|state| {
+                    async move {
+                        let blocks = state.subscribe_blocks(height).await;
+                        let vid_common = state.subscribe_vid_common(height).await;
+                        Ok(blocks
+                            .zip(vid_common)
+                            .map(move |(block, common)| namespace_proof(&block, &common, ns_id)))
+                    }
+                    .boxed()
                 })
-            }
+                .await
         }
+        .try_flatten_stream()
         .boxed()
     })?;
 
     Ok(api)
 }
 
+/// Fetch a block and its VID common data, giving up after `timeout`.
+async fn fetch_block_and_vid_common<D>(
+    state: &D,
+    height: usize,
+    timeout: Duration,
+) -> Result<(BlockQueryData<SeqTypes>, VidCommonQueryData<SeqTypes>), availability::Error>
+where
+    D: AvailabilityDataSource<SeqTypes> + Sync,
+{
+    try_join!(
+        async {
+            state
+                .get_block(height)
+                .await
+                .with_timeout(timeout)
+                .await
+                .context(FetchBlockSnafu {
+                    resource: height.to_string(),
+                })
+        },
+        async {
+            state
+                .get_vid_common(height)
+                .await
+                .with_timeout(timeout)
+                .await
+                .context(FetchBlockSnafu {
+                    resource: height.to_string(),
+                })
+        }
+    )
+}
+
+/// Get the transactions in a namespace of a block, along with a proof.
+///
+/// If the namespace is not present in the block, there are no transactions and no proof.
+fn namespace_proof(
+    block: &BlockQueryData<SeqTypes>,
+    common: &VidCommonQueryData<SeqTypes>,
+    ns_id: NamespaceId,
+) -> Result<NamespaceProofQueryData, availability::Error> {
+    let ns_table = block.payload().ns_table();
+    let Some(ns_index) = ns_table.find_ns_id(&ns_id) else {
+        // ns_id not found in ns_table, prove its absence instead
+        return Ok(NamespaceProofQueryData {
+            proof: None,
+            transactions: Vec::new(),
+            non_inclusion_proof: NsNonInclusionProof::new(ns_table, &ns_id),
+        });
+    };
+
+    let proof = NsProof::new(block.payload(), &ns_index, common.common()).context(CustomSnafu {
+        message: format!("failed to make proof for namespace {ns_id}"),
+        status: StatusCode::NOT_FOUND,
+    })?;
+
+    Ok(NamespaceProofQueryData {
+        transactions: proof.export_all_txs(&ns_id),
+        proof: Some(proof),
+        non_inclusion_proof: None,
+    })
+}
+
 type ExplorerApi<N, P, D, V, ApiVer> = Api<AvailState<N, P, D, V>, explorer::Error, ApiVer>;
 
 pub(super) fn explorer<N, P, D, V: Versions>(
@@ -217,7 +421,9 @@ where
 
     Ok(api)
 }
-pub(super) fn submit<N, P, S, ApiVer: StaticVersionType + 'static>() -> Result<Api<S, Error, ApiVer>>
+pub(super) fn submit<N, P, S, ApiVer: StaticVersionType + 'static>(
+    opt: &options::Submit,
+) -> Result<Api<S, SequencerApiError, ApiVer>>
 where
     N: ConnectedNetwork<PubKey>,
     S: 'static + Send + Sync + ReadState,
@@ -225,19 +431,113 @@ where
     S::State: Send + Sync + SubmitDataSource<N, P>,
 {
     let toml = toml::from_str::<toml::Value>(include_str!("../../api/submit.toml"))?;
-    let mut api = Api::<S, Error, ApiVer>::new(toml)?;
+    let mut api = Api::<S, SequencerApiError, ApiVer>::new(toml)?;
 
-    api.at("submit", |req, state| {
+    let namespace_max_tx_size: Arc<HashMap<NamespaceId, u64>> =
+        Arc::new(opt.namespace_max_tx_size.iter().copied().collect());
+
+    let batch_namespace_max_tx_size = namespace_max_tx_size.clone();
+    let max_batch_size = opt.max_batch_size;
+    api.at("submit", move |req, state| {
+        let namespace_max_tx_size = namespace_max_tx_size.clone();
         async move {
             let tx = req
                 .body_auto::<Transaction, ApiVer>(ApiVer::instance())
-                .map_err(Error::from_request_error)?;
+                .map_err(SequencerApiError::from_request_error)?;
+
+            tx.validate_namespace(namespace_max_tx_size.get(&tx.namespace()).copied())?;
 
             let hash = tx.commit();
             state
                 .read(|state| state.submit(tx).boxed())
                 .await
-                .map_err(|err| Error::internal(err.to_string()))?;
+                .map_err(
+                    |err| match err.downcast_ref::<TransactionValidationError>() {
+                        Some(err) => err.clone().into(),
+                        None => SequencerApiError::catch_all(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            format!("{err:#}"),
+                        ),
+                    },
+                )?;
+            Ok(hash)
+        }
+        .boxed()
+    })?
+    .at("batch", move |req, state| {
+        let namespace_max_tx_size = batch_namespace_max_tx_size.clone();
+        async move {
+            let txs = req
+                .body_auto::<Vec<Transaction>, ApiVer>(ApiVer::instance())
+                .map_err(SequencerApiError::from_request_error)?;
+            if txs.len() > max_batch_size {
+                return Err(SequencerApiError::catch_all(
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "batch of {} transactions exceeds the maximum of {max_batch_size}",
+                        txs.len()
+                    ),
+                ));
+            }
+
+            // Check each transaction against the namespace limits, and submit the valid ones in a
+            // single batch.
+            let mut results: Vec<Result<Commitment<Transaction>, SubmitError>> =
+                Vec::with_capacity(txs.len());
+            let mut valid = vec![];
+            for tx in txs {
+                match tx.validate_namespace(namespace_max_tx_size.get(&tx.namespace()).copied()) {
+                    Ok(()) => {
+                        results.push(Ok(tx.commit()));
+                        valid.push((results.len() - 1, tx));
+                    }
+                    Err(err) => results.push(Err(err.into())),
+                }
+            }
+
+            let (indices, valid): (Vec<_>, Vec<_>) = valid.into_iter().unzip();
+            let outcomes = state.read(|state| state.submit_batch(valid).boxed()).await;
+            for (i, outcome) in indices.into_iter().zip(outcomes) {
+                if let Err(err) = outcome {
+                    results[i] = Err(err);
+                }
+            }
+            Ok(results)
+        }
+        .boxed()
+    })?
+    .get("status", |req, state| {
+        async move {
+            let hash = req
+                .blob_param("hash")
+                .map_err(SequencerApiError::from_request_error)?;
+            state.transaction_status(hash).await.ok_or_else(|| {
+                SequencerApiError::catch_all(
+                    StatusCode::NOT_FOUND,
+                    format!("transaction {hash} is not tracked"),
+                )
+            })
+        }
+        .boxed()
+    })?
+    .at("transfer", |req, state| {
+        async move {
+            let tx = req
+                .body_auto::<TransferTx, ApiVer>(ApiVer::instance())
+                .map_err(SequencerApiError::from_request_error)?;
+
+            let hash = tx.commit();
+            state
+                .read(|state| state.submit_transfer(tx).boxed())
+                .await
+                .map_err(|err| {
+                    let status = if err.is::<ExecutionError>() {
+                        StatusCode::BAD_REQUEST
+                    } else {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    };
+                    SequencerApiError::catch_all(status, format!("{err:#}"))
+                })?;
             Ok(hash)
         }
         .boxed()
@@ -3,8 +3,9 @@
 use anyhow::{bail, Context};
 use clap::Parser;
 use espresso_types::{
-    v0::traits::{EventConsumer, NullEventConsumer, PersistenceOptions, SequencerPersistence},
-    BlockMerkleTree, PubKey,
+    parse_size,
+    v0::traits::{EventConsumer, PersistenceOptions, SequencerPersistence},
+    BlockMerkleTree, NamespaceId, PubKey, SequencerApiError,
 };
 use futures::{
     channel::oneshot,
@@ -15,7 +16,7 @@ use hotshot_query_service::{
     data_source::{ExtensibleDataSource, MetricsDataSource},
     fetching::provider::QueryServiceProvider,
     status::{self, UpdateStatusData},
-    ApiState as AppState, Error,
+    ApiState as AppState,
 };
 use hotshot_types::traits::{
     metrics::{Metrics, NoMetrics},
@@ -202,7 +203,7 @@ impl Options {
                 // storage.
                 let ds = MetricsDataSource::default();
                 let metrics = ds.populate_metrics();
-                let mut app = App::<_, Error>::with_state(AppState::from(
+                let mut app = App::<_, SequencerApiError>::with_state(AppState::from(
                     ExtensibleDataSource::new(ds, state.clone()),
                 ));
 
@@ -214,7 +215,7 @@ impl Options {
                 self.init_hotshot_modules(&mut app)?;
 
                 if self.hotshot_events.is_some() {
-                    self.init_and_spawn_hotshot_event_streaming_module(state, &mut tasks)?;
+                    self.init_and_spawn_hotshot_event_streaming_module(state.clone(), &mut tasks)?;
                 }
 
                 tasks.spawn(
@@ -222,7 +223,7 @@ impl Options {
                     self.listen(self.http.port, app, SequencerApiVersion::instance()),
                 );
 
-                (metrics, Box::new(NullEventConsumer))
+                (metrics, Box::new(state))
             } else {
                 // If no status or availability API is requested, we don't need metrics or a query
                 // service data source. The only app state is the HotShot handle, which we use to
@@ -230,12 +231,13 @@ impl Options {
                 //
                 // If we have no availability API, we cannot load a saved leaf from local storage,
                 // so we better have been provided the leaf ahead of time if we want it at all.
-                let mut app = App::<_, Error>::with_state(AppState::from(state.clone()));
+                let mut app =
+                    App::<_, SequencerApiError>::with_state(AppState::from(state.clone()));
 
                 self.init_hotshot_modules(&mut app)?;
 
                 if self.hotshot_events.is_some() {
-                    self.init_and_spawn_hotshot_event_streaming_module(state, &mut tasks)?;
+                    self.init_and_spawn_hotshot_event_streaming_module(state.clone(), &mut tasks)?;
                 }
 
                 tasks.spawn(
@@ -243,7 +245,7 @@ impl Options {
                     self.listen(self.http.port, app, SequencerApiVersion::instance()),
                 );
 
-                (Box::new(NoMetrics), Box::new(NullEventConsumer))
+                (Box::new(NoMetrics), Box::new(state))
             };
 
         let ctx = init_context(metrics, consumer).await?;
@@ -262,7 +264,7 @@ impl Options {
     ) -> anyhow::Result<(
         Box<dyn Metrics>,
         Arc<StorageState<N, P, D, V>>,
-        App<AppState<StorageState<N, P, D, V>>, Error>,
+        App<AppState<StorageState<N, P, D, V>>, SequencerApiError>,
     )>
     where
         N: ConnectedNetwork<PubKey>,
@@ -272,7 +274,7 @@ impl Options {
         let metrics = ds.populate_metrics();
         let ds = Arc::new(ExtensibleDataSource::new(ds, state.clone()));
         let api_state: endpoints::AvailState<N, P, D, V> = ds.clone().into();
-        let mut app = App::<_, Error>::with_state(api_state);
+        let mut app = App::<_, SequencerApiError>::with_state(api_state);
 
         // Initialize status API
         if self.status.is_some() {
@@ -390,7 +392,10 @@ impl Options {
     /// This function adds the `submit`, `state`, and `state_signature` API modules to the given
     /// app. These modules only require a HotShot handle as state, and thus they work with any data
     /// source, so initialization is the same no matter what mode the service is running in.
-    fn init_hotshot_modules<N, P, S>(&self, app: &mut App<S, Error>) -> anyhow::Result<()>
+    fn init_hotshot_modules<N, P, S>(
+        &self,
+        app: &mut App<S, SequencerApiError>,
+    ) -> anyhow::Result<()>
     where
         S: 'static + Send + Sync + ReadState,
         P: SequencerPersistence,
@@ -405,8 +410,8 @@ impl Options {
     {
         let bind_version = SequencerApiVersion::instance();
         // Initialize submit API
-        if self.submit.is_some() {
-            let submit_api = endpoints::submit::<_, _, _, SequencerApiVersion>()?;
+        if let Some(opt) = &self.submit {
+            let submit_api = endpoints::submit::<_, _, _, SequencerApiVersion>(opt)?;
             app.register_module("submit", submit_api)?;
         }
 
@@ -520,9 +525,51 @@ impl Http {
     }
 }
 
+/// Default maximum number of transactions submitted in a single batch.
+pub const DEFAULT_MAX_SUBMIT_BATCH_SIZE: usize = 1000;
+
 /// Options for the submission API module.
-#[derive(Parser, Clone, Copy, Debug, Default)]
-pub struct Submit;
+#[derive(Parser, Clone, Debug)]
+pub struct Submit {
+    /// Maximum payload size of a transaction submitted to specific namespaces.
+    ///
+    /// Given as a comma-separated list of `namespace:size` pairs, e.g. `1:1kb,2:100kb`.
+    /// Transactions for namespaces not listed are only limited by the maximum block size.
+    #[clap(
+        long,
+        env = "ESPRESSO_SEQUENCER_SUBMIT_NAMESPACE_MAX_TX_SIZE",
+        value_delimiter = ',',
+        value_parser = parse_namespace_size_limit
+    )]
+    pub namespace_max_tx_size: Vec<(NamespaceId, u64)>,
+
+    /// Maximum number of transactions submitted in a single batch.
+    ///
+    /// Larger batches are rejected as a whole.
+    #[clap(
+        long,
+        env = "ESPRESSO_SEQUENCER_SUBMIT_MAX_BATCH_SIZE",
+        default_value_t = DEFAULT_MAX_SUBMIT_BATCH_SIZE
+    )]
+    pub max_batch_size: usize,
+}
+
+impl Default for Submit {
+    fn default() -> Self {
+        Self {
+            namespace_max_tx_size: vec![],
+            max_batch_size: DEFAULT_MAX_SUBMIT_BATCH_SIZE,
+        }
+    }
+}
+
+fn parse_namespace_size_limit(s: &str) -> anyhow::Result<(NamespaceId, u64)> {
+    let (namespace, size) = s
+        .split_once(':')
+        .context("expected a `namespace:size` pair")?;
+    let namespace: u32 = namespace.parse().context("invalid namespace")?;
+    Ok((namespace.into(), parse_size(size)?))
+}
 
 /// Options for the status API module.
 #[derive(Parser, Clone, Copy, Debug, Default)]
@@ -2,9 +2,8 @@ use anyhow::{bail, ensure, Context};
 use async_trait::async_trait;
 use committable::{Commitment, Committable};
 use espresso_types::{
-    get_l1_deposits,
-    v0_99::{ChainConfig, IterableFeeInfo},
-    BlockMerkleTree, FeeAccount, FeeMerkleTree, Leaf, Leaf2, NodeState, ValidatedState,
+    get_l1_deposits, v0_4::ChainConfig, v0_99::IterableFeeInfo, BlockMerkleTree, FeeAccount,
+    FeeMerkleTree, Leaf, Leaf2, NodeState, ValidatedState,
 };
 use hotshot::traits::ValidatedState as _;
 use hotshot_query_service::{
@@ -0,0 +1,279 @@
+//! Tracking of the status of submitted transactions.
+
+use std::collections::{BTreeMap, HashMap};
+
+use committable::{Commitment, Committable};
+use espresso_types::{NsTable, Payload, Transaction, TransactionStatus};
+use hotshot::types::{Event, EventType};
+use hotshot_query_service::availability::QueryablePayload;
+use hotshot_types::{event::LeafInfo, traits::block_contents::BlockHeader};
+
+use crate::SeqTypes;
+
+/// Number of blocks after submission within which a transaction must be included before it is
+/// considered expired.
+pub const TX_STATUS_EXPIRY_BLOCKS: u64 = 100;
+
+/// Number of blocks for which the final status of a transaction is retained.
+///
+/// This is also how long the payload of a decided block is waited for, before pending transactions
+/// it may include are allowed to expire.
+pub const TX_STATUS_RETENTION_BLOCKS: u64 = 1000;
+
+/// Maximum number of transactions tracked at once.
+///
+/// Beyond this, the transactions whose status changed longest ago stop being tracked.
+pub const TX_STATUS_MAX_TRACKED: usize = 100_000;
+
+#[derive(Clone, Debug)]
+struct TrackedTransaction {
+    status: TransactionStatus,
+    /// Block height at which the transaction was submitted, or reached its final status.
+    since: u64,
+}
+
+/// Status of the transactions recently submitted to this node.
+#[derive(Debug)]
+pub(crate) struct TxStatusTracker {
+    transactions: HashMap<Commitment<Transaction>, TrackedTransaction>,
+    /// Namespace tables of decided blocks whose payload is not yet known, by height.
+    ///
+    /// Pending transactions may have been included in these blocks, so they do not expire until
+    /// the payloads are known.
+    unresolved: BTreeMap<u64, NsTable>,
+    /// Height of the latest decided block.
+    height: u64,
+    max_tracked: usize,
+}
+
+impl Default for TxStatusTracker {
+    fn default() -> Self {
+        Self::with_max_tracked(TX_STATUS_MAX_TRACKED)
+    }
+}
+
+impl TxStatusTracker {
+    fn with_max_tracked(max_tracked: usize) -> Self {
+        Self {
+            transactions: Default::default(),
+            unresolved: Default::default(),
+            height: 0,
+            max_tracked,
+        }
+    }
+
+    /// Start tracking a submitted transaction.
+    pub(crate) fn submitted(&mut self, hash: Commitment<Transaction>) {
+        // Resubmitting a transaction restarts its expiry window, unless it is already included.
+        if let Some(TrackedTransaction {
+            status: TransactionStatus::Included { .. },
+            ..
+        }) = self.transactions.get(&hash)
+        {
+            return;
+        }
+        self.transactions.insert(
+            hash,
+            TrackedTransaction {
+                status: TransactionStatus::Pending,
+                since: self.height,
+            },
+        );
+        self.evict();
+    }
+
+    /// The status of a transaction, if it is tracked.
+    pub(crate) fn status(&self, hash: &Commitment<Transaction>) -> Option<TransactionStatus> {
+        self.transactions.get(hash).map(|tx| tx.status.clone())
+    }
+
+    /// Update the status of tracked transactions from a consensus event.
+    ///
+    /// Returns the heights of decided blocks whose payload was not included in the event. Once
+    /// fetched, they should be passed to [`payload_available`](Self::payload_available).
+    pub(crate) fn handle_event(&mut self, event: &Event<SeqTypes>) -> Vec<u64> {
+        let EventType::Decide { leaf_chain, .. } = &event.event else {
+            return vec![];
+        };
+
+        // Leaves in a decide event are ordered from newest to oldest.
+        let mut unresolved = vec![];
+        for LeafInfo { leaf, .. } in leaf_chain.iter().rev() {
+            let header = leaf.block_header();
+            let block_height = header.block_number();
+            self.height = self.height.max(block_height);
+
+            if self.transactions.is_empty() {
+                continue;
+            }
+            match leaf.block_payload() {
+                Some(payload) => self.included(block_height, header.metadata(), &payload),
+                None => {
+                    self.unresolved
+                        .insert(block_height, header.metadata().clone());
+                    unresolved.push(block_height);
+                }
+            }
+        }
+
+        self.expire();
+        unresolved
+    }
+
+    /// Update the status of tracked transactions from the payload of a decided block, which was
+    /// not available when the block was decided.
+    pub(crate) fn payload_available(&mut self, block_height: u64, payload: &Payload) {
+        if let Some(ns_table) = self.unresolved.remove(&block_height) {
+            self.included(block_height, &ns_table, payload);
+        }
+    }
+
+    /// Mark the tracked transactions in a decided block as included.
+    fn included(&mut self, block_height: u64, ns_table: &NsTable, payload: &Payload) {
+        for index in payload.iter(ns_table) {
+            let Some(tx) = payload.transaction(&index) else {
+                continue;
+            };
+            if let Some(tracked) = self.transactions.get_mut(&tx.commit()) {
+                tracked.status = TransactionStatus::Included {
+                    block_height,
+                    namespace: tx.namespace(),
+                    namespace_index: index.ns().clone(),
+                };
+                tracked.since = block_height;
+            }
+        }
+    }
+
+    /// Expire transactions which were not included in time, and forget old final statuses.
+    fn expire(&mut self) {
+        let height = self.height;
+
+        // Give up on payloads which have not been found for too long.
+        self.unresolved = self
+            .unresolved
+            .split_off(&height.saturating_sub(TX_STATUS_RETENTION_BLOCKS));
+
+        let unresolved = &self.unresolved;
+        self.transactions.retain(|_, tracked| match tracked.status {
+            TransactionStatus::Pending => {
+                // A transaction may be in a block whose payload we do not know yet.
+                if height.saturating_sub(tracked.since) > TX_STATUS_EXPIRY_BLOCKS
+                    && unresolved.range(tracked.since..).next().is_none()
+                {
+                    tracked.status = TransactionStatus::Expired;
+                    tracked.since = height;
+                }
+                true
+            }
+            _ => height.saturating_sub(tracked.since) <= TX_STATUS_RETENTION_BLOCKS,
+        });
+
+        // Only blocks decided since the oldest pending transaction was submitted can include it.
+        let oldest_pending = self
+            .transactions
+            .values()
+            .filter(|tracked| tracked.status == TransactionStatus::Pending)
+            .map(|tracked| tracked.since)
+            .min();
+        match oldest_pending {
+            Some(since) => self.unresolved = self.unresolved.split_off(&since),
+            None => self.unresolved.clear(),
+        }
+    }
+
+    /// Stop tracking the transactions whose status changed longest ago, if too many are tracked.
+    fn evict(&mut self) {
+        if self.transactions.len() <= self.max_tracked {
+            return;
+        }
+
+        // Evict down to 90% of the limit, so that the cost of eviction is spread over many
+        // submissions.
+        let mut by_age = self
+            .transactions
+            .iter()
+            .map(|(hash, tracked)| (tracked.since, *hash))
+            .collect::<Vec<_>>();
+        let excess = by_age.len() - self.max_tracked * 9 / 10;
+        by_age.select_nth_unstable_by_key(excess - 1, |(since, _)| *since);
+        for (_, hash) in &by_age[..excess] {
+            self.transactions.remove(hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use espresso_types::NamespaceId;
+    use hotshot::traits::BlockPayload;
+
+    use super::*;
+
+    async fn block(txs: &[Transaction]) -> (Payload, NsTable) {
+        Payload::from_transactions(txs.to_vec(), &Default::default(), &Default::default())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tx_status_unresolved_payload() {
+        let tx = Transaction::new(NamespaceId::from(1_u32), vec![1, 2, 3]);
+        let mut tracker = TxStatusTracker::default();
+        tracker.submitted(tx.commit());
+
+        // The transaction is in a block decided without its payload.
+        let (payload, ns_table) = block(&[tx.clone()]).await;
+        tracker.height = 1;
+        tracker.unresolved.insert(1, ns_table);
+
+        // It stays pending past the expiry window while the payload is unknown...
+        tracker.height = 2 + TX_STATUS_EXPIRY_BLOCKS;
+        tracker.expire();
+        assert_eq!(
+            tracker.status(&tx.commit()),
+            Some(TransactionStatus::Pending)
+        );
+
+        // ...and is included once the payload is found.
+        tracker.payload_available(1, &payload);
+        assert!(matches!(
+            tracker.status(&tx.commit()),
+            Some(TransactionStatus::Included {
+                block_height: 1,
+                ..
+            })
+        ));
+
+        // A transaction in none of the known blocks expires.
+        let other = Transaction::new(NamespaceId::from(1_u32), vec![4, 5, 6]);
+        tracker.submitted(other.commit());
+        tracker.height += 1 + TX_STATUS_EXPIRY_BLOCKS;
+        tracker.expire();
+        assert_eq!(
+            tracker.status(&other.commit()),
+            Some(TransactionStatus::Expired)
+        );
+    }
+
+    #[test]
+    fn test_tx_status_max_tracked() {
+        let mut tracker = TxStatusTracker::with_max_tracked(10);
+        let txs = (0..=10u8)
+            .map(|i| Transaction::new(NamespaceId::from(1_u32), vec![i]))
+            .collect::<Vec<_>>();
+        for (height, tx) in txs.iter().enumerate() {
+            tracker.height = height as u64;
+            tracker.submitted(tx.commit());
+        }
+
+        // Exceeding the limit evicts the oldest transactions.
+        assert_eq!(tracker.transactions.len(), 9);
+        assert_eq!(tracker.status(&txs[0].commit()), None);
+        assert_eq!(tracker.status(&txs[1].commit()), None);
+        assert_eq!(
+            tracker.status(&txs[10].commit()),
+            Some(TransactionStatus::Pending)
+        );
+    }
+}
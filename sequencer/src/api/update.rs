@@ -6,14 +6,19 @@ use derivative::Derivative;
 use derive_more::From;
 use espresso_types::{v0::traits::SequencerPersistence, PubKey};
 use hotshot::types::Event;
-use hotshot_query_service::data_source::UpdateDataSource;
+use hotshot_query_service::{availability::AvailabilityDataSource, data_source::UpdateDataSource};
 use hotshot_types::traits::{network::ConnectedNetwork, node_implementation::Versions};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::spawn;
 
-use super::{data_source::SequencerDataSource, StorageState};
+use super::{data_source::SequencerDataSource, ApiState, StorageState};
 use crate::{EventConsumer, SeqTypes};
 
+/// How long to wait for the payload of a decided block when updating transaction statuses.
+const TX_STATUS_PAYLOAD_FETCH_TIMEOUT: Duration = Duration::from_secs(600);
+
 #[derive(Derivative, From)]
 #[derivative(Clone(bound = ""), Debug(bound = "D: Debug"))]
 pub(crate) struct ApiEventConsumer<N, P, D, V>
@@ -37,6 +42,48 @@ where
         if let Err(height) = self.inner.update(event).await {
             bail!("failed to update API state after {height}: {event:?}",);
         }
+
+        // Transactions may be included in blocks whose payload we have not seen yet. Fetch those
+        // payloads in the background, so that their transactions do not expire in the meantime.
+        let api_state: &ApiState<N, P, V> = (*self.inner).as_ref();
+        let unresolved = api_state.tx_status.write().await.handle_event(event);
+        for height in unresolved {
+            let inner = self.inner.clone();
+            spawn(async move {
+                let Some(block) = inner
+                    .get_block(height as usize)
+                    .await
+                    .with_timeout(TX_STATUS_PAYLOAD_FETCH_TIMEOUT)
+                    .await
+                else {
+                    tracing::warn!(height, "unable to fetch payload for transaction status");
+                    return;
+                };
+                let api_state: &ApiState<N, P, V> = (*inner).as_ref();
+                api_state
+                    .tx_status
+                    .write()
+                    .await
+                    .payload_available(height, block.payload());
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the status of submitted transactions up to date.
+///
+/// This is used directly when the API has no query service data source to update; otherwise it is
+/// driven by [`ApiEventConsumer`].
+#[async_trait]
+impl<N, P, V> EventConsumer for ApiState<N, P, V>
+where
+    N: ConnectedNetwork<PubKey>,
+    P: SequencerPersistence,
+    V: Versions,
+{
+    async fn handle_event(&self, event: &Event<SeqTypes>) -> anyhow::Result<()> {
+        self.tx_status.write().await.handle_event(event);
         Ok(())
     }
 }
@@ -83,6 +83,10 @@ struct UpdateArgs {
     #[clap(long, value_parser = parse_update::<String>, default_value = "")]
     pub text: Update<String>,
 
+    /// Must be greater than the nonce of the last update accepted for this rollup.
+    #[clap(long)]
+    pub nonce: u64,
+
     /// The private key is provided in tagged-base64 format.
     /// If not provided, a default private key with a seed of `[0; 32]` and an index of `9876` will be used.
     #[clap(long = "privkey")]
@@ -183,6 +187,7 @@ async fn update(opt: UpdateArgs) -> Result<()> {
         reserve_price,
         active,
         text,
+        nonce,
         private_key,
     } = opt;
 
@@ -208,6 +213,7 @@ async fn update(opt: UpdateArgs) -> Result<()> {
         signature_keys: Update::Skip,
         signature_key: pubkey,
         text,
+        nonce,
     };
 
     // Sign the rollup update body
@@ -221,7 +227,7 @@ async fn update(opt: UpdateArgs) -> Result<()> {
 
     // update a rollup
     client
-        .post::<RollupRegistration>("register_rollup")
+        .post::<RollupRegistration>("update_rollup")
         .body_json(&update)
         .unwrap()
         .send()
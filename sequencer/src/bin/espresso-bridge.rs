@@ -2,13 +2,16 @@ use anyhow::{bail, ensure, Context};
 use clap::{Parser, Subcommand};
 use client::SequencerClient;
 use contract_bindings::fee_contract::FeeContract;
-use espresso_types::{eth_signature_key::EthKeyPair, parse_duration, Header};
+use espresso_types::{
+    eth_signature_key::EthKeyPair, parse_duration, v0_99::TransferTxBody, FeeAccount, Header,
+};
 use ethers::{
     middleware::{Middleware, SignerMiddleware},
     providers::Provider,
     types::{Address, BlockId, U256},
 };
 use futures::stream::StreamExt;
+use hotshot_types::{data::ViewNumber, traits::node_implementation::ConsensusTime};
 use sequencer_utils::logging;
 use std::{sync::Arc, time::Duration};
 use surf_disco::Url;
@@ -26,6 +29,7 @@ struct Options {
 #[derive(Debug, Subcommand)]
 enum Command {
     Deposit(Deposit),
+    Transfer(Transfer),
     Balance(Balance),
     L1Balance(L1Balance),
 }
@@ -77,6 +81,40 @@ struct Deposit {
     confirmations: usize,
 }
 
+/// Transfer fee balance to another Espresso account.
+///
+/// The transfer is signed and submitted to the Espresso node, and its hash is written to standard
+/// out. It can only be executed in the given view, so the view should be far enough ahead of the
+/// current one for the transfer to reach its leader.
+#[derive(Debug, Parser)]
+struct Transfer {
+    /// Espresso query service provider.
+    ///
+    /// This must point to an Espresso node running the node, Merklized state and submit APIs.
+    #[clap(short, long, env = "ESPRESSO_PROVIDER")]
+    espresso_provider: Url,
+
+    /// Mnemonic to generate the account from which to transfer.
+    #[clap(short, long, env = "MNEMONIC")]
+    mnemonic: String,
+
+    /// Account index when deriving an account from MNEMONIC.
+    #[clap(short = 'i', long, env = "ACCOUNT_INDEX", default_value = "0")]
+    account_index: u32,
+
+    /// Account to transfer to.
+    #[clap(short, long, env = "RECIPIENT")]
+    recipient: Address,
+
+    /// Amount of WEI to transfer.
+    #[clap(short, long, env = "AMOUNT")]
+    amount: u64,
+
+    /// The view in which the transfer is to be executed.
+    #[clap(long, env = "VIEW")]
+    view: u64,
+}
+
 /// Check the balance (in ETH) of an Espresso account.
 #[derive(Debug, Parser)]
 struct Balance {
@@ -248,6 +286,50 @@ async fn deposit(opt: Deposit) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn transfer(opt: Transfer) -> anyhow::Result<()> {
+    // Derive the account to transfer from.
+    let key_pair = EthKeyPair::from_mnemonic(opt.mnemonic, opt.account_index)?;
+    let amount = U256::from(opt.amount);
+
+    // Check the balance on Espresso, so we don't sign a transfer that is bound to fail.
+    let espresso = SequencerClient::new(opt.espresso_provider);
+    let balance = espresso
+        .get_espresso_balance(key_pair.address(), None)
+        .await
+        .context("getting Espresso balance")?;
+    ensure!(
+        balance >= amount.into(),
+        "insufficient balance (balance: {balance}, amount: {amount})",
+    );
+
+    let tx = TransferTxBody::new(
+        key_pair.fee_account(),
+        FeeAccount::from(opt.recipient),
+        amount.into(),
+        ViewNumber::new(opt.view),
+    )
+    .signed(&key_pair)?;
+    tracing::info!(
+        from = %key_pair.address(),
+        to = %opt.recipient,
+        %amount,
+        view = opt.view,
+        "signed transfer"
+    );
+
+    let hash = espresso
+        .submit_transfer(&tx)
+        .await
+        .context("submitting transfer")?;
+    tracing::info!(%hash, "submitted transfer");
+
+    // Output the hash on regular standard out, rather than as a log message, to make scripting
+    // easier.
+    println!("{hash}");
+
+    Ok(())
+}
+
 async fn balance(opt: Balance) -> anyhow::Result<()> {
     // Derive the address to look up.
     let address = if let Some(address) = opt.address {
@@ -301,6 +383,7 @@ async fn main() -> anyhow::Result<()> {
 
     match opt.command {
         Command::Deposit(opt) => deposit(opt).await,
+        Command::Transfer(opt) => transfer(opt).await,
         Command::Balance(opt) => balance(opt).await,
         Command::L1Balance(opt) => l1_balance(opt).await,
     }
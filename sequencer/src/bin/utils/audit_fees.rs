@@ -0,0 +1,295 @@
+//! Offline audit of the fee ledger.
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{bail, ensure, Context};
+use clap::Parser;
+use client::SequencerClient;
+use committable::Committable;
+use espresso_types::{BackoffParams, L1Client, Leaf2, NodeState, SeqTypes, ValidatedState};
+use hotshot::traits::ValidatedState as _;
+use hotshot_query_service::availability::LeafQueryData;
+use hotshot_types::traits::metrics::NoMetrics;
+use jf_merkle_tree::MerkleTreeScheme;
+use sequencer::{catchup::StatePeers, Genesis, SequencerApiVersion};
+use surf_disco::Url;
+use tokio::time::sleep;
+
+/// Replay headers and L1 deposits to check the fee and block Merkle roots.
+///
+/// Starting from the state after block FROM - 1 (or the genesis state, if FROM is 0), each header
+/// in [FROM, TO) is applied with the deposits the fee contract emitted on L1, exactly as a
+/// validator would. The resulting Merkle roots are compared with those committed in the header,
+/// and the audit stops at the first divergence. For a diverging fee root, every account touched by
+/// the block whose replayed balance differs from the node's is reported.
+#[derive(Clone, Debug, Parser)]
+pub struct Options {
+    /// Start auditing at block FROM.
+    #[clap(long, name = "FROM", default_value = "0")]
+    from: u64,
+
+    /// Stop auditing at block TO.
+    ///
+    /// Defaults to the current block height.
+    #[clap(long, name = "TO")]
+    to: Option<u64>,
+
+    /// Path to the genesis file of the chain being audited.
+    #[clap(long, name = "GENESIS_FILE", env = "ESPRESSO_SEQUENCER_GENESIS_FILE")]
+    genesis_file: PathBuf,
+
+    /// L1 RPC URL, used to fetch deposits from the fee contract.
+    #[clap(long, env = "ESPRESSO_SEQUENCER_L1_PROVIDER")]
+    l1_provider_url: Url,
+
+    /// Number of times to retry fetching a leaf from the node before giving up.
+    #[clap(long, default_value = "10")]
+    retries: usize,
+
+    /// URL of a sequencer node with the availability, catchup and fee state APIs enabled.
+    url: Url,
+}
+
+pub async fn run(opt: Options) -> anyhow::Result<()> {
+    let genesis = Genesis::from_file(&opt.genesis_file)?;
+    let client = SequencerClient::new(opt.url.clone());
+    let query = surf_disco::Client::<hotshot_query_service::Error, SequencerApiVersion>::new(
+        opt.url.clone(),
+    );
+
+    let to = match opt.to {
+        Some(to) => to,
+        None => client.get_height().await?,
+    };
+    ensure!(opt.from < to, "empty range [{}, {to})", opt.from);
+
+    let mut genesis_state = ValidatedState {
+        chain_config: genesis.chain_config.into(),
+        ..Default::default()
+    };
+    for (address, amount) in genesis.accounts {
+        genesis_state.prefund_account(address, amount);
+    }
+    let instance = NodeState::new(
+        0,
+        genesis.chain_config,
+        L1Client::new(opt.l1_provider_url.clone()).await?,
+        StatePeers::<SequencerApiVersion>::from_urls(
+            vec![opt.url.clone()],
+            BackoffParams::default(),
+            &NoMetrics,
+        ),
+        genesis.base_version,
+    )
+    .with_genesis(genesis_state.clone())
+    .with_upgrades(genesis.upgrades);
+
+    // Establish the state we replay from. Starting from genesis lets us check the genesis header
+    // too; otherwise we trust the roots of the parent of the first audited block and fetch any
+    // state we need from the node, verified against those roots.
+    let (mut state, mut parent, heights) = if opt.from == 0 {
+        let leaf = get_leaf(&query, 0, opt.retries).await?;
+        check_genesis(&genesis_state, &leaf)?;
+        (genesis_state, leaf, 1..to)
+    } else {
+        let leaf = get_leaf(&query, opt.from - 1, opt.retries).await?;
+        let state = ValidatedState::from_header(leaf.block_header());
+        (state, leaf, opt.from..to)
+    };
+
+    tracing::info!(from = opt.from, to, "auditing fee ledger");
+    for height in heights {
+        let leaf = get_leaf(&query, height, opt.retries).await?;
+        let header = leaf.block_header();
+        let (mut next, delta) = state
+            .apply_header(
+                &instance,
+                &instance.peers,
+                &parent,
+                header,
+                leaf.view_number(),
+                header.version(),
+            )
+            .await
+            .with_context(|| format!("applying header {height}"))?;
+
+        if next.fee_merkle_tree.commitment() != header.fee_merkle_tree_root() {
+            tracing::error!(
+                height,
+                expected = ?header.fee_merkle_tree_root(),
+                actual = ?next.fee_merkle_tree.commitment(),
+                "fee merkle root diverges",
+            );
+            for account in delta.fees_delta {
+                let replayed = next
+                    .balance(account)
+                    .context(format!("missing merkle path for fee account {account}"))?;
+                let expected = client
+                    .get_espresso_balance(account.into(), Some(height + 1))
+                    .await?;
+                if replayed != expected {
+                    tracing::error!(%account, %expected, %replayed, "account balance diverges");
+                }
+            }
+            bail!("fee ledger diverges at block {height}");
+        }
+        if next.block_merkle_tree.commitment() != header.block_merkle_tree_root() {
+            tracing::error!(
+                height,
+                expected = ?header.block_merkle_tree_root(),
+                actual = ?next.block_merkle_tree.commitment(),
+                "block merkle root diverges",
+            );
+            bail!("block merkle tree diverges at block {height}");
+        }
+
+        if height % 1000 == 0 {
+            tracing::info!(height, "fee ledger ok");
+        }
+        state = next;
+        parent = leaf;
+    }
+
+    tracing::info!("fee ledger in [{}, {to}) ok", opt.from);
+    Ok(())
+}
+
+/// Check that the genesis state matches the roots committed in the genesis header.
+fn check_genesis(state: &ValidatedState, leaf: &Leaf2) -> anyhow::Result<()> {
+    let header = leaf.block_header();
+    ensure!(
+        state.fee_merkle_tree.commitment() == header.fee_merkle_tree_root(),
+        "genesis fee merkle root does not match genesis file: expected {:?}, actual {:?}",
+        header.fee_merkle_tree_root(),
+        state.fee_merkle_tree.commitment(),
+    );
+    ensure!(
+        state.block_merkle_tree.commitment() == header.block_merkle_tree_root(),
+        "genesis block merkle root does not match genesis file: expected {:?}, actual {:?}",
+        header.block_merkle_tree_root(),
+        state.block_merkle_tree.commitment(),
+    );
+    ensure!(
+        state.chain_config.commit() == header.chain_config().commit(),
+        "genesis chain config does not match genesis file",
+    );
+    Ok(())
+}
+
+async fn get_leaf(
+    client: &surf_disco::Client<hotshot_query_service::Error, SequencerApiVersion>,
+    height: u64,
+    retries: usize,
+) -> anyhow::Result<Leaf2> {
+    let mut attempt = 0;
+    loop {
+        match client
+            .get::<LeafQueryData<SeqTypes>>(&format!("availability/leaf/{height}"))
+            .send()
+            .await
+        {
+            Ok(leaf) => break Ok(leaf.leaf().clone().into()),
+            Err(err) if attempt < retries => {
+                tracing::warn!(attempt, "error fetching leaf {height}: {err}");
+                attempt += 1;
+
+                // Back off a bit and then retry.
+                sleep(Duration::from_millis(100)).await;
+            }
+            Err(err) => bail!("error fetching leaf {height} after {attempt} retries: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use espresso_types::MockSequencerVersions;
+    use ethers::utils::Anvil;
+    use portpicker::pick_unused_port;
+    use sequencer::{
+        api::{
+            self,
+            data_source::testing::TestableSequencerDataSource,
+            sql::DataSource,
+            test_helpers::{TestNetwork, TestNetworkConfigBuilder},
+        },
+        genesis::{L1Finalized, StakeTableConfig},
+        testing::TestConfigBuilder,
+    };
+    use sequencer_utils::test_utils::setup_test;
+    use tempfile::TempDir;
+    use vbs::version::Version;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_audit_fees() {
+        setup_test();
+
+        let port = pick_unused_port().expect("No ports free");
+        let url: Url = format!("http://localhost:{port}").parse().unwrap();
+        let tmp = TempDir::new().unwrap();
+        let anvil = Anvil::new().spawn();
+        let l1: Url = anvil.endpoint().parse().unwrap();
+
+        // The test network starts from the default state and chain config.
+        let genesis_file = tmp.path().join("genesis.toml");
+        Genesis {
+            chain_config: Default::default(),
+            stake_table: StakeTableConfig { capacity: 10 },
+            l1_finalized: L1Finalized::Number { number: 0 },
+            header: Default::default(),
+            upgrades: Default::default(),
+            base_version: Version { major: 0, minor: 1 },
+            upgrade_version: Version { major: 0, minor: 2 },
+            accounts: Default::default(),
+        }
+        .to_file(&genesis_file)
+        .unwrap();
+
+        // Replaying from the middle of the chain needs merklized state catchup from the node.
+        let storage = DataSource::create_storage().await;
+        let api_config = DataSource::options(&storage, api::Options::with_port(port))
+            .catchup(Default::default());
+        let network_config = TestConfigBuilder::default().l1_url(l1.clone()).build();
+        let config = TestNetworkConfigBuilder::default()
+            .api_config(api_config)
+            .network_config(network_config)
+            .build();
+        let _network = TestNetwork::new(config, MockSequencerVersions::new()).await;
+
+        // Wait for a few blocks to be decided, and for the node to store their state.
+        let client = SequencerClient::new(url.clone());
+        while client.get_height().await.unwrap() < 8 {
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        let opt = Options {
+            from: 0,
+            to: Some(5),
+            genesis_file,
+            l1_provider_url: l1,
+            retries: 10,
+            url,
+        };
+
+        // Replay from genesis, and from the middle of the chain.
+        run(opt.clone()).await.unwrap();
+        run(Options {
+            from: 3,
+            ..opt.clone()
+        })
+        .await
+        .unwrap();
+
+        // Blocks the node will not have for a long time are not retried forever.
+        run(Options {
+            from: 3,
+            to: Some(1000),
+            retries: 2,
+            ..opt
+        })
+        .await
+        .unwrap_err();
+    }
+}
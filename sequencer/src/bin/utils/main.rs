@@ -3,6 +3,7 @@
 use clap::{Parser, Subcommand};
 
 use sequencer_utils::logging;
+mod audit_fees;
 mod keygen;
 mod pubkey;
 mod reset_storage;
@@ -18,6 +19,7 @@ struct Options {
 
 #[derive(Debug, Subcommand)]
 enum Command {
+    AuditFees(audit_fees::Options),
     Keygen(keygen::Options),
     Pubkey(pubkey::Options),
     #[command(subcommand)]
@@ -30,6 +32,7 @@ async fn main() -> anyhow::Result<()> {
     opt.logging.init();
 
     match opt.command {
+        Command::AuditFees(opt) => audit_fees::run(opt).await,
         Command::Keygen(opt) => keygen::run(opt),
         Command::Pubkey(opt) => {
             pubkey::run(opt);
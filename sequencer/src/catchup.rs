@@ -7,7 +7,7 @@ use committable::Commitment;
 use committable::Committable;
 use espresso_types::traits::SequencerPersistence;
 use espresso_types::{
-    v0::traits::StateCatchup, v0_99::ChainConfig, BackoffParams, BlockMerkleTree, FeeAccount,
+    v0::traits::StateCatchup, v0_4::ChainConfig, BackoffParams, BlockMerkleTree, FeeAccount,
     FeeAccountProof, FeeMerkleCommitment, FeeMerkleTree, Leaf2, NodeState,
 };
 use futures::future::{Future, FutureExt, TryFuture, TryFutureExt};
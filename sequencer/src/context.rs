@@ -37,7 +37,7 @@ use tracing::{Instrument, Level};
 use url::Url;
 
 use crate::{
-    external_event_handler::{self, ExternalEventHandler},
+    external_event_handler::{self, ExternalEventHandler, TransferSubmitter},
     proposal_fetcher::ProposalFetcherConfig,
     state_signature::StateSigner,
     static_stake_table_commitment, Node, SeqTypes, SequencerApiVersion,
@@ -75,6 +75,8 @@ pub struct SequencerContext<N: ConnectedNetwork<PubKey>, P: SequencerPersistence
 
     #[derivative(Debug = "ignore")]
     validator_config: ValidatorConfig<<SeqTypes as NodeType>::SignatureKey>,
+
+    transfer_submitter: TransferSubmitter,
 }
 
 impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> SequencerContext<N, P, V> {
@@ -153,10 +155,15 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
 
         // Create the external event handler
         let mut tasks = TaskList::default();
-        let external_event_handler =
-            ExternalEventHandler::new(&mut tasks, network, roll_call_info, pub_key)
-                .await
-                .with_context(|| "Failed to create external event handler")?;
+        let external_event_handler = ExternalEventHandler::new(
+            &mut tasks,
+            network,
+            roll_call_info,
+            pub_key,
+            instance_state.pending_transfers.clone(),
+        )
+        .await
+        .with_context(|| "Failed to create external event handler")?;
 
         Ok(Self::new(
             handle,
@@ -194,6 +201,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         let events = handle.event_stream();
 
         let node_id = node_state.node_id;
+        let transfer_submitter = external_event_handler.transfer_submitter();
         let mut ctx = Self {
             handle: Arc::new(RwLock::new(handle)),
             state_signer: Arc::new(state_signer),
@@ -204,6 +212,7 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
             node_state,
             network_config,
             validator_config,
+            transfer_submitter,
         };
 
         // Spawn proposal fetching tasks.
@@ -259,6 +268,11 @@ impl<N: ConnectedNetwork<PubKey>, P: SequencerPersistence, V: Versions> Sequence
         Ok(())
     }
 
+    /// Return a handle for submitting transfers through this node.
+    pub(crate) fn transfer_submitter(&self) -> TransferSubmitter {
+        self.transfer_submitter.clone()
+    }
+
     /// get event streamer
     pub fn event_streamer(&self) -> Arc<RwLock<EventsStreamer<SeqTypes>>> {
         self.events_streamer.clone()
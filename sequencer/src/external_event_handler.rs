@@ -2,7 +2,7 @@
 
 use crate::context::TaskList;
 use anyhow::{Context, Result};
-use espresso_types::{PubKey, SeqTypes};
+use espresso_types::{v0_99::TransferTx, PendingTransfers, PubKey, SeqTypes};
 use hotshot::types::{BLSPubKey, Message};
 use hotshot_types::{
     message::MessageKind,
@@ -27,6 +27,9 @@ pub enum ExternalMessage {
     /// A response to a roll call request
     /// Contains the identifier of the node
     RollCallResponse(RollCallInfo),
+
+    /// A transfer submitted to a node, to be held until a block is proposed in its view
+    Transfer(TransferTx),
 }
 
 /// Information about a node that is used in a roll call response
@@ -47,9 +50,39 @@ pub struct ExternalEventHandler<V: Versions> {
     // The outbound message queue
     pub outbound_message_sender: Sender<OutboundMessage>,
 
+    // The transfers waiting to be included in a block proposed by this node
+    pub pending_transfers: PendingTransfers,
+
     _pd: PhantomData<V>,
 }
 
+/// Submits transfers to this node, and broadcasts them to the other nodes
+///
+/// A transfer can only be included by the leader of its view, so every node needs to hold it.
+#[derive(Debug, Clone)]
+pub struct TransferSubmitter {
+    public_key: BLSPubKey,
+    pending_transfers: PendingTransfers,
+    outbound_message_sender: Sender<OutboundMessage>,
+}
+
+impl TransferSubmitter {
+    /// Hold `tx` until a block is proposed in its view, and broadcast it to the other nodes
+    ///
+    /// # Errors
+    /// If the transfer is not validly signed or the outbound message queue is full
+    pub async fn submit(&self, tx: TransferTx) -> Result<()> {
+        self.pending_transfers.insert(tx.clone()).await?;
+
+        let message_bytes = create_message(&self.public_key, &ExternalMessage::Transfer(tx))
+            .with_context(|| "Failed to create transfer message")?;
+        self.outbound_message_sender
+            .try_send(OutboundMessage::Broadcast(message_bytes))
+            .with_context(|| "External outbound message queue is full")?;
+        Ok(())
+    }
+}
+
 // The different types of outbound messages (broadcast or direct)
 #[derive(Debug)]
 pub enum OutboundMessage {
@@ -64,6 +97,7 @@ impl<V: Versions> ExternalEventHandler<V> {
         network: Arc<N>,
         roll_call_info: RollCallInfo,
         public_key: BLSPubKey,
+        pending_transfers: PendingTransfers,
     ) -> Result<Self> {
         // Create the outbound message queue
         let (outbound_message_sender, outbound_message_receiver) = channel(10);
@@ -90,10 +124,20 @@ impl<V: Versions> ExternalEventHandler<V> {
             roll_call_info,
             public_key,
             outbound_message_sender,
+            pending_transfers,
             _pd: Default::default(),
         })
     }
 
+    /// Returns a handle for submitting transfers through this node
+    pub fn transfer_submitter(&self) -> TransferSubmitter {
+        TransferSubmitter {
+            public_key: self.public_key,
+            pending_transfers: self.pending_transfers.clone(),
+            outbound_message_sender: self.outbound_message_sender.clone(),
+        }
+    }
+
     /// Handles an event
     ///
     /// # Errors
@@ -124,6 +168,15 @@ impl<V: Versions> ExternalEventHandler<V> {
                     .with_context(|| "External outbound message queue is full")?;
             }
 
+            ExternalMessage::Transfer(tx) => {
+                // Hold the transfer in case we lead its view. The node it was submitted to has
+                // already broadcast it to everyone, so we don't pass it on.
+                self.pending_transfers
+                    .insert(tx)
+                    .await
+                    .with_context(|| "Received invalid transfer")?;
+            }
+
             _ => {
                 return Err(anyhow::anyhow!("Unknown external message type"));
             }
@@ -138,19 +191,8 @@ impl<V: Versions> ExternalEventHandler<V> {
     ) -> Result<Vec<u8>> {
         let response = ExternalMessage::RollCallResponse(roll_call_info.clone());
 
-        // Serialize the response
-        let response_bytes = bincode::serialize(&response)
-            .with_context(|| "Failed to serialize roll call response")?;
-
-        let message = Message::<SeqTypes> {
-            sender: *public_key,
-            kind: MessageKind::<SeqTypes>::External(response_bytes),
-        };
-
-        let response_bytes = bincode::serialize(&message)
-            .with_context(|| "Failed to serialize roll call response")?;
-
-        Ok(response_bytes)
+        create_message(public_key, &response)
+            .with_context(|| "Failed to serialize roll call response")
     }
 
     /// The main loop for sending outbound messages.
@@ -181,3 +223,17 @@ impl<V: Versions> ExternalEventHandler<V> {
         }
     }
 }
+
+/// Wraps an external message from `public_key` so it can be sent over the network
+fn create_message(public_key: &BLSPubKey, external_message: &ExternalMessage) -> Result<Vec<u8>> {
+    // Serialize the external message
+    let external_message_bytes = bincode::serialize(external_message)
+        .with_context(|| "Failed to serialize external message")?;
+
+    let message = Message::<SeqTypes> {
+        sender: *public_key,
+        kind: MessageKind::<SeqTypes>::External(external_message_bytes),
+    };
+
+    bincode::serialize(&message).with_context(|| "Failed to serialize message")
+}
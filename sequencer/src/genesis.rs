@@ -4,13 +4,15 @@ use std::{
 };
 
 use anyhow::Context;
+use client::SequencerClient;
 use espresso_types::{
-    v0_99::ChainConfig, FeeAccount, FeeAmount, GenesisHeader, L1BlockInfo, L1Client, Timestamp,
+    v0_4::ChainConfig, FeeAccount, FeeAmount, GenesisHeader, L1BlockInfo, L1Client, Timestamp,
     Upgrade, UpgradeType,
 };
 use ethers::types::H160;
 use sequencer_utils::deployer::is_proxy_contract;
 use serde::{Deserialize, Serialize};
+use url::Url;
 use vbs::version::Version;
 
 /// Initial configuration of an Espresso stake table.
@@ -82,6 +84,40 @@ impl Genesis {
 
         base_fee
     }
+
+    /// The dynamic base fee of the block after the latest one decided by `state_peers`.
+    ///
+    /// This is computed from the `base_fee` of the latest header, and is never less than the
+    /// static base fee of any upgrade, which is also the fallback if the latest header cannot be
+    /// fetched.
+    pub async fn next_base_fee(&self, state_peers: &[Url]) -> FeeAmount {
+        let max_base_fee = self.max_base_fee();
+        if state_peers.is_empty() {
+            return max_base_fee;
+        }
+
+        let client = SequencerClient::with_failover(state_peers.iter().cloned());
+        let base_fee = async {
+            let height = client.get_height().await?;
+            let parent = client.get_header(height.saturating_sub(1)).await?;
+            let chain_config = match parent.chain_config().resolve() {
+                Some(chain_config) => chain_config,
+                None => {
+                    client
+                        .get_chain_config(parent.chain_config().commit())
+                        .await?
+                }
+            };
+            anyhow::Ok(parent.next_base_fee(&chain_config))
+        };
+        match base_fee.await {
+            Ok(base_fee) => base_fee.max(max_base_fee),
+            Err(err) => {
+                tracing::warn!("failed to compute the base fee of the next block: {err:#}");
+                max_base_fee
+            }
+        }
+    }
 }
 
 impl Genesis {
@@ -417,7 +453,8 @@ mod test {
                 base_fee: 1.into(),
                 fee_recipient: FeeAccount::default(),
                 fee_contract: Some(Address::default()),
-                bid_recipient: None
+                bid_recipient: None,
+                ns_fee_multipliers: None,
             }
         );
         assert_eq!(
@@ -489,6 +526,7 @@ mod test {
                 fee_recipient: FeeAccount::default(),
                 bid_recipient: None,
                 fee_contract: None,
+                ns_fee_multipliers: None,
             }
         );
         assert_eq!(
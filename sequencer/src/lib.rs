@@ -521,6 +521,7 @@ pub async fn init_node<P: SequencerPersistence, V: Versions>(
         node_id: node_index,
         upgrades: genesis.upgrades,
         current_version: V::Base::VERSION,
+        pending_transfers: Default::default(),
     };
 
     let mut ctx = SequencerContext::init(
@@ -9,7 +9,7 @@ use super::{
 };
 use clap::Parser;
 use espresso_types::{
-    traits::NullEventConsumer, FeeVersion, MarketplaceVersion, SequencerVersions,
+    traits::NullEventConsumer, FeeMarketVersion, FeeVersion, MarketplaceVersion, SequencerVersions,
     SolverAuctionResultsProvider, V0_0,
 };
 use futures::future::FutureExt;
@@ -47,6 +47,15 @@ pub async fn main() -> anyhow::Result<()> {
             )
             .await
         }
+        (FeeVersion::VERSION, FeeMarketVersion::VERSION) => {
+            run(
+                genesis,
+                modules,
+                opt,
+                SequencerVersions::<FeeVersion, FeeMarketVersion>::new(),
+            )
+            .await
+        }
         (FeeVersion::VERSION, _) => {
             run(
                 genesis,
@@ -56,6 +65,15 @@ pub async fn main() -> anyhow::Result<()> {
             )
             .await
         }
+        (FeeMarketVersion::VERSION, _) => {
+            run(
+                genesis,
+                modules,
+                opt,
+                SequencerVersions::<FeeMarketVersion, V0_0>::new(),
+            )
+            .await
+        }
         (MarketplaceVersion::VERSION, _) => {
             run(
                 genesis,
@@ -171,11 +189,11 @@ where
     };
 
     let marketplace_config = MarketplaceConfig {
-        auction_results_provider: Arc::new(SolverAuctionResultsProvider {
-            url: opt.auction_results_solver_url,
-            marketplace_path: opt.marketplace_solver_path,
-            results_path: opt.auction_results_path,
-        }),
+        auction_results_provider: Arc::new(SolverAuctionResultsProvider::new(
+            opt.auction_results_solver_url,
+            opt.marketplace_solver_path,
+            opt.auction_results_path,
+        )),
         fallback_builder_url: opt.fallback_builder_url,
     };
     let proposal_fetcher_config = opt.proposal_fetcher_config;
@@ -3,7 +3,7 @@ use std::{sync::Arc, time::Duration};
 
 use anyhow::{bail, ensure, Context};
 use espresso_types::{
-    traits::StateCatchup, v0_99::ChainConfig, BlockMerkleTree, Delta, FeeAccount, FeeMerkleTree,
+    traits::StateCatchup, v0_4::ChainConfig, BlockMerkleTree, Delta, FeeAccount, FeeMerkleTree,
     Leaf2, ValidatedState,
 };
 use futures::future::Future;
@@ -56,7 +56,14 @@ pub(crate) async fn compute_state_update(
     );
 
     state
-        .apply_header(instance, peers, parent_leaf, header, header.version())
+        .apply_header(
+            instance,
+            peers,
+            parent_leaf,
+            header,
+            proposed_leaf.view_number(),
+            header.version(),
+        )
         .await
 }
 
@@ -48,6 +48,7 @@ use crate::{
 type V1Serializer = vbs::Serializer<StaticVersion<0, 1>>;
 type V2Serializer = vbs::Serializer<StaticVersion<0, 2>>;
 type V3Serializer = vbs::Serializer<StaticVersion<0, 3>>;
+type V4Serializer = vbs::Serializer<StaticVersion<0, 4>>;
 type V99Serializer = vbs::Serializer<StaticVersion<0, 99>>;
 
 async fn reference_payload() -> Payload {
@@ -137,7 +138,7 @@ async fn reference_header(version: Version) -> Header {
     let state = ValidatedState::default();
 
     Header::create(
-        reference_chain_config(),
+        reference_chain_config().into(),
         42,
         789,
         124,
@@ -233,6 +234,7 @@ change in the serialization of this data structure.
         "v1" => V1Serializer::serialize(&reference).unwrap(),
         "v2" => V2Serializer::serialize(&reference).unwrap(),
         "v3" => V3Serializer::serialize(&reference).unwrap(),
+        "v4" => V4Serializer::serialize(&reference).unwrap(),
         "v99" => V99Serializer::serialize(&reference).unwrap(),
         _ => panic!("invalid version"),
     };
@@ -263,6 +265,7 @@ change in the serialization of this data structure.
         "v1" => V1Serializer::deserialize(&expected).unwrap(),
         "v2" => V2Serializer::deserialize(&expected).unwrap(),
         "v3" => V3Serializer::deserialize(&expected).unwrap(),
+        "v4" => V4Serializer::deserialize(&expected).unwrap(),
         "v99" => V99Serializer::deserialize(&expected).unwrap(),
         _ => panic!("invalid version"),
     };
@@ -389,6 +392,15 @@ async fn test_reference_header_v2() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reference_header_v4() {
+    reference_test_without_committable(
+        "v4",
+        "header",
+        &reference_header(StaticVersion::<0, 4>::version()).await,
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_reference_header_v99() {
     reference_test(
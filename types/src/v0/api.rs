@@ -0,0 +1,383 @@
+//! Types used in the sequencer API which are not part of consensus.
+//!
+//! These are shared between the API server in the `sequencer` crate and its clients, so that clients
+//! can deserialize responses without depending on the server.
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use anyhow::Context;
+use hotshot_query_service::{availability, explorer, merklized_state, node, status};
+use hotshot_types::{
+    network::{
+        BuilderType, CombinedNetworkConfig, Libp2pConfig, NetworkConfig, RandomBuilderConfig,
+    },
+    vid::VidCommon,
+    HotShotConfig, PeerConfig, ValidatorConfig,
+};
+use jf_merkle_tree::MerkleTreeScheme;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tide_disco::{Error as _, StatusCode};
+use url::Url;
+use vec1::Vec1;
+
+use crate::{
+    BlockMerkleTree, Header, NamespaceId, NsIndex, NsMultiProof, NsNonInclusionProof, NsProof,
+    PubKey, Transaction, TransactionValidationError, TxIndex, TxProof,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NamespaceProofQueryData {
+    pub proof: Option<NsProof>,
+    pub transactions: Vec<Transaction>,
+    /// Proof that the namespace is absent from the block, if it is.
+    #[serde(default)]
+    pub non_inclusion_proof: Option<NsNonInclusionProof>,
+}
+
+/// A single transaction, along with a proof of its inclusion in a block.
+///
+/// The proof can be checked with [`TxProof::verify`] against the namespace table and payload
+/// commitment in `header` and the given `vid_common`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionProofQueryData {
+    pub header: Header,
+    pub ns_index: NsIndex,
+    pub tx_index: TxIndex,
+    pub transaction: Transaction,
+    pub proof: TxProof,
+    pub vid_common: VidCommon,
+}
+
+/// The transactions in several namespaces of a block, along with a single proof for all of them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NamespaceMultiProofQueryData {
+    pub proof: NsMultiProof,
+    /// The transactions in each requested namespace that is present in the block, in namespace
+    /// table order.
+    pub namespaces: Vec<(NamespaceId, Vec<Transaction>)>,
+}
+
+/// The transactions in a namespace of one block in a range, along with a proof and the header of
+/// the block the proof is against.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NamespaceProofRangeEntry {
+    pub header: Header,
+    pub proof: Option<NsProof>,
+    pub transactions: Vec<Transaction>,
+    /// Proof that the namespace is absent from the block, if it is.
+    #[serde(default)]
+    pub non_inclusion_proof: Option<NsNonInclusionProof>,
+}
+
+/// The blocks Merkle tree frontier: the path to the most recently appended leaf.
+pub type BlocksFrontier = <BlockMerkleTree as MerkleTreeScheme>::MembershipProof;
+
+/// This struct defines the public Hotshot validator configuration.
+/// Private key and state key pairs are excluded for security reasons.
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PublicValidatorConfig {
+    public_key: PubKey,
+    stake_value: u64,
+    is_da: bool,
+    private_key: String,
+    state_public_key: String,
+    state_key_pair: String,
+}
+
+impl From<ValidatorConfig<PubKey>> for PublicValidatorConfig {
+    fn from(v: ValidatorConfig<PubKey>) -> Self {
+        let ValidatorConfig::<PubKey> {
+            public_key,
+            private_key: _,
+            stake_value,
+            state_key_pair,
+            is_da,
+        } = v;
+
+        let state_public_key = state_key_pair.ver_key();
+
+        Self {
+            public_key,
+            stake_value,
+            is_da,
+            state_public_key: state_public_key.to_string(),
+            private_key: "*****".into(),
+            state_key_pair: "*****".into(),
+        }
+    }
+}
+
+/// This struct defines the public Hotshot configuration parameters.
+/// Our config module features a GET endpoint accessible via the route `/hotshot` to display the hotshot config parameters.
+/// Hotshot config has sensitive information like private keys and such fields are excluded from this struct.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PublicHotShotConfig {
+    start_threshold: (u64, u64),
+    num_nodes_with_stake: NonZeroUsize,
+    known_nodes_with_stake: Vec<PeerConfig<PubKey>>,
+    known_da_nodes: Vec<PeerConfig<PubKey>>,
+    da_staked_committee_size: usize,
+    fixed_leader_for_gpuvid: usize,
+    next_view_timeout: u64,
+    view_sync_timeout: Duration,
+    num_bootstrap: usize,
+    builder_timeout: Duration,
+    data_request_delay: Duration,
+    builder_urls: Vec1<Url>,
+    start_proposing_view: u64,
+    stop_proposing_view: u64,
+    start_voting_view: u64,
+    stop_voting_view: u64,
+    start_proposing_time: u64,
+    stop_proposing_time: u64,
+    start_voting_time: u64,
+    stop_voting_time: u64,
+    epoch_height: u64,
+}
+
+impl From<HotShotConfig<PubKey>> for PublicHotShotConfig {
+    fn from(v: HotShotConfig<PubKey>) -> Self {
+        // Destructure all fields from HotShotConfig to return an error
+        // if new fields are added to HotShotConfig. This makes sure that we handle
+        // all fields appropriately and do not miss any updates.
+        let HotShotConfig::<PubKey> {
+            start_threshold,
+            num_nodes_with_stake,
+            known_nodes_with_stake,
+            known_da_nodes,
+            da_staked_committee_size,
+            fixed_leader_for_gpuvid,
+            next_view_timeout,
+            view_sync_timeout,
+            num_bootstrap,
+            builder_timeout,
+            data_request_delay,
+            builder_urls,
+            start_proposing_view,
+            stop_proposing_view,
+            start_voting_view,
+            stop_voting_view,
+            start_proposing_time,
+            stop_proposing_time,
+            start_voting_time,
+            stop_voting_time,
+            epoch_height,
+        } = v;
+
+        Self {
+            start_threshold,
+            num_nodes_with_stake,
+            known_nodes_with_stake,
+            known_da_nodes,
+            da_staked_committee_size,
+            fixed_leader_for_gpuvid,
+            next_view_timeout,
+            view_sync_timeout,
+            num_bootstrap,
+            builder_timeout,
+            data_request_delay,
+            builder_urls,
+            start_proposing_view,
+            stop_proposing_view,
+            start_voting_view,
+            stop_voting_view,
+            start_proposing_time,
+            stop_proposing_time,
+            start_voting_time,
+            stop_voting_time,
+            epoch_height,
+        }
+    }
+}
+
+impl PublicHotShotConfig {
+    pub fn into_hotshot_config(self) -> HotShotConfig<PubKey> {
+        HotShotConfig {
+            start_threshold: self.start_threshold,
+            num_nodes_with_stake: self.num_nodes_with_stake,
+            known_nodes_with_stake: self.known_nodes_with_stake,
+            known_da_nodes: self.known_da_nodes,
+            da_staked_committee_size: self.da_staked_committee_size,
+            fixed_leader_for_gpuvid: self.fixed_leader_for_gpuvid,
+            next_view_timeout: self.next_view_timeout,
+            view_sync_timeout: self.view_sync_timeout,
+            num_bootstrap: self.num_bootstrap,
+            builder_timeout: self.builder_timeout,
+            data_request_delay: self.data_request_delay,
+            builder_urls: self.builder_urls,
+            start_proposing_view: self.start_proposing_view,
+            stop_proposing_view: self.stop_proposing_view,
+            start_voting_view: self.start_voting_view,
+            stop_voting_view: self.stop_voting_view,
+            start_proposing_time: self.start_proposing_time,
+            stop_proposing_time: self.stop_proposing_time,
+            start_voting_time: self.start_voting_time,
+            stop_voting_time: self.stop_voting_time,
+            epoch_height: self.epoch_height,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PublicNetworkConfig {
+    rounds: usize,
+    indexed_da: bool,
+    transactions_per_round: usize,
+    manual_start_password: Option<String>,
+    num_bootrap: usize,
+    next_view_timeout: u64,
+    view_sync_timeout: Duration,
+    builder_timeout: Duration,
+    data_request_delay: Duration,
+    node_index: u64,
+    seed: [u8; 32],
+    transaction_size: usize,
+    key_type_name: String,
+    libp2p_config: Option<Libp2pConfig>,
+    config: PublicHotShotConfig,
+    cdn_marshal_address: Option<String>,
+    combined_network_config: Option<CombinedNetworkConfig>,
+    commit_sha: String,
+    builder: BuilderType,
+    random_builder: Option<RandomBuilderConfig>,
+}
+
+impl From<NetworkConfig<PubKey>> for PublicNetworkConfig {
+    fn from(cfg: NetworkConfig<PubKey>) -> Self {
+        Self {
+            rounds: cfg.rounds,
+            indexed_da: cfg.indexed_da,
+            transactions_per_round: cfg.transactions_per_round,
+            manual_start_password: Some("*****".into()),
+            num_bootrap: cfg.num_bootrap,
+            next_view_timeout: cfg.next_view_timeout,
+            view_sync_timeout: cfg.view_sync_timeout,
+            builder_timeout: cfg.builder_timeout,
+            data_request_delay: cfg.data_request_delay,
+            node_index: cfg.node_index,
+            seed: cfg.seed,
+            transaction_size: cfg.transaction_size,
+            key_type_name: cfg.key_type_name,
+            libp2p_config: cfg.libp2p_config,
+            config: cfg.config.into(),
+            cdn_marshal_address: cfg.cdn_marshal_address,
+            combined_network_config: cfg.combined_network_config,
+            commit_sha: cfg.commit_sha,
+            builder: cfg.builder,
+            random_builder: cfg.random_builder,
+        }
+    }
+}
+
+impl PublicNetworkConfig {
+    pub fn into_network_config(
+        self,
+        my_own_validator_config: ValidatorConfig<PubKey>,
+    ) -> anyhow::Result<NetworkConfig<PubKey>> {
+        let node_index = self
+            .config
+            .known_nodes_with_stake
+            .iter()
+            .position(|peer| peer.stake_table_entry.stake_key == my_own_validator_config.public_key)
+            .context(format!(
+                "the node {} is not in the stake table",
+                my_own_validator_config.public_key
+            ))? as u64;
+
+        Ok(NetworkConfig {
+            rounds: self.rounds,
+            indexed_da: self.indexed_da,
+            transactions_per_round: self.transactions_per_round,
+            manual_start_password: self.manual_start_password,
+            num_bootrap: self.num_bootrap,
+            next_view_timeout: self.next_view_timeout,
+            view_sync_timeout: self.view_sync_timeout,
+            builder_timeout: self.builder_timeout,
+            data_request_delay: self.data_request_delay,
+            node_index,
+            seed: self.seed,
+            transaction_size: self.transaction_size,
+            key_type_name: self.key_type_name,
+            libp2p_config: self.libp2p_config,
+            config: self.config.into_hotshot_config(),
+            cdn_marshal_address: self.cdn_marshal_address,
+            combined_network_config: self.combined_network_config,
+            commit_sha: self.commit_sha,
+            builder: self.builder,
+            random_builder: self.random_builder,
+            public_keys: Vec::new(),
+        })
+    }
+}
+
+/// An error returned by the sequencer API.
+///
+/// Errors from the query service modules are serialized exactly like a
+/// [`hotshot_query_service::Error`], so clients which only know that type can still decode them. A
+/// transaction rejected by the submit API is serialized as the [`TransactionValidationError`]
+/// giving the reason, so clients can tell why it was rejected without parsing an error message.
+#[derive(Clone, Debug, Error, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SequencerApiError {
+    #[error(transparent)]
+    InvalidTransaction(TransactionValidationError),
+    #[error(transparent)]
+    Query(hotshot_query_service::Error),
+}
+
+impl tide_disco::Error for SequencerApiError {
+    fn catch_all(status: StatusCode, message: String) -> Self {
+        Self::Query(hotshot_query_service::Error::catch_all(status, message))
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidTransaction(_) => StatusCode::BAD_REQUEST,
+            Self::Query(err) => err.status(),
+        }
+    }
+}
+
+impl From<TransactionValidationError> for SequencerApiError {
+    fn from(err: TransactionValidationError) -> Self {
+        Self::InvalidTransaction(err)
+    }
+}
+
+impl From<hotshot_query_service::Error> for SequencerApiError {
+    fn from(err: hotshot_query_service::Error) -> Self {
+        Self::Query(err)
+    }
+}
+
+impl From<availability::Error> for SequencerApiError {
+    fn from(err: availability::Error) -> Self {
+        Self::Query(err.into())
+    }
+}
+
+impl From<explorer::Error> for SequencerApiError {
+    fn from(err: explorer::Error) -> Self {
+        Self::Query(err.into())
+    }
+}
+
+impl From<merklized_state::Error> for SequencerApiError {
+    fn from(err: merklized_state::Error) -> Self {
+        Self::Query(err.into())
+    }
+}
+
+impl From<node::Error> for SequencerApiError {
+    fn from(err: node::Error) -> Self {
+        Self::Query(err.into())
+    }
+}
+
+impl From<status::Error> for SequencerApiError {
+    fn from(err: status::Error) -> Self {
+        Self::Query(err.into())
+    }
+}
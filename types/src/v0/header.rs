@@ -4,7 +4,7 @@ use vbs::version::Version;
 
 use crate::{
     v0_1::{self, ChainConfig},
-    v0_2, v0_3, v0_99,
+    v0_2, v0_3, v0_4, v0_99,
 };
 
 /// Each variant represents a specific minor version header.
@@ -13,6 +13,7 @@ pub enum Header {
     V1(v0_1::Header),
     V2(v0_2::Header),
     V3(v0_3::Header),
+    V4(v0_4::Header),
     V99(v0_99::Header),
 }
 
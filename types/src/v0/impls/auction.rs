@@ -1,34 +1,90 @@
 use super::{state::ValidatedState, MarketplaceVersion};
 use crate::{
     eth_signature_key::{EthKeyPair, SigningError},
-    v0_99::{BidTx, BidTxBody, FullNetworkTx, SolverAuctionResults},
-    FeeAccount, FeeAmount, FeeError, FeeInfo, NamespaceId,
+    v0_4::ChainConfig,
+    v0_99::{AuctionResultsRequest, BidTx, BidTxBody, FullNetworkTx, SolverAuctionResults},
+    ChainId, FeeAccount, FeeAmount, FeeError, FeeInfo, NamespaceId, SeqTypes,
 };
 use anyhow::Context;
 use async_trait::async_trait;
 use committable::{Commitment, Committable};
+use ethers::types::Signature;
+use futures::stream::{BoxStream, StreamExt};
 use hotshot_types::{
     data::ViewNumber,
     traits::{
         auction_results_provider::AuctionResultsProvider,
-        node_implementation::{ConsensusTime, HasUrls, NodeType},
+        node_implementation::{ConsensusTime, HasUrls},
         signature_key::BuilderSignatureKey,
     },
 };
-use std::str::FromStr;
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 use thiserror::Error;
 use tide_disco::error::ServerError;
+use tokio::{
+    spawn,
+    sync::{Mutex, RwLock},
+    task::JoinHandle,
+    time::sleep,
+};
 use url::Url;
 
 impl FullNetworkTx {
     /// Proxy for `execute` method of each transaction variant.
-    pub fn execute(&self, state: &mut ValidatedState) -> Result<(), ExecutionError> {
+    ///
+    /// `view` is the view of the block the transaction is executed in. Transfers are only valid in
+    /// the view they were signed for, which prevents them from being replayed.
+    pub fn execute(
+        &self,
+        state: &mut ValidatedState,
+        view: ViewNumber,
+    ) -> Result<(), ExecutionError> {
         match self {
             Self::Bid(bid) => bid.execute(state),
+            Self::Transfer(transfer) => transfer.execute(state, view),
+        }
+    }
+
+    /// Commitment to the body of the transaction, which identifies it regardless of its signature.
+    pub(crate) fn body_commitment(&self) -> [u8; 32] {
+        match self {
+            Self::Bid(bid) => bid.body.commit().into(),
+            Self::Transfer(transfer) => transfer.body.commit().into(),
+        }
+    }
+
+    /// The fee accounts whose balances executing the transaction may change.
+    pub(crate) fn accounts(&self, chain_config: &ChainConfig) -> Vec<FeeAccount> {
+        match self {
+            Self::Bid(bid) => [bid.account()]
+                .into_iter()
+                .chain(chain_config.bid_recipient)
+                .collect(),
+            Self::Transfer(transfer) => vec![transfer.account(), transfer.recipient()],
         }
     }
 }
 
+impl Committable for FullNetworkTx {
+    fn tag() -> String {
+        "FULL_NETWORK_TX".to_string()
+    }
+
+    fn commit(&self) -> Commitment<Self> {
+        let comm = committable::RawCommitmentBuilder::new(&Self::tag());
+        let comm = match self {
+            Self::Bid(bid) => comm.field("bid", bid.commit()),
+            Self::Transfer(transfer) => comm.field("transfer", transfer.commit()),
+        };
+        comm.finalize()
+    }
+}
+
 impl Committable for BidTx {
     fn tag() -> String {
         "BID_TX".to_string()
@@ -170,6 +226,18 @@ pub enum ExecutionError {
     #[error("Bid recipient not set on `ChainConfig`")]
     /// Bid Recipient is not set on `ChainConfig`
     BidRecipientNotFound,
+    #[error("Transfer amount is zero")]
+    /// Transfer of a zero amount.
+    ZeroTransfer,
+    #[error("Transaction is for view {tx_view:?}, executed in view {view:?}")]
+    /// Transaction executed in a view other than the one it was signed for.
+    InvalidView {
+        tx_view: ViewNumber,
+        view: ViewNumber,
+    },
+    #[error("Duplicate transaction")]
+    /// The same transaction appears more than once in a block.
+    DuplicateTransaction,
 }
 
 impl From<FeeError> for ExecutionError {
@@ -218,7 +286,7 @@ impl BidTx {
         Ok(())
     }
     /// Cryptographic signature verification
-    fn verify(&self) -> Result<(), ExecutionError> {
+    pub fn verify(&self) -> Result<(), ExecutionError> {
         self.body
             .account
             .validate_builder_signature(&self.signature, self.body.commit().as_ref())
@@ -245,6 +313,14 @@ impl BidTx {
     pub fn view(&self) -> ViewNumber {
         self.body.view
     }
+    /// get the signature over the body
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+    /// get the namespaces the bid is for
+    pub fn namespaces(&self) -> &[NamespaceId] {
+        &self.body.namespaces
+    }
     /// Get the `url` field from the body.
     pub fn url(&self) -> Url {
         self.body.url()
@@ -287,6 +363,43 @@ impl Committable for SolverAuctionResults {
     }
 }
 
+impl Committable for AuctionResultsRequest {
+    fn tag() -> String {
+        "AUCTION_RESULTS_REQUEST".to_string()
+    }
+
+    fn commit(&self) -> Commitment<Self> {
+        committable::RawCommitmentBuilder::new(&Self::tag())
+            .fixed_size_field("chain_id", &self.chain_id.to_fixed_bytes())
+            .var_size_field("solver_url", self.solver_url.as_str().as_ref())
+            .u64_field("view_number", self.view_number.u64())
+            .finalize()
+    }
+}
+
+impl AuctionResultsRequest {
+    /// Construct an `AuctionResultsRequest`
+    pub fn new(chain_id: ChainId, solver_url: Url, view_number: ViewNumber) -> Self {
+        Self {
+            chain_id,
+            solver_url,
+            view_number,
+        }
+    }
+    /// Get the chain id
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+    /// Get the solver URL
+    pub fn solver_url(&self) -> &Url {
+        &self.solver_url
+    }
+    /// Get the view number
+    pub fn view_number(&self) -> ViewNumber {
+        self.view_number
+    }
+}
+
 impl SolverAuctionResults {
     /// Construct a `SolverAuctionResults`
     pub fn new(
@@ -341,37 +454,172 @@ impl HasUrls for SolverAuctionResults {
 
 type SurfClient = surf_disco::Client<ServerError, MarketplaceVersion>;
 
+/// Number of views of pushed auction results a provider keeps.
+const AUCTION_RESULTS_CACHE_VIEWS: u64 = 100;
+
+/// Delay before resubscribing to the solver's auction results after the stream ends.
+const AUCTION_RESULTS_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 /// Auction Results provider holding the Url of the solver in order to fetch auction results.
+///
+/// Once used, the provider subscribes to the results the solver pushes as each auction closes, and
+/// only falls back to querying the solver for views whose results have not been pushed.
 pub struct SolverAuctionResultsProvider {
     pub url: Url,
     pub marketplace_path: String,
     pub results_path: String,
+    cache: AuctionResultsCache,
+}
+
+/// Auction results pushed by the solver, shared by all clones of a provider.
+#[derive(Clone, Debug, Default)]
+struct AuctionResultsCache(Arc<AuctionResultsCacheInner>);
+
+#[derive(Debug, Default)]
+struct AuctionResultsCacheInner {
+    results: RwLock<BTreeMap<ViewNumber, SolverAuctionResults>>,
+    subscription: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Drop for AuctionResultsCacheInner {
+    fn drop(&mut self) {
+        if let Some(task) = self.subscription.get_mut().take() {
+            task.abort();
+        }
+    }
+}
+
+// The cache is not part of the provider's identity: providers for the same solver are equal.
+impl PartialEq for AuctionResultsCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for AuctionResultsCache {}
+
+impl std::hash::Hash for AuctionResultsCache {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
+impl AuctionResultsCacheInner {
+    async fn get(&self, view: ViewNumber) -> Option<SolverAuctionResults> {
+        self.results.read().await.get(&view).cloned()
+    }
+
+    async fn insert(&self, results: SolverAuctionResults) {
+        let mut cache = self.results.write().await;
+        let view = results.view();
+        cache.insert(view, results);
+
+        // Drop results for views too old to be requested.
+        let latest = cache
+            .last_key_value()
+            .map(|(view, _)| **view)
+            .unwrap_or_default();
+        let cutoff = ViewNumber::new(latest.saturating_sub(AUCTION_RESULTS_CACHE_VIEWS));
+        *cache = cache.split_off(&cutoff);
+    }
 }
 
 impl Default for SolverAuctionResultsProvider {
     fn default() -> Self {
+        Self::new(
+            Url::from_str("http://localhost:25000").unwrap(),
+            "marketplace-solver/".into(),
+            "auction_results/".into(),
+        )
+    }
+}
+
+impl SolverAuctionResultsProvider {
+    pub fn new(url: Url, marketplace_path: String, results_path: String) -> Self {
         Self {
-            url: Url::from_str("http://localhost:25000").unwrap(),
-            marketplace_path: "marketplace-solver/".into(),
-            results_path: "auction_results/".into(),
+            url,
+            marketplace_path,
+            results_path,
+            cache: Default::default(),
+        }
+    }
+
+    /// Subscribe to auction results, which the solver pushes as soon as each view's auction
+    /// closes.
+    pub async fn subscribe(
+        &self,
+    ) -> anyhow::Result<BoxStream<'static, anyhow::Result<SolverAuctionResults>>> {
+        let stream = SurfClient::new(
+            self.url
+                .join(&self.marketplace_path)
+                .context("Malformed solver URL")?,
+        )
+        .socket("auction_results_stream")
+        .subscribe::<SolverAuctionResults>()
+        .await?;
+        Ok(stream.map(|res| res.map_err(anyhow::Error::from)).boxed())
+    }
+
+    /// Start caching the results pushed by the solver, unless already doing so.
+    async fn spawn_subscription(&self) {
+        let mut task = self.cache.0.subscription.lock().await;
+        if task.is_none() {
+            let provider = Self::new(
+                self.url.clone(),
+                self.marketplace_path.clone(),
+                self.results_path.clone(),
+            );
+            *task = Some(spawn(
+                provider.cache_pushed_results(Arc::downgrade(&self.cache.0)),
+            ));
+        }
+    }
+
+    /// Cache each result the solver pushes, resubscribing whenever the stream ends, until the
+    /// cache is dropped.
+    async fn cache_pushed_results(self, cache: Weak<AuctionResultsCacheInner>) {
+        loop {
+            match self.subscribe().await {
+                Ok(mut stream) => {
+                    while let Some(res) = stream.next().await {
+                        let results = match res {
+                            Ok(results) => results,
+                            Err(err) => {
+                                tracing::warn!("error in auction results stream: {err:#}");
+                                break;
+                            }
+                        };
+                        let Some(cache) = cache.upgrade() else {
+                            return;
+                        };
+                        cache.insert(results).await;
+                    }
+                }
+                Err(err) => tracing::warn!("failed to subscribe to auction results: {err:#}"),
+            }
+            sleep(AUCTION_RESULTS_RESUBSCRIBE_DELAY).await;
         }
     }
 }
 
 #[async_trait]
-impl<TYPES: NodeType> AuctionResultsProvider<TYPES> for SolverAuctionResultsProvider {
-    /// Fetch the auction results from the solver.
+impl AuctionResultsProvider<SeqTypes> for SolverAuctionResultsProvider {
+    /// Fetch the auction results, from those pushed by the solver if available, or else by
+    /// querying the solver.
     async fn fetch_auction_result(
         &self,
-        view_number: TYPES::View,
-    ) -> anyhow::Result<TYPES::AuctionResult> {
+        view_number: ViewNumber,
+    ) -> anyhow::Result<SolverAuctionResults> {
+        self.spawn_subscription().await;
+        if let Some(results) = self.cache.0.get(view_number).await {
+            return Ok(results);
+        }
+
         let resp = SurfClient::new(
             self.url
                 .join(&self.marketplace_path)
                 .context("Malformed solver URL")?,
         )
-        .get::<TYPES::AuctionResult>(&format!("{}{}", self.results_path, *view_number))
+        .get::<SolverAuctionResults>(&format!("{}{}", self.results_path, *view_number))
         .send()
         .await?;
         Ok(resp)
@@ -1,3 +1,5 @@
+mod ns_multi_proof;
+mod ns_non_inclusion_proof;
 mod ns_proof;
 mod ns_table;
 mod payload;
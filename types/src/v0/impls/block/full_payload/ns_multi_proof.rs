@@ -0,0 +1,218 @@
+use hotshot_types::{
+    traits::EncodeBytes,
+    vid::{vid_scheme, VidCommitment, VidCommon, VidSchemeType},
+};
+use jf_vid::{
+    payload_prover::{PayloadProver, Statement},
+    VidScheme,
+};
+
+use crate::{
+    v0_1::NsProofGroup, NamespaceId, NsIndex, NsMultiProof, NsTable, Payload, PayloadByteLen,
+    Transaction,
+};
+
+impl NsMultiProof {
+    /// Returns the payload bytes for each namespace in `indices`, along with a
+    /// proof of correctness for those bytes. Returns `None` on error.
+    ///
+    /// Duplicate indices are ignored. Each run of indices that are adjacent in
+    /// the namespace table is proven by a single VID opening.
+    ///
+    /// Like [`NsProof`](crate::NsProof), the namespace payloads are included as
+    /// hidden fields in the returned [`NsMultiProof`].
+    pub fn new(payload: &Payload, indices: &[NsIndex], common: &VidCommon) -> Option<NsMultiProof> {
+        let payload_byte_len = payload.byte_len();
+        if !payload_byte_len.is_consistent(common) {
+            tracing::warn!(
+                "payload byte len {} inconsistent with common {}",
+                payload_byte_len,
+                VidSchemeType::get_payload_byte_len(common)
+            );
+            return None; // error: payload byte len inconsistent with common
+        }
+
+        let mut indices: Vec<usize> = indices.iter().map(|index| index.0).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        if let Some(index) = indices
+            .iter()
+            .find(|index| !payload.ns_table().in_bounds(&NsIndex(**index)))
+        {
+            tracing::warn!("ns_index {index} out of bounds");
+            return None; // error: index out of bounds
+        }
+
+        // TODO vid_scheme() arg should be u32 to match get_num_storage_nodes
+        // https://github.com/EspressoSystems/HotShot/issues/3298
+        let vid = vid_scheme(
+            VidSchemeType::get_num_storage_nodes(common)
+                .try_into()
+                .ok()?, // error: failure to convert u32 to usize
+        );
+        let payload_bytes_arc = payload.encode(); // pacify borrow checker
+        let payload_bytes = payload_bytes_arc.as_ref();
+
+        let mut groups = Vec::new();
+        for run in indices.chunk_by(|prev, next| *next == prev + 1) {
+            let ns_payload_ranges: Vec<_> = run
+                .iter()
+                .map(|index| {
+                    payload
+                        .ns_table()
+                        .ns_range(&NsIndex(*index), &payload_byte_len)
+                })
+                .collect();
+
+            // Adjacent namespaces occupy a contiguous range of the payload.
+            let range = ns_payload_ranges[0].as_block_range().start
+                ..ns_payload_ranges[ns_payload_ranges.len() - 1]
+                    .as_block_range()
+                    .end;
+            let ns_proof = if range.is_empty() {
+                None
+            } else {
+                Some(
+                    vid.payload_proof(payload_bytes, range).ok()?, // error: internal to payload_proof()
+                )
+            };
+
+            groups.push(NsProofGroup {
+                first_ns_index: NsIndex(run[0]),
+                ns_payloads: ns_payload_ranges
+                    .iter()
+                    .map(|range| payload.read_ns_payload(range).to_owned())
+                    .collect(),
+                ns_proof,
+            });
+        }
+
+        Some(NsMultiProof { groups })
+    }
+
+    /// Verify a [`NsMultiProof`] against a payload commitment. Returns `None`
+    /// on error or if verification fails.
+    ///
+    /// If verification is successful then return the [`NamespaceId`] and
+    /// transactions of each proven namespace, in namespace table order. See
+    /// [`NsProof::verify`](crate::NsProof::verify) for why the transactions
+    /// are returned.
+    pub fn verify(
+        &self,
+        ns_table: &NsTable,
+        commit: &VidCommitment,
+        common: &VidCommon,
+    ) -> Option<Vec<(NamespaceId, Vec<Transaction>)>> {
+        VidSchemeType::is_consistent(commit, common).ok()?;
+        let payload_byte_len = PayloadByteLen::from_vid_common(common);
+
+        // TODO vid_scheme() arg should be u32 to match get_num_storage_nodes
+        // https://github.com/EspressoSystems/HotShot/issues/3298
+        let vid = vid_scheme(
+            VidSchemeType::get_num_storage_nodes(common)
+                .try_into()
+                .ok()?, // error: failure to convert u32 to usize
+        );
+
+        let mut namespaces = Vec::new();
+        for group in &self.groups {
+            if group.ns_payloads.is_empty() {
+                tracing::error!("ns multi verify: empty group");
+                return None;
+            }
+
+            let indices: Vec<_> = (group.first_ns_index.0..)
+                .take(group.ns_payloads.len())
+                .map(NsIndex)
+                .collect();
+            if !indices.iter().all(|index| ns_table.in_bounds(index)) {
+                return None; // error: index out of bounds
+            }
+
+            // Each namespace payload must exactly fill its range, and the
+            // ranges must be contiguous, so that the concatenated payloads are
+            // proven by a single opening of the combined range.
+            let ranges: Vec<_> = indices
+                .iter()
+                .map(|index| ns_table.ns_range(index, &payload_byte_len).as_block_range())
+                .collect();
+            if group
+                .ns_payloads
+                .iter()
+                .zip(&ranges)
+                .any(|(ns_payload, range)| ns_payload.as_bytes_slice().len() != range.len())
+                || ranges.windows(2).any(|pair| pair[0].end != pair[1].start)
+            {
+                tracing::error!("ns multi verify: ns payloads inconsistent with ns table");
+                return None;
+            }
+            let range = ranges[0].start..ranges[ranges.len() - 1].end;
+
+            match (&group.ns_proof, range.is_empty()) {
+                (Some(proof), false) => {
+                    let payload_subslice: Vec<u8> = group
+                        .ns_payloads
+                        .iter()
+                        .flat_map(|ns_payload| ns_payload.as_bytes_slice())
+                        .copied()
+                        .collect();
+                    vid.payload_verify(
+                        Statement {
+                            payload_subslice: &payload_subslice,
+                            range,
+                            commit,
+                            common,
+                        },
+                        proof,
+                    )
+                    .ok()? // error: internal to payload_verify()
+                    .ok()?; // verification failure
+                }
+                (None, true) => {} // 0-length namespaces, nothing to verify
+                (None, false) => {
+                    tracing::error!(
+                        "ns multi verify: missing proof for nonempty ns payload range {:?}",
+                        range
+                    );
+                    return None;
+                }
+                (Some(_), true) => {
+                    tracing::error!("ns multi verify: unexpected proof for empty ns payload range");
+                    return None;
+                }
+            }
+
+            // verification succeeded for this group, collect some data
+            for (index, ns_payload) in indices.iter().zip(&group.ns_payloads) {
+                let ns_id = ns_table.read_ns_id_unchecked(index);
+                namespaces.push((ns_id, ns_payload.export_all_txs(&ns_id)));
+            }
+        }
+
+        Some(namespaces)
+    }
+
+    /// Return the [`NamespaceId`] and transactions of each namespace whose
+    /// payload is proven by `self`, in namespace table order, without
+    /// verifying the proof.
+    ///
+    /// See [`NsProof::export_all_txs`](crate::NsProof::export_all_txs) for the
+    /// design warning that also applies here.
+    pub fn export_all_txs(&self, ns_table: &NsTable) -> Vec<(NamespaceId, Vec<Transaction>)> {
+        self.groups
+            .iter()
+            .flat_map(|group| {
+                (group.first_ns_index.0..)
+                    .map(NsIndex)
+                    .zip(&group.ns_payloads)
+                    .filter_map(|(index, ns_payload)| {
+                        let ns_id = ns_table.read_ns_id(&index)?;
+                        Some((ns_id, ns_payload.export_all_txs(&ns_id)))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test;
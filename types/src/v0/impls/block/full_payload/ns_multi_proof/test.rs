@@ -0,0 +1,74 @@
+use hotshot::helpers::initialize_logging;
+use hotshot::traits::BlockPayload;
+use hotshot_types::{traits::EncodeBytes, vid::vid_scheme};
+use jf_vid::VidScheme;
+
+use crate::{v0::impls::block::test::ValidTest, NsIndex, NsMultiProof, Payload};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ns_multi_proof() {
+    initialize_logging();
+
+    let mut rng = jf_utils::test_rng();
+    let test = ValidTest::from_tx_lengths(
+        vec![vec![5, 8, 8], vec![7, 9, 11], vec![10, 5, 8], vec![7, 8, 9]],
+        &mut rng,
+    );
+    let block =
+        Payload::from_transactions(test.all_txs(), &Default::default(), &Default::default())
+            .await
+            .unwrap()
+            .0;
+    let vid = vid_scheme(10).disperse(block.encode()).unwrap();
+    let ns_table = block.ns_table();
+
+    // Namespaces 0 and 1 are adjacent and share an opening; namespace 3 gets
+    // its own. Duplicates and order do not matter.
+    let indices = [NsIndex(3), NsIndex(1), NsIndex(0), NsIndex(1)];
+    let proof = NsMultiProof::new(&block, &indices, &vid.common).unwrap();
+    assert_eq!(proof.groups.len(), 2);
+
+    let namespaces = proof.verify(ns_table, &vid.commit, &vid.common).unwrap();
+    assert_eq!(namespaces.len(), 3);
+    assert_eq!(proof.export_all_txs(ns_table), namespaces);
+    for ((ns_id, txs), index) in namespaces.iter().zip([0, 1, 3]) {
+        assert_eq!(*ns_id, ns_table.read_ns_id(&NsIndex(index)).unwrap());
+        assert_eq!(txs, &test.nss[ns_id]);
+    }
+
+    // All namespaces at once need only a single opening.
+    let all: Vec<_> = ns_table.iter().collect();
+    let proof_all = NsMultiProof::new(&block, &all, &vid.common).unwrap();
+    assert_eq!(proof_all.groups.len(), 1);
+    let namespaces = proof_all
+        .verify(ns_table, &vid.commit, &vid.common)
+        .unwrap();
+    assert_eq!(namespaces.len(), test.nss.len());
+
+    // Out of bounds indices are rejected.
+    assert!(NsMultiProof::new(&block, &[NsIndex(4)], &vid.common).is_none());
+
+    // wrong vid commitment
+    let other_block = Payload::from_transactions(
+        ValidTest::from_tx_lengths(vec![vec![1, 2, 3]], &mut rng).all_txs(),
+        &Default::default(),
+        &Default::default(),
+    )
+    .await
+    .unwrap()
+    .0;
+    let other_vid = vid_scheme(10).disperse(other_block.encode()).unwrap();
+    assert!(proof
+        .verify(ns_table, &other_vid.commit, &vid.common)
+        .is_none());
+
+    // hack the proof: claim the payloads belong to different namespaces
+    let mut hacked = proof.clone();
+    hacked.groups[0].first_ns_index = NsIndex(2);
+    assert!(hacked.verify(ns_table, &vid.commit, &vid.common).is_none());
+
+    // hack the proof: drop a namespace from a group
+    let mut hacked = proof.clone();
+    hacked.groups[0].ns_payloads.pop();
+    assert!(hacked.verify(ns_table, &vid.commit, &vid.common).is_none());
+}
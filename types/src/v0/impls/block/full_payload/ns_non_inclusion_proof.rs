@@ -0,0 +1,39 @@
+use committable::{Commitment, Committable};
+
+use crate::{NamespaceId, NsNonInclusionProof, NsTable};
+
+impl NsNonInclusionProof {
+    /// Returns a proof that `ns_id` is absent from `ns_table`. Returns `None`
+    /// if `ns_id` is present.
+    pub fn new(ns_table: &NsTable, ns_id: &NamespaceId) -> Option<Self> {
+        if ns_table.find_ns_id(ns_id).is_some() {
+            return None; // error: ns id is present
+        }
+        Some(Self {
+            ns_table: ns_table.clone(),
+        })
+    }
+
+    /// Verify a [`NsNonInclusionProof`] that `ns_id` is absent from the
+    /// namespace table with commitment `ns_table_commit`, as committed in a
+    /// block header. Returns `None` on error or if verification fails.
+    pub fn verify(&self, ns_table_commit: &Commitment<NsTable>, ns_id: &NamespaceId) -> Option<()> {
+        if self.ns_table.commit() != *ns_table_commit {
+            tracing::info!("ns table does not match commitment");
+            return None; // error: wrong ns table
+        }
+        if self.ns_table.find_ns_id(ns_id).is_some() {
+            tracing::info!("ns id {ns_id} is present in ns table");
+            return None; // verification failure: ns id is present
+        }
+        Some(())
+    }
+
+    /// The namespace table from which the namespace is absent.
+    pub fn ns_table(&self) -> &NsTable {
+        &self.ns_table
+    }
+}
+
+#[cfg(test)]
+mod test;
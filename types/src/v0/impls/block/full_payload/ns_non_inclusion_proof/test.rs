@@ -0,0 +1,40 @@
+use committable::Committable;
+use sequencer_utils::test_utils::setup_test;
+
+use crate::{NamespaceId, NsNonInclusionProof, NsTableBuilder};
+
+#[test]
+fn ns_non_inclusion_proof() {
+    setup_test();
+
+    let present = NamespaceId::from(1u32);
+    let absent = NamespaceId::from(2u32);
+
+    let mut builder = NsTableBuilder::new();
+    builder.append_entry(present, 10);
+    builder.append_entry(NamespaceId::from(3u32), 20);
+    let ns_table = builder.into_ns_table();
+    let commit = ns_table.commit();
+
+    // Cannot prove absence of a namespace that is present.
+    assert!(NsNonInclusionProof::new(&ns_table, &present).is_none());
+
+    let proof = NsNonInclusionProof::new(&ns_table, &absent).unwrap();
+    assert_eq!(proof.verify(&commit, &absent), Some(()));
+
+    // The proof does not verify for a namespace that is present...
+    assert!(proof.verify(&commit, &present).is_none());
+
+    // ...or against a different namespace table.
+    let other = {
+        let mut builder = NsTableBuilder::new();
+        builder.append_entry(absent, 10);
+        builder.into_ns_table()
+    };
+    assert!(proof.verify(&other.commit(), &absent).is_none());
+
+    // An empty namespace table excludes every namespace.
+    let empty = NsTableBuilder::new().into_ns_table();
+    let proof = NsNonInclusionProof::new(&empty, &present).unwrap();
+    assert_eq!(proof.verify(&empty.commit(), &present), Some(()));
+}
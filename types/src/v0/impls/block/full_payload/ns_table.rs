@@ -142,6 +142,33 @@ impl NsTable {
         Ok(())
     }
 
+    /// Byte length of the payload described by this namespace table.
+    ///
+    /// This is the final offset in the table, or 0 if the table is empty. It
+    /// is only meaningful for a table which has passed [`NsTable::validate`].
+    pub fn payload_byte_len(&self) -> PayloadByteLen {
+        let len = self.len().0;
+        if len == 0 {
+            return PayloadByteLen(0);
+        }
+        PayloadByteLen(self.read_ns_offset_unchecked(&NsIndex(len - 1)))
+    }
+
+    /// Iterator over the namespace id and payload byte length of each
+    /// namespace in the namespace table.
+    ///
+    /// Like [`NsTable::payload_byte_len`], this is only meaningful for a table
+    /// which has passed [`NsTable::validate`].
+    pub fn ns_byte_lens(&self) -> impl Iterator<Item = (NamespaceId, usize)> + '_ {
+        let mut start = 0;
+        self.iter().map(move |index| {
+            let end = self.read_ns_offset_unchecked(&index);
+            let byte_len = end.saturating_sub(start);
+            start = end;
+            (self.read_ns_id_unchecked(&index), byte_len)
+        })
+    }
+
     // CRATE-VISIBLE HELPERS START HERE
 
     /// Read subslice range for the `index`th namespace from the namespace
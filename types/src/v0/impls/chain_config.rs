@@ -46,6 +46,14 @@ impl From<u64> for ChainId {
     }
 }
 
+impl FromStr for ChainId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_string(s.to_string())
+    }
+}
+
 impl_serde_from_string_or_integer!(BlockSize);
 
 impl FromStringOrInteger for BlockSize {
@@ -16,7 +16,7 @@ use jf_merkle_tree::{
     MerkleCommitment, MerkleTreeError, MerkleTreeScheme, ToTraversalPath,
     UniversalMerkleTreeScheme,
 };
-use num_traits::CheckedSub;
+use num_traits::{CheckedAdd, CheckedSub};
 use sequencer_utils::{
     impl_serde_from_string_or_integer, impl_to_fixed_bytes, ser::FromStringOrInteger,
 };
@@ -161,6 +161,12 @@ impl From<FeeAmount> for MonetaryValue {
     }
 }
 
+impl CheckedAdd for FeeAmount {
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        self.0.checked_add(v.0).map(FeeAmount)
+    }
+}
+
 impl CheckedSub for FeeAmount {
     fn checked_sub(&self, v: &Self) -> Option<Self> {
         self.0.checked_sub(v.0).map(FeeAmount)
@@ -1,11 +1,13 @@
 use anyhow::{ensure, Context};
 use ark_serialize::CanonicalSerialize;
 use committable::{Commitment, Committable, RawCommitmentBuilder};
+use ethers::types::U256;
 use hotshot_query_service::{availability::QueryableHeader, explorer::ExplorerHeader};
 use hotshot_types::{
+    data::ViewNumber,
     traits::{
         block_contents::{BlockHeader, BuilderFee},
-        node_implementation::NodeType,
+        node_implementation::{ConsensusTime, NodeType},
         signature_key::BuilderSignatureKey,
         BlockPayload, ValidatedState as _,
     },
@@ -30,13 +32,21 @@ use crate::{
         MarketplaceVersion,
     },
     v0_1, v0_2, v0_3,
-    v0_99::{self, ChainConfig, IterableFeeInfo, SolverAuctionResults},
+    v0_4::{self, ChainConfig},
+    v0_99::{self, FullNetworkTx, IterableFeeInfo, SolverAuctionResults, TransferTx},
     BlockMerkleCommitment, BuilderSignature, FeeAccount, FeeAmount, FeeInfo, FeeMerkleCommitment,
     Header, L1BlockInfo, L1Snapshot, Leaf2, NamespaceId, NsTable, SeqTypes, UpgradeType,
 };
 
 use super::{instance_state::NodeState, state::ValidatedState};
 
+/// Ratio of `max_block_size` to the block size targeted by the dynamic base fee.
+const BASE_FEE_ELASTICITY_MULTIPLIER: u64 = 2;
+
+/// Bound on the change of the dynamic base fee from one block to the next, as a fraction of the
+/// parent's base fee.
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
 impl v0_1::Header {
     pub(crate) fn commit(&self) -> Commitment<Header> {
         let mut bmt_bytes = vec![];
@@ -80,6 +90,11 @@ impl Committable for Header {
                 .u64_field("version_minor", 3)
                 .field("fields", fields.commit())
                 .finalize(),
+            Self::V4(fields) => RawCommitmentBuilder::new(&Self::tag())
+                .u64_field("version_major", 0)
+                .u64_field("version_minor", 4)
+                .field("fields", fields.commit())
+                .finalize(),
             Self::V99(fields) => RawCommitmentBuilder::new(&Self::tag())
                 .u64_field("version_major", 0)
                 .u64_field("version_minor", 3)
@@ -112,6 +127,11 @@ impl Serialize for Header {
                 fields: fields.clone(),
             }
             .serialize(serializer),
+            Self::V4(fields) => VersionedHeader {
+                version: EitherOrVersion::Version(Version { major: 0, minor: 4 }),
+                fields: fields.clone(),
+            }
+            .serialize(serializer),
             Self::V99(fields) => VersionedHeader {
                 version: EitherOrVersion::Version(Version {
                     major: 0,
@@ -165,6 +185,10 @@ impl<'de> Deserialize<'de> for Header {
                         seq.next_element()?
                             .ok_or_else(|| de::Error::missing_field("fields"))?,
                     )),
+                    EitherOrVersion::Version(Version { major: 0, minor: 4 }) => Ok(Header::V4(
+                        seq.next_element()?
+                            .ok_or_else(|| de::Error::missing_field("fields"))?,
+                    )),
                     EitherOrVersion::Version(Version {
                         major: 0,
                         minor: 99,
@@ -203,6 +227,9 @@ impl<'de> Deserialize<'de> for Header {
                         EitherOrVersion::Version(Version { major: 0, minor: 3 }) => Ok(Header::V3(
                             serde_json::from_value(fields.clone()).map_err(de::Error::custom)?,
                         )),
+                        EitherOrVersion::Version(Version { major: 0, minor: 4 }) => Ok(Header::V4(
+                            serde_json::from_value(fields.clone()).map_err(de::Error::custom)?,
+                        )),
                         EitherOrVersion::Version(Version {
                             major: 0,
                             minor: 99,
@@ -266,6 +293,7 @@ impl Header {
             Self::V1(_) => Version { major: 0, minor: 1 },
             Self::V2(_) => Version { major: 0, minor: 2 },
             Self::V3(_) => Version { major: 0, minor: 3 },
+            Self::V4(_) => Version { major: 0, minor: 4 },
             Self::V99(_) => Version {
                 major: 0,
                 minor: 99,
@@ -344,9 +372,26 @@ impl Header {
                 fee_info: fee_info[0], // NOTE this is asserted to exist above
                 builder_signature: builder_signature.first().copied(),
             }),
+            4 => Self::V4(v0_4::Header {
+                chain_config: v0_4::ResolvableChainConfig::from(chain_config),
+                height,
+                timestamp,
+                l1_head,
+                l1_finalized,
+                payload_commitment,
+                builder_commitment,
+                ns_table,
+                block_merkle_tree_root,
+                fee_merkle_tree_root,
+                fee_info: fee_info[0], // NOTE this is asserted to exist above
+                builder_signature: builder_signature.first().copied(),
+                base_fee: chain_config.base_fee,
+            }),
 
             99 => Self::V99(v0_99::Header {
-                chain_config: v0_99::ResolvableChainConfig::from(chain_config),
+                chain_config: v0_99::ResolvableChainConfig::from(
+                    v0_99::ChainConfig::try_from(chain_config).unwrap(),
+                ),
                 height,
                 timestamp,
                 l1_head,
@@ -359,6 +404,8 @@ impl Header {
                 fee_info,
                 builder_signature,
                 auction_results: SolverAuctionResults::genesis(),
+                full_network_txs: vec![],
+                base_fee: None,
             }),
             // This case should never occur
             // but if it does, we must panic
@@ -375,6 +422,7 @@ macro_rules! field {
             Self::V1(data) => &data.$name,
             Self::V2(data) => &data.$name,
             Self::V3(data) => &data.$name,
+            Self::V4(data) => &data.$name,
             Self::V99(data) => &data.$name,
         }
     };
@@ -386,6 +434,7 @@ macro_rules! field_mut {
             Self::V1(data) => &mut data.$name,
             Self::V2(data) => &mut data.$name,
             Self::V3(data) => &mut data.$name,
+            Self::V4(data) => &mut data.$name,
             Self::V99(data) => &mut data.$name,
         }
     };
@@ -407,6 +456,7 @@ impl Header {
         chain_config: ChainConfig,
         version: Version,
         auction_results: Option<SolverAuctionResults>,
+        transfers: Vec<TransferTx>,
     ) -> anyhow::Result<Self> {
         ensure!(
             version.major == 0,
@@ -505,6 +555,22 @@ impl Header {
                 .context(format!("invalid builder fee {fee_info:?}"))?;
         }
 
+        // Execute the pending transfers for this view, leaving out any which no longer apply, for
+        // example because the sender has since spent their balance. A transfer which fails leaves
+        // the state unchanged.
+        let full_network_txs = transfers
+            .into_iter()
+            .filter_map(
+                |tx| match tx.execute(&mut state, ViewNumber::new(view_number)) {
+                    Ok(()) => Some(FullNetworkTx::Transfer(tx)),
+                    Err(err) => {
+                        tracing::warn!(?tx, "leaving out transfer: {err}");
+                        None
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
         let fee_info = FeeInfo::from_builder_fees(builder_fee.clone());
 
         let builder_signature: Vec<BuilderSignature> =
@@ -565,7 +631,7 @@ impl Header {
                 fee_info: fee_info[0],
                 builder_signature: builder_signature.first().copied(),
             }),
-            99 => Self::V99(v0_99::Header {
+            4 => Self::V4(v0_4::Header {
                 chain_config: chain_config.into(),
                 height,
                 timestamp,
@@ -576,9 +642,26 @@ impl Header {
                 ns_table,
                 block_merkle_tree_root,
                 fee_merkle_tree_root,
+                fee_info: fee_info[0],
+                builder_signature: builder_signature.first().copied(),
+                base_fee: parent_header.next_base_fee(&chain_config),
+            }),
+            99 => Self::V99(v0_99::Header {
+                chain_config: v0_99::ChainConfig::try_from(chain_config)?.into(),
+                height,
+                timestamp,
+                l1_head: l1.head,
+                l1_finalized: l1.finalized,
+                payload_commitment,
+                builder_commitment,
+                ns_table,
+                block_merkle_tree_root,
+                fee_merkle_tree_root,
                 fee_info,
                 builder_signature,
                 auction_results: auction_results.unwrap(),
+                full_network_txs,
+                base_fee: Some(parent_header.next_base_fee(&chain_config)),
             }),
             // This case should never occur
             // but if it does, we must panic
@@ -616,12 +699,13 @@ impl Header {
 
 impl Header {
     /// A commitment to a ChainConfig or a full ChainConfig.
-    pub fn chain_config(&self) -> v0_99::ResolvableChainConfig {
+    pub fn chain_config(&self) -> v0_4::ResolvableChainConfig {
         match self {
-            Self::V1(fields) => v0_99::ResolvableChainConfig::from(&fields.chain_config),
-            Self::V2(fields) => v0_99::ResolvableChainConfig::from(&fields.chain_config),
-            Self::V3(fields) => v0_99::ResolvableChainConfig::from(&fields.chain_config),
-            Self::V99(fields) => fields.chain_config,
+            Self::V1(fields) => v0_4::ResolvableChainConfig::from(&fields.chain_config),
+            Self::V2(fields) => v0_4::ResolvableChainConfig::from(&fields.chain_config),
+            Self::V3(fields) => v0_4::ResolvableChainConfig::from(&fields.chain_config),
+            Self::V4(fields) => fields.chain_config,
+            Self::V99(fields) => v0_4::ResolvableChainConfig::from(&fields.chain_config),
         }
     }
 
@@ -739,6 +823,7 @@ impl Header {
             Self::V1(fields) => vec![fields.fee_info],
             Self::V2(fields) => vec![fields.fee_info],
             Self::V3(fields) => vec![fields.fee_info],
+            Self::V4(fields) => vec![fields.fee_info],
             Self::V99(fields) => fields.fee_info.clone(),
         }
     }
@@ -760,9 +845,60 @@ impl Header {
             Self::V1(fields) => fields.builder_signature.as_slice().to_vec(),
             Self::V2(fields) => fields.builder_signature.as_slice().to_vec(),
             Self::V3(fields) => fields.builder_signature.as_slice().to_vec(),
+            Self::V4(fields) => fields.builder_signature.as_slice().to_vec(),
             Self::V99(fields) => fields.builder_signature.clone(),
         }
     }
+
+    /// Full network transactions executed in this block, in order.
+    pub fn full_network_txs(&self) -> &[FullNetworkTx] {
+        match self {
+            Self::V1(_) | Self::V2(_) | Self::V3(_) | Self::V4(_) => &[],
+            Self::V99(fields) => &fields.full_network_txs,
+        }
+    }
+
+    /// The dynamic base fee of this block, if it has one.
+    ///
+    /// Only v0.4 headers and non-genesis v0.99 headers carry a dynamic base fee. For other headers
+    /// the base fee is the static `ChainConfig::base_fee`.
+    pub fn base_fee(&self) -> Option<FeeAmount> {
+        match self {
+            Self::V1(_) => None,
+            Self::V2(_) => None,
+            Self::V3(_) => None,
+            Self::V4(fields) => Some(fields.base_fee),
+            Self::V99(fields) => fields.base_fee,
+        }
+    }
+
+    /// Compute the base fee of the child of this block.
+    ///
+    /// Modeled on EIP-1559: the base fee targets blocks half of `max_block_size`. It increases by
+    /// up to 1/8 after a block larger than the target and decreases by up to 1/8 after a block
+    /// smaller than the target, but never below `ChainConfig::base_fee`.
+    pub fn next_base_fee(&self, chain_config: &ChainConfig) -> FeeAmount {
+        let min_base_fee = chain_config.base_fee.0;
+        let base_fee = self.base_fee().map_or(min_base_fee, |fee| fee.0);
+        let target = U256::from(*chain_config.max_block_size / BASE_FEE_ELASTICITY_MULTIPLIER);
+        let block_size = U256::from(self.ns_table().payload_byte_len().as_usize());
+
+        let next = if target.is_zero() || block_size == target {
+            base_fee
+        } else if block_size > target {
+            let delta = base_fee.saturating_mul(block_size - target)
+                / target
+                / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            // Always increase by at least 1 wei, so that a base fee of 0 can grow.
+            base_fee.saturating_add(delta.max(1.into()))
+        } else {
+            let delta = base_fee.saturating_mul(target - block_size)
+                / target
+                / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            base_fee - delta
+        };
+        next.max(min_base_fee).into()
+    }
 }
 
 #[derive(Debug, Error)]
@@ -791,6 +927,7 @@ impl BlockHeader<SeqTypes> for Header {
             Self::V1(_) => None,
             Self::V2(_) => None,
             Self::V3(_) => None,
+            Self::V4(_) => None,
             Self::V99(fields) => Some(fields.auction_results.clone()),
         }
     }
@@ -871,15 +1008,25 @@ impl BlockHeader<SeqTypes> for Header {
         } else {
             vec![]
         };
+        // Take the transfers submitted for this view, which we will include if they still apply.
+        let transfers = instance_state
+            .pending_transfers
+            .take(ViewNumber::new(view_number))
+            .await;
+
         // Find missing fee state entries. We will need to use the builder account which is paying a
         // fee and the recipient account which is receiving it, plus any counts receiving deposits
-        // in this block.
-
+        // in this block, and the accounts of the transfers.
         let missing_accounts = parent_state.forgotten_accounts(
             [chain_config.fee_recipient]
                 .into_iter()
                 .chain(builder_fee.accounts())
-                .chain(l1_deposits.accounts()),
+                .chain(l1_deposits.accounts())
+                .chain(
+                    transfers
+                        .iter()
+                        .flat_map(|tx| [tx.account(), tx.recipient()]),
+                ),
         );
 
         if !missing_accounts.is_empty() {
@@ -941,6 +1088,7 @@ impl BlockHeader<SeqTypes> for Header {
             chain_config,
             version,
             auction_results,
+            transfers,
         )?)
     }
 
@@ -1074,6 +1222,7 @@ impl BlockHeader<SeqTypes> for Header {
             chain_config,
             version,
             None,
+            vec![],
         )?)
     }
 
@@ -1188,7 +1337,10 @@ mod test_headers {
     use v0_1::{BlockMerkleTree, FeeMerkleTree, L1Client};
     use vbs::{bincode_serializer::BincodeSerializer, version::StaticVersion, BinarySerializer};
 
-    use crate::{eth_signature_key::EthKeyPair, mock::MockStateCatchup, Leaf};
+    use crate::{
+        eth_signature_key::EthKeyPair, mock::MockStateCatchup, v0_99::TransferTxBody, Leaf,
+        NsTableBuilder,
+    };
 
     use super::*;
 
@@ -1276,6 +1428,7 @@ mod test_headers {
                 genesis.instance_state.chain_config,
                 Version { major: 0, minor: 1 },
                 None,
+                vec![],
             )
             .unwrap();
             assert_eq!(header.height(), parent.height() + 1);
@@ -1569,6 +1722,7 @@ mod test_headers {
                 &genesis_state.peers,
                 &parent_leaf,
                 &proposal,
+                ViewNumber::new(*parent_leaf.view_number() + 1),
                 StaticVersion::<0, 1>::version(),
             )
             .await
@@ -1589,6 +1743,178 @@ mod test_headers {
         // );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_header_full_network_txs() {
+        setup_test();
+
+        let genesis = GenesisForTest::default().await;
+        let key = FeeAccount::test_key_pair();
+        let account = key.fee_account();
+        let (recipient, _) = FeeAccount::generated_from_seed_indexed([1; 32], 0);
+        let mut parent_state = genesis.validated_state.clone();
+        parent_state.prefund_account(account, 10.into());
+
+        let view = *genesis.leaf.view_number() + 1;
+        let transfer = |amount: u64| {
+            TransferTxBody::new(account, recipient, amount.into(), ViewNumber::new(view))
+                .signed(&key)
+                .unwrap()
+        };
+        let fee_signature = FeeAccount::sign_sequencing_fee_marketplace(&key, 0, view).unwrap();
+
+        // The proposer leaves out transfers which do not apply.
+        let proposal = Header::from_info(
+            genesis.header.payload_commitment(),
+            genesis.header.builder_commitment().clone(),
+            genesis.ns_table,
+            &genesis.leaf,
+            L1Snapshot {
+                head: 0,
+                finalized: None,
+            },
+            &[],
+            vec![BuilderFee {
+                fee_account: account,
+                fee_amount: 0,
+                fee_signature,
+            }],
+            view,
+            genesis.header.timestamp(),
+            parent_state.clone(),
+            genesis.instance_state.chain_config,
+            MarketplaceVersion::version(),
+            Some(SolverAuctionResults::genesis()),
+            vec![transfer(7), transfer(4)],
+        )
+        .unwrap();
+        assert_eq!(
+            proposal.full_network_txs(),
+            [FullNetworkTx::Transfer(transfer(7))]
+        );
+
+        // Applying the header executes its transfers.
+        let (mut state, delta) = parent_state
+            .apply_header(
+                &genesis.instance_state,
+                &genesis.instance_state.peers,
+                &genesis.leaf,
+                &proposal,
+                ViewNumber::new(view),
+                MarketplaceVersion::version(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            state.fee_merkle_tree.commitment(),
+            proposal.fee_merkle_tree_root()
+        );
+        assert_eq!(state.balance(account), Some(3.into()));
+        assert_eq!(state.balance(recipient), Some(7.into()));
+        assert!(delta.fees_delta.contains(&recipient));
+
+        // A header which repeats a transfer is rejected.
+        let Header::V99(mut fields) = proposal else {
+            unreachable!()
+        };
+        fields
+            .full_network_txs
+            .push(fields.full_network_txs[0].clone());
+        parent_state
+            .apply_header(
+                &genesis.instance_state,
+                &genesis.instance_state.peers,
+                &genesis.leaf,
+                &Header::V99(fields),
+                ViewNumber::new(view),
+                MarketplaceVersion::version(),
+            )
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_next_base_fee() {
+        setup_test();
+
+        let genesis = GenesisForTest::default().await;
+        let chain_config = |base_fee: u64| ChainConfig {
+            base_fee: base_fee.into(),
+            max_block_size: 1000.into(),
+            ..genesis.instance_state.chain_config
+        };
+        let parent = |block_size: usize, base_fee: Option<u64>| {
+            let mut ns_table = NsTableBuilder::new();
+            if block_size > 0 {
+                ns_table.append_entry(NamespaceId::from(1u64), block_size);
+            }
+            let mut header = Header::create(
+                genesis.instance_state.chain_config,
+                1,
+                2,
+                3,
+                Default::default(),
+                genesis.header.payload_commitment(),
+                genesis.header.builder_commitment().clone(),
+                ns_table.into_ns_table(),
+                genesis.header.fee_merkle_tree_root(),
+                genesis.header.block_merkle_tree_root(),
+                vec![FeeInfo::genesis()],
+                Default::default(),
+                Version {
+                    major: 0,
+                    minor: if base_fee.is_some() { 4 } else { 3 },
+                },
+            );
+            if let (Header::V4(fields), Some(base_fee)) = (&mut header, base_fee) {
+                fields.base_fee = base_fee.into();
+            }
+            header
+        };
+
+        // A parent without a dynamic base fee starts from `ChainConfig::base_fee`.
+        assert_eq!(
+            parent(500, None).next_base_fee(&chain_config(100)),
+            100.into()
+        );
+        assert_eq!(
+            parent(1000, None).next_base_fee(&chain_config(100)),
+            112.into()
+        );
+
+        // The base fee stays constant at the target block size, rises after larger blocks and
+        // falls after smaller ones.
+        assert_eq!(
+            parent(500, Some(800)).next_base_fee(&chain_config(100)),
+            800.into()
+        );
+        assert_eq!(
+            parent(1000, Some(800)).next_base_fee(&chain_config(100)),
+            900.into()
+        );
+        assert_eq!(
+            parent(750, Some(800)).next_base_fee(&chain_config(100)),
+            850.into()
+        );
+        assert_eq!(
+            parent(250, Some(800)).next_base_fee(&chain_config(100)),
+            750.into()
+        );
+        assert_eq!(
+            parent(0, Some(800)).next_base_fee(&chain_config(100)),
+            700.into()
+        );
+
+        // The base fee never falls below `ChainConfig::base_fee`.
+        assert_eq!(
+            parent(0, Some(112)).next_base_fee(&chain_config(100)),
+            100.into()
+        );
+
+        // A base fee of 0 can still grow.
+        assert_eq!(parent(1000, None).next_base_fee(&chain_config(0)), 1.into());
+        assert_eq!(parent(0, None).next_base_fee(&chain_config(0)), 0.into());
+    }
+
     #[test]
     fn verify_builder_signature() {
         // simulate a fixed size hash by padding our message
@@ -1681,6 +2007,33 @@ mod test_headers {
             },
         );
 
+        let v4_header = Header::create(
+            genesis.instance_state.chain_config,
+            1,
+            2,
+            3,
+            Default::default(),
+            header.payload_commitment(),
+            header.builder_commitment().clone(),
+            ns_table.clone(),
+            header.fee_merkle_tree_root(),
+            header.block_merkle_tree_root(),
+            vec![FeeInfo {
+                amount: 0.into(),
+                account: fee_account,
+            }],
+            Default::default(),
+            Version { major: 0, minor: 4 },
+        );
+        assert_eq!(
+            v4_header.base_fee(),
+            Some(genesis.instance_state.chain_config.base_fee)
+        );
+
+        let serialized = serde_json::to_string(&v4_header).unwrap();
+        let deserialized: Header = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(v4_header, deserialized);
+
         let serialized = serde_json::to_string(&v99_header).unwrap();
         let deserialized: Header = serde_json::from_str(&serialized).unwrap();
         assert_eq!(v99_header, deserialized);
@@ -1695,6 +2048,11 @@ mod test_headers {
             BincodeSerializer::<StaticVersion<0, 2>>::deserialize(&v2_bytes).unwrap();
         assert_eq!(v2_header, deserialized);
 
+        let v4_bytes = BincodeSerializer::<StaticVersion<0, 4>>::serialize(&v4_header).unwrap();
+        let deserialized: Header =
+            BincodeSerializer::<StaticVersion<0, 4>>::deserialize(&v4_bytes).unwrap();
+        assert_eq!(v4_header, deserialized);
+
         let v99_bytes = BincodeSerializer::<StaticVersion<0, 99>>::serialize(&v99_header).unwrap();
         let deserialized: Header =
             BincodeSerializer::<StaticVersion<0, 99>>::deserialize(&v99_bytes).unwrap();
@@ -1,5 +1,5 @@
 use crate::v0::{
-    traits::StateCatchup, v0_99::ChainConfig, GenesisHeader, L1BlockInfo, L1Client, PubKey,
+    traits::StateCatchup, v0_4::ChainConfig, GenesisHeader, L1BlockInfo, L1Client, PubKey,
     Timestamp, Upgrade, UpgradeMode,
 };
 use hotshot_types::traits::states::InstanceState;
@@ -9,7 +9,7 @@ use vbs::version::Version;
 #[cfg(any(test, feature = "testing"))]
 use vbs::version::{StaticVersion, StaticVersionType};
 
-use super::state::ValidatedState;
+use super::{state::ValidatedState, transfer::PendingTransfers};
 
 /// Represents the immutable state of a node.
 ///
@@ -17,7 +17,8 @@ use super::state::ValidatedState;
 #[derive(derive_more::Debug, Clone)]
 pub struct NodeState {
     pub node_id: u64,
-    pub chain_config: crate::v0_99::ChainConfig,
+    /// The chain config in the v0.4 format, which can represent the chain config of every version.
+    pub chain_config: crate::v0_4::ChainConfig,
     pub l1_client: L1Client,
     #[debug("{}", peers.name())]
     pub peers: Arc<dyn StateCatchup>,
@@ -41,6 +42,9 @@ pub struct NodeState {
     /// to use in functions such as genesis.
     /// (example: genesis returns V2 Header if version is 0.2)
     pub current_version: Version,
+
+    /// Transfers waiting to be included in a block proposed by this node.
+    pub pending_transfers: PendingTransfers,
 }
 
 impl NodeState {
@@ -64,6 +68,7 @@ impl NodeState {
             l1_genesis: None,
             upgrades: Default::default(),
             current_version,
+            pending_transfers: Default::default(),
         }
     }
 
@@ -93,6 +98,19 @@ impl NodeState {
         )
     }
 
+    #[cfg(any(test, feature = "testing"))]
+    pub fn mock_v4() -> Self {
+        use vbs::version::StaticVersion;
+
+        Self::new(
+            0,
+            ChainConfig::default(),
+            L1Client::http("http://localhost:3331".parse().unwrap()),
+            mock::MockStateCatchup::default(),
+            StaticVersion::<0, 4>::version(),
+        )
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn mock_v99() -> Self {
         use vbs::version::StaticVersion;
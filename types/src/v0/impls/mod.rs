@@ -10,12 +10,15 @@ mod l1;
 mod solver;
 mod state;
 mod transaction;
+mod transfer;
 
-pub use auction::SolverAuctionResultsProvider;
+pub use auction::{ExecutionError, SolverAuctionResultsProvider};
 pub use fee_info::{retain_accounts, FeeError};
 pub use instance_state::NodeState;
 pub use state::ProposalValidationError;
 pub use state::{get_l1_deposits, BuilderValidationError, StateValidationError, ValidatedState};
+pub use transaction::{SubmitError, TransactionStatus, TransactionValidationError};
+pub use transfer::PendingTransfers;
 
 #[cfg(any(test, feature = "testing"))]
 pub use instance_state::mock;
@@ -93,6 +93,8 @@ impl Committable for RollupUpdatebody {
             comm = comm.u64_field("text", 0);
         }
 
+        comm = comm.u64_field("nonce", self.nonce);
+
         comm.finalize()
     }
 }
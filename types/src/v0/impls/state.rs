@@ -23,15 +23,16 @@ use serde::{Deserialize, Serialize};
 use std::ops::Add;
 use thiserror::Error;
 use time::OffsetDateTime;
-use vbs::version::Version;
+use vbs::version::{StaticVersionType, Version};
 
 use super::{
     auction::ExecutionError, fee_info::FeeError, instance_state::NodeState, BlockMerkleCommitment,
-    BlockSize, FeeMerkleCommitment, L1Client,
+    BlockSize, FeeMarketVersion, FeeMerkleCommitment, L1Client,
 };
 use crate::{
     traits::StateCatchup,
-    v0_99::{ChainConfig, FullNetworkTx, IterableFeeInfo, ResolvableChainConfig},
+    v0_4::{ChainConfig, ResolvableChainConfig},
+    v0_99::{FullNetworkTx, IterableFeeInfo},
     BlockMerkleTree, Delta, FeeAccount, FeeAmount, FeeInfo, FeeMerkleTree, Header, Leaf2,
     NsTableValidationError, PayloadByteLen, SeqTypes, UpgradeType, BLOCK_MERKLE_TREE_HEIGHT,
     FEE_MERKLE_TREE_HEIGHT,
@@ -78,6 +79,11 @@ pub enum ProposalValidationError {
         base_fee: FeeAmount,
         proposed_fee: FeeAmount,
     },
+    #[error("Invalid base fee: expected={expected:?}, proposal={proposal:?}")]
+    InvalidBaseFee {
+        expected: Option<FeeAmount>,
+        proposal: Option<FeeAmount>,
+    },
     #[error("Invalid Height: parent_height={parent_height}, proposal_height={proposal_height}")]
     InvalidHeight {
         parent_height: u64,
@@ -121,6 +127,8 @@ pub enum ProposalValidationError {
     BuilderValidationError(BuilderValidationError),
     #[error("Invalid proposal: l1 finalized does not match the proposal")]
     InvalidL1Finalized,
+    #[error("Invalid full network transaction: {0}")]
+    InvalidFullNetworkTx(ExecutionError),
 }
 
 impl StateDelta for Delta {}
@@ -335,6 +343,22 @@ impl<'a> Proposal<'a> {
         Ok(())
     }
 
+    /// The dynamic base fee of the proposal must be equal to the one
+    /// computed from its parent.
+    fn validate_base_fee(
+        &self,
+        expected_base_fee: Option<FeeAmount>,
+    ) -> Result<(), ProposalValidationError> {
+        let proposed_base_fee = self.header.base_fee();
+        if proposed_base_fee != expected_base_fee {
+            return Err(ProposalValidationError::InvalidBaseFee {
+                expected: expected_base_fee,
+                proposal: proposed_base_fee,
+            });
+        }
+        Ok(())
+    }
+
     /// The timestamp must be non-decreasing relative to parent.
     fn validate_timestamp_non_dec(
         &self,
@@ -516,12 +540,20 @@ impl<'a> ValidatedTransition<'a> {
         }
         Ok(())
     }
-    /// Validates proposals [`ChainConfig`] against expectation by comparing commitments.
+    /// Validates proposals [`ChainConfig`] against expectation by comparing commitments, and the
+    /// proposed dynamic base fee against the one computed from the parent.
     fn validate_chain_config(&self) -> Result<(), ProposalValidationError> {
         self.proposal
             .validate_chain_config(&self.expected_chain_config)?;
+        self.proposal.validate_base_fee(self.expected_base_fee())?;
         Ok(())
     }
+    /// The dynamic base fee the proposal must carry, or `None` if the
+    /// proposal's version has no dynamic base fee.
+    fn expected_base_fee(&self) -> Option<FeeAmount> {
+        (self.proposal.header.version() >= FeeMarketVersion::version())
+            .then(|| self.parent.next_base_fee(&self.expected_chain_config))
+    }
     /// Validate that proposal block size does not exceed configured
     /// `ChainConfig.max_block_size`.
     fn validate_block_size(&self) -> Result<(), ProposalValidationError> {
@@ -535,7 +567,8 @@ impl<'a> ValidatedTransition<'a> {
         Ok(())
     }
     /// Validate that [`FeeAmount`] (or sum of fees for Marketplace Version) is
-    /// sufficient for block size.
+    /// sufficient for block size, at the dynamic base fee if the proposal has
+    /// one and at `ChainConfig.base_fee` otherwise.
     fn validate_fee(&self) -> Result<(), ProposalValidationError> {
         // TODO this should be updated to `base_fee * bundle_size` when we have
         // VID per bundle or namespace.
@@ -543,10 +576,21 @@ impl<'a> ValidatedTransition<'a> {
             return Err(ProposalValidationError::SomeFeeAmountOutOfRange);
         };
 
-        if amount < self.expected_chain_config.base_fee * self.proposal.block_size {
+        // The dynamic base fee has already been checked by `validate_chain_config`.
+        let base_fee = self
+            .proposal
+            .header
+            .base_fee()
+            .unwrap_or(self.expected_chain_config.base_fee);
+        let min_fee = self.expected_chain_config.block_fee(
+            base_fee,
+            self.proposal.header.ns_table(),
+            self.proposal.block_size,
+        );
+        if amount < min_fee {
             return Err(ProposalValidationError::InsufficientFee {
                 max_block_size: self.expected_chain_config.max_block_size,
-                base_fee: self.expected_chain_config.base_fee,
+                base_fee,
                 proposed_fee: amount,
             });
         }
@@ -700,12 +744,14 @@ impl ValidatedState {
     ///   * Resolves [`ChainConfig`].
     ///   * Performs catchup.
     ///   * Charges fees.
+    ///   * Executes full network transactions, as of `view`, the view of the proposal.
     pub async fn apply_header(
         &self,
         instance: &NodeState,
         peers: &impl StateCatchup,
         parent_leaf: &Leaf2,
         proposed_header: &Header,
+        view: ViewNumber,
         version: Version,
     ) -> anyhow::Result<(Self, Delta)> {
         // Clone state to avoid mutation. Consumer can take update
@@ -733,12 +779,18 @@ impl ValidatedState {
 
         // Find missing fee state entries. We will need to use the builder account which is paying a
         // fee and the recipient account which is receiving it, plus any counts receiving deposits
-        // in this block.
+        // in this block, and any touched by its full network transactions.
         let missing_accounts = self.forgotten_accounts(
             [chain_config.fee_recipient]
                 .into_iter()
                 .chain(proposed_header.fee_info().accounts())
-                .chain(l1_deposits.accounts()),
+                .chain(l1_deposits.accounts())
+                .chain(
+                    proposed_header
+                        .full_network_txs()
+                        .iter()
+                        .flat_map(|tx| tx.accounts(&chain_config)),
+                ),
         );
 
         let parent_height = parent_leaf.height();
@@ -797,6 +849,13 @@ impl ValidatedState {
             chain_config.fee_recipient,
         )?;
 
+        apply_full_transactions(
+            &mut validated_state,
+            &mut delta,
+            view,
+            proposed_header.full_network_txs(),
+        )?;
+
         Ok((validated_state, delta))
     }
 
@@ -848,13 +907,32 @@ impl ValidatedState {
     }
 }
 
-fn _apply_full_transactions(
+/// Execute `full_network_txs` in order in `view`, recording the accounts they touch in `delta`.
+fn apply_full_transactions(
     validated_state: &mut ValidatedState,
-    full_network_txs: Vec<FullNetworkTx>,
+    delta: &mut Delta,
+    view: ViewNumber,
+    full_network_txs: &[FullNetworkTx],
 ) -> Result<(), ExecutionError> {
-    full_network_txs
+    // Transactions are bound to a single view, so the only way to replay one is to include it
+    // twice in the same block. Signatures are malleable, so a replay may carry a different
+    // signature over the same body.
+    if !full_network_txs
         .iter()
-        .try_for_each(|tx| tx.execute(validated_state))
+        .map(FullNetworkTx::body_commitment)
+        .all_unique()
+    {
+        return Err(ExecutionError::DuplicateTransaction);
+    }
+    for tx in full_network_txs {
+        tx.execute(validated_state, view)?;
+        let chain_config = validated_state
+            .chain_config
+            .resolve()
+            .ok_or(ExecutionError::UnresolvableChainConfig)?;
+        delta.fees_delta.extend(tx.accounts(&chain_config));
+    }
+    Ok(())
 }
 
 pub async fn get_l1_deposits(
@@ -907,19 +985,28 @@ impl HotShotState<SeqTypes> for ValidatedState {
         version: Version,
         view_number: u64,
     ) -> Result<(Self, Self::Delta), Self::Error> {
-        // Unwrapping here is okay as we retry in a loop
-        //so we should either get a validated state or until hotshot cancels the task
-        let (validated_state, delta) = self
+        let applied = self
             // TODO We can add this logic to `ValidatedTransition` or do something similar to that here.
             .apply_header(
                 instance,
                 &instance.peers,
                 parent_leaf,
                 proposed_header,
+                ViewNumber::new(view_number),
                 version,
             )
-            .await
-            .unwrap();
+            .await;
+        let (validated_state, delta) = match applied {
+            // A full network transaction which cannot be executed makes the proposal invalid, which
+            // retrying would not change.
+            Err(err) if err.is::<ExecutionError>() => {
+                let err = err.downcast::<ExecutionError>().unwrap();
+                return Err(ProposalValidationError::InvalidFullNetworkTx(err).into());
+            }
+            // Unwrapping here is okay as we retry in a loop
+            //so we should either get a validated state or until hotshot cancels the task
+            applied => applied.unwrap(),
+        };
 
         // Validate the proposal.
         let validated_state = ValidatedTransition::new(
@@ -1059,7 +1146,7 @@ impl MerklizedState<SeqTypes, { Self::ARITY }> for FeeMerkleTree {
 
 #[cfg(test)]
 mod test {
-    use ethers::types::U256;
+    use ethers::types::{Signature, U256};
     use hotshot::{helpers::initialize_logging, traits::BlockPayload};
     use hotshot_query_service::Resolvable;
     use hotshot_types::traits::{
@@ -1073,8 +1160,8 @@ mod test {
     use super::*;
     use crate::{
         eth_signature_key::{BuilderSignature, EthKeyPair},
-        v0_1, v0_2, v0_3,
-        v0_99::{self, BidTx},
+        v0_1, v0_2, v0_3, v0_4,
+        v0_99::{self, BidTx, TransferTx, TransferTxBody},
         BlockSize, FeeAccountProof, FeeMerkleProof, Leaf, Payload, Transaction,
     };
 
@@ -1114,6 +1201,11 @@ mod test {
                     timestamp: OffsetDateTime::now_utc().unix_timestamp() as u64,
                     ..parent.clone()
                 }),
+                Header::V4(parent) => Header::V4(v0_4::Header {
+                    height: parent.height + 1,
+                    timestamp: OffsetDateTime::now_utc().unix_timestamp() as u64,
+                    ..parent.clone()
+                }),
                 Header::V99(_) => {
                     panic!("You called `Header.next()` on unimplemented version (v3)")
                 }
@@ -1144,6 +1236,11 @@ mod test {
                     builder_signature: Some(sig),
                     ..header.clone()
                 }),
+                Header::V4(header) => Header::V4(v0_4::Header {
+                    fee_info,
+                    builder_signature: Some(sig),
+                    ..header.clone()
+                }),
                 Header::V99(_) => {
                     panic!("You called `Header.sign()` on unimplemented version (v3)")
                 }
@@ -1178,6 +1275,11 @@ mod test {
                     builder_signature: Some(sig),
                     ..parent.clone()
                 }),
+                Header::V4(parent) => Header::V4(v0_4::Header {
+                    fee_info,
+                    builder_signature: Some(sig),
+                    ..parent.clone()
+                }),
                 Header::V99(_) => panic!(
                     "You called `Header.invalid_builder_signature()` on unimplemented version (v3)"
                 ),
@@ -1217,16 +1319,138 @@ mod test {
         let mut state = ValidatedState::default();
         let txs = mock_full_network_txs(None);
         // Default key can be verified b/c it is the same that signs the mock tx
-        _apply_full_transactions(&mut state, txs).unwrap();
+        apply_full_transactions(
+            &mut state,
+            &mut Delta::default(),
+            ViewNumber::genesis(),
+            &txs,
+        )
+        .unwrap();
 
         // Tx will be invalid if it is signed by a different key than
         // set in `account` field.
         let key = FeeAccount::generated_from_seed_indexed([1; 32], 0).1;
         let invalid = mock_full_network_txs(Some(key));
-        let err = _apply_full_transactions(&mut state, invalid).unwrap_err();
+        let err = apply_full_transactions(
+            &mut state,
+            &mut Delta::default(),
+            ViewNumber::genesis(),
+            &invalid,
+        )
+        .unwrap_err();
         assert_eq!(ExecutionError::InvalidSignature, err);
     }
 
+    /// The other valid signature of the same message: ECDSA signatures remain valid if `s` is
+    /// negated modulo the curve order and the recovery ID flipped.
+    fn malleate(sig: Signature) -> Signature {
+        let order = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            16,
+        )
+        .unwrap();
+        Signature {
+            r: sig.r,
+            s: order - sig.s,
+            v: sig.v ^ 1,
+        }
+    }
+
+    #[test]
+    fn test_apply_full_transfer_tx() {
+        let key = FeeAccount::test_key_pair();
+        let account = key.fee_account();
+        let (recipient, recipient_key) = FeeAccount::generated_from_seed_indexed([1; 32], 0);
+        let view = ViewNumber::new(5);
+        let mut state = ValidatedState::default();
+        let mut delta = Delta::default();
+        state.prefund_account(account, 10.into());
+
+        let transfer = |key: &EthKeyPair, from, to, amount: u64, view| {
+            FullNetworkTx::Transfer(
+                TransferTxBody::new(from, to, amount.into(), view)
+                    .signed(key)
+                    .unwrap(),
+            )
+        };
+
+        // Transfers apply in order.
+        apply_full_transactions(
+            &mut state,
+            &mut delta,
+            view,
+            &[
+                transfer(&key, account, recipient, 7, view),
+                transfer(&recipient_key, recipient, account, 2, view),
+            ],
+        )
+        .unwrap();
+        assert_eq!(state.balance(account), Some(5.into()));
+        assert_eq!(state.balance(recipient), Some(5.into()));
+        assert_eq!(delta.fees_delta, [account, recipient].into_iter().collect());
+
+        // A transfer cannot be replayed in a later view...
+        let tx = transfer(&key, account, recipient, 1, view);
+        apply_full_transactions(&mut state, &mut delta, view, &[tx.clone()]).unwrap();
+        let err =
+            apply_full_transactions(&mut state, &mut delta, ViewNumber::new(6), &[tx.clone()])
+                .unwrap_err();
+        assert_eq!(
+            err,
+            ExecutionError::InvalidView {
+                tx_view: view,
+                view: ViewNumber::new(6)
+            }
+        );
+
+        // ...or in the same block.
+        let mut state = ValidatedState::default();
+        state.prefund_account(account, 10.into());
+        let err = apply_full_transactions(&mut state, &mut delta, view, &[tx.clone(), tx.clone()])
+            .unwrap_err();
+        assert_eq!(err, ExecutionError::DuplicateTransaction);
+        assert_eq!(state.balance(account), Some(10.into()));
+
+        // A replay is detected even if its signature differs.
+        let FullNetworkTx::Transfer(signed) = &tx else {
+            unreachable!()
+        };
+        let resigned = FullNetworkTx::Transfer(TransferTx {
+            body: signed.body.clone(),
+            signature: malleate(signed.signature),
+        });
+        assert_ne!(resigned, tx);
+        let err =
+            apply_full_transactions(&mut state, &mut delta, view, &[tx, resigned]).unwrap_err();
+        assert_eq!(err, ExecutionError::DuplicateTransaction);
+        assert_eq!(state.balance(account), Some(10.into()));
+
+        // Transfers must be signed by the sending account.
+        let err = apply_full_transactions(
+            &mut state,
+            &mut delta,
+            view,
+            &[transfer(&recipient_key, account, recipient, 1, view)],
+        )
+        .unwrap_err();
+        assert_eq!(err, ExecutionError::InvalidSignature);
+
+        // Transfers cannot exceed the sender's balance.
+        let err = apply_full_transactions(
+            &mut state,
+            &mut delta,
+            view,
+            &[transfer(&key, account, recipient, 11, view)],
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ExecutionError::FeeError(FeeError::InsufficientFunds { .. })
+        ));
+        assert_eq!(state.balance(account), Some(10.into()));
+        assert_eq!(state.balance(recipient), Some(0.into()));
+    }
+
     #[test]
     fn test_fee_proofs() {
         initialize_logging();
@@ -1420,6 +1644,96 @@ mod test {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_validation_dynamic_base_fee() {
+        initialize_logging();
+        // Setup
+        let instance = NodeState::mock_v4();
+        let parent: Leaf2 = Leaf::genesis(&instance.genesis_state, &instance)
+            .await
+            .into();
+        let parent = parent.block_header();
+        let expected = parent.next_base_fee(&instance.chain_config);
+        let with_base_fee = |base_fee: FeeAmount| match parent {
+            Header::V4(parent) => Header::V4(v0_4::Header {
+                height: parent.height + 1,
+                base_fee,
+                ..parent.clone()
+            }),
+            _ => panic!("expected a v4 header"),
+        };
+
+        // The proposal must carry the base fee computed from its parent.
+        let header = with_base_fee(expected);
+        let proposal = Proposal::new(&header, 0);
+        ValidatedTransition::mock(instance.clone(), parent, proposal)
+            .validate_chain_config()
+            .unwrap();
+
+        let header = with_base_fee(expected + 1.into());
+        let proposal = Proposal::new(&header, 0);
+        let err = ValidatedTransition::mock(instance.clone(), parent, proposal)
+            .validate_chain_config()
+            .unwrap_err();
+        assert_eq!(
+            ProposalValidationError::InvalidBaseFee {
+                expected: Some(expected),
+                proposal: Some(expected + 1.into()),
+            },
+            err
+        );
+
+        // The fee is checked against the dynamic base fee rather than `ChainConfig::base_fee`.
+        let header = with_base_fee(1000.into());
+        let proposal = Proposal::new(&header, 10);
+        let err = ValidatedTransition::mock(instance.clone(), parent, proposal)
+            .validate_fee()
+            .unwrap_err();
+        assert_eq!(
+            ProposalValidationError::InsufficientFee {
+                max_block_size: instance.chain_config.max_block_size,
+                base_fee: 1000.into(),
+                proposed_fee: header.fee_info().amount().unwrap()
+            },
+            err
+        );
+
+        // v0.99 proposals carry the base fee forward.
+        let instance = NodeState::mock_v99();
+        let parent: Leaf2 = Leaf::genesis(&instance.genesis_state, &instance)
+            .await
+            .into();
+        let parent = parent.block_header();
+        let expected = parent.next_base_fee(&instance.chain_config);
+        let with_base_fee = |base_fee: Option<FeeAmount>| match parent {
+            Header::V99(parent) => Header::V99(v0_99::Header {
+                height: parent.height + 1,
+                base_fee,
+                ..parent.clone()
+            }),
+            _ => panic!("expected a v99 header"),
+        };
+
+        let header = with_base_fee(Some(expected));
+        let proposal = Proposal::new(&header, 0);
+        ValidatedTransition::mock(instance.clone(), parent, proposal)
+            .validate_chain_config()
+            .unwrap();
+
+        let header = with_base_fee(None);
+        let proposal = Proposal::new(&header, 0);
+        let err = ValidatedTransition::mock(instance.clone(), parent, proposal)
+            .validate_chain_config()
+            .unwrap_err();
+        assert_eq!(
+            ProposalValidationError::InvalidBaseFee {
+                expected: Some(expected),
+                proposal: None,
+            },
+            err
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_validation_height() {
         initialize_logging();
@@ -1791,6 +2105,11 @@ mod test {
                 fee_info: FeeInfo::new(account, data),
                 ..header
             }),
+            Header::V4(header) => Header::V4(v0_4::Header {
+                builder_signature: Some(sig),
+                fee_info: FeeInfo::new(account, data),
+                ..header
+            }),
             Header::V99(header) => Header::V99(v0_99::Header {
                 builder_signature: vec![sig],
                 fee_info: vec![FeeInfo::new(account, data)],
@@ -1851,6 +2170,11 @@ mod test {
                 fee_info: FeeInfo::new(account, data),
                 ..header
             }),
+            Header::V4(header) => Header::V4(v0_4::Header {
+                builder_signature: Some(sig),
+                fee_info: FeeInfo::new(account, data),
+                ..header
+            }),
             Header::V99(header) => Header::V99(v0_99::Header {
                 builder_signature: vec![sig],
                 fee_info: vec![FeeInfo::new(account, data)],
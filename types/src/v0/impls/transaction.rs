@@ -1,9 +1,10 @@
 use committable::{Commitment, Committable};
 use hotshot_query_service::explorer::ExplorerTransaction;
 use hotshot_types::traits::block_contents::Transaction as HotShotTransaction;
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use thiserror::Error;
 
-use crate::{NamespaceId, Transaction};
+use crate::{NamespaceId, NsIndex, Transaction};
 
 use super::{NsPayloadBuilder, NsTableBuilder};
 
@@ -27,7 +28,7 @@ impl<'de> Deserialize<'de> for NamespaceId {
         use serde::de::Unexpected;
 
         let ns_id = <u64 as Deserialize>::deserialize(deserializer)?;
-        if ns_id > u32::MAX as u64 {
+        if ns_id > NamespaceId::MAX {
             Err(D::Error::invalid_value(
                 Unexpected::Unsigned(ns_id),
                 &"at most u32::MAX",
@@ -39,12 +40,55 @@ impl<'de> Deserialize<'de> for NamespaceId {
 }
 
 impl NamespaceId {
+    /// The largest namespace ID that can be encoded in a block.
+    pub const MAX: u64 = u32::MAX as u64;
+
     #[cfg(any(test, feature = "testing"))]
     pub fn random(rng: &mut dyn rand::RngCore) -> Self {
         Self(rng.next_u32() as u64)
     }
 }
 
+/// Reasons a transaction is rejected before being submitted to consensus.
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionValidationError {
+    #[error("transaction size ({size}) is greater than max_block_size ({max_block_size})")]
+    TooLarge { size: u64, max_block_size: u64 },
+    #[error(
+        "transaction size ({size}) exceeds the limit of {limit} bytes for namespace {namespace}"
+    )]
+    NamespaceSizeLimitExceeded {
+        namespace: NamespaceId,
+        size: u64,
+        limit: u64,
+    },
+}
+
+/// Reasons a submitted transaction is not passed on to consensus.
+#[derive(Clone, Debug, Error, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubmitError {
+    #[error(transparent)]
+    Invalid(#[from] TransactionValidationError),
+    #[error("failed to submit transaction: {0}")]
+    Failed(String),
+}
+
+/// Status of a transaction submitted to a node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionStatus {
+    /// Submitted, but not yet included in a decided block.
+    Pending,
+    /// Included in a decided block.
+    Included {
+        block_height: u64,
+        namespace: NamespaceId,
+        namespace_index: NsIndex,
+    },
+    /// Not included in a decided block soon enough after submission, and no longer tracked for
+    /// inclusion. The transaction was most likely dropped.
+    Expired,
+}
+
 impl Transaction {
     pub fn new(namespace: NamespaceId, payload: Vec<u8>) -> Self {
         Self { namespace, payload }
@@ -72,6 +116,37 @@ impl Transaction {
         }
     }
 
+    /// Check that the transaction fits in a block of at most `max_block_size` bytes, on its own.
+    pub fn validate_size(&self, max_block_size: u64) -> Result<(), TransactionValidationError> {
+        let size = self.size_in_block(true);
+        if size > max_block_size {
+            return Err(TransactionValidationError::TooLarge {
+                size,
+                max_block_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that the payload does not exceed the size limit configured for the transaction's
+    /// namespace, if any.
+    pub fn validate_namespace(
+        &self,
+        max_payload_size: Option<u64>,
+    ) -> Result<(), TransactionValidationError> {
+        let size = self.payload.len() as u64;
+        match max_payload_size {
+            Some(limit) if size > limit => {
+                Err(TransactionValidationError::NamespaceSizeLimitExceeded {
+                    namespace: self.namespace,
+                    size,
+                    limit,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn random(rng: &mut dyn rand::RngCore) -> Self {
         use rand::Rng;
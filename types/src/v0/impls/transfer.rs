@@ -0,0 +1,254 @@
+use super::{auction::ExecutionError, state::ValidatedState};
+use crate::{
+    eth_signature_key::{EthKeyPair, SigningError},
+    v0_99::{TransferTx, TransferTxBody},
+    FeeAccount, FeeAmount, FeeInfo,
+};
+use committable::{Commitment, Committable};
+use ethers::types::Signature;
+use hotshot_types::{
+    data::ViewNumber,
+    traits::{node_implementation::ConsensusTime, signature_key::BuilderSignatureKey},
+};
+use std::{collections::BTreeMap, sync::Arc};
+use tokio::sync::Mutex;
+
+impl Committable for TransferTx {
+    fn tag() -> String {
+        "TRANSFER_TX".to_string()
+    }
+
+    fn commit(&self) -> Commitment<Self> {
+        let comm = committable::RawCommitmentBuilder::new(&Self::tag())
+            .field("body", self.body.commit())
+            .fixed_size_field("signature", &self.signature.into());
+        comm.finalize()
+    }
+}
+
+impl Committable for TransferTxBody {
+    fn tag() -> String {
+        "TRANSFER_TX_BODY".to_string()
+    }
+
+    fn commit(&self) -> Commitment<Self> {
+        let comm = committable::RawCommitmentBuilder::new(&Self::tag())
+            .fixed_size_field("account", &self.account.to_fixed_bytes())
+            .fixed_size_field("recipient", &self.recipient.to_fixed_bytes())
+            .fixed_size_field("amount", &self.amount.to_fixed_bytes())
+            .u64_field("view", self.view.u64());
+        comm.finalize()
+    }
+}
+
+impl TransferTxBody {
+    /// Construct a new `TransferTxBody`.
+    pub fn new(
+        account: FeeAccount,
+        recipient: FeeAccount,
+        amount: FeeAmount,
+        view: ViewNumber,
+    ) -> Self {
+        Self {
+            account,
+            recipient,
+            amount,
+            view,
+        }
+    }
+
+    /// Sign Body and return a `TransferTx`. This is the expected way to obtain a `TransferTx`.
+    /// ```
+    /// # use espresso_types::FeeAccount;
+    /// # use espresso_types::v0_99::TransferTxBody;
+    ///
+    /// TransferTxBody::default().signed(&FeeAccount::test_key_pair()).unwrap();
+    /// ```
+    pub fn signed(self, key: &EthKeyPair) -> Result<TransferTx, SigningError> {
+        let signature = FeeAccount::sign_builder_message(key, self.commit().as_ref())?;
+        Ok(TransferTx {
+            body: self,
+            signature,
+        })
+    }
+}
+
+impl Default for TransferTxBody {
+    fn default() -> Self {
+        let key = FeeAccount::test_key_pair();
+        Self {
+            account: key.fee_account(),
+            recipient: FeeAccount::generated_from_seed_indexed([1; 32], 0).0,
+            amount: FeeAmount::from(1),
+            view: ViewNumber::genesis(),
+        }
+    }
+}
+
+impl TransferTx {
+    /// Execute `TransferTx` in `view`.
+    ///   * verify signature
+    ///   * check the transaction is for `view`
+    ///   * move the amount from the sending to the receiving account
+    pub fn execute(
+        &self,
+        state: &mut ValidatedState,
+        view: ViewNumber,
+    ) -> Result<(), ExecutionError> {
+        self.verify()?;
+
+        if self.view() != view {
+            return Err(ExecutionError::InvalidView {
+                tx_view: self.view(),
+                view,
+            });
+        }
+        if self.amount() == FeeAmount::default() {
+            return Err(ExecutionError::ZeroTransfer);
+        }
+
+        state
+            .charge_fee(
+                FeeInfo::new(self.account(), self.amount()),
+                self.recipient(),
+            )
+            .map_err(ExecutionError::from)
+    }
+    /// Cryptographic signature verification
+    pub fn verify(&self) -> Result<(), ExecutionError> {
+        self.body
+            .account
+            .validate_builder_signature(&self.signature, self.body.commit().as_ref())
+            .then_some(())
+            .ok_or(ExecutionError::InvalidSignature)
+    }
+    /// Return the body of the transaction
+    pub fn body(self) -> TransferTxBody {
+        self.body
+    }
+    /// get the account to debit
+    pub fn account(&self) -> FeeAccount {
+        self.body.account
+    }
+    /// get the account to credit
+    pub fn recipient(&self) -> FeeAccount {
+        self.body.recipient
+    }
+    /// get the transfer amount
+    pub fn amount(&self) -> FeeAmount {
+        self.body.amount
+    }
+    /// get the view number
+    pub fn view(&self) -> ViewNumber {
+        self.body.view
+    }
+    /// get the signature over the body
+    pub fn signature(&self) -> Signature {
+        self.signature
+    }
+}
+
+/// Transfers submitted to a node and waiting to be included in a block, by the view they are for.
+///
+/// Only the leader of the view a transfer is signed for can include it, so every node holds the
+/// transfers it hears about until it either proposes in their view or that view has passed.
+#[derive(Clone, Debug, Default)]
+pub struct PendingTransfers(Arc<Mutex<BTreeMap<ViewNumber, Vec<TransferTx>>>>);
+
+impl PendingTransfers {
+    /// The maximum number of transfers held at once.
+    ///
+    /// When this is reached, the transfers for the earliest view are dropped to make room.
+    pub const CAPACITY: usize = 1000;
+
+    /// Hold `tx` until a block is proposed in its view.
+    ///
+    /// Transfers with an invalid signature are rejected. A transfer with the same body as one
+    /// already held is ignored, since including both would make the block invalid.
+    pub async fn insert(&self, tx: TransferTx) -> Result<(), ExecutionError> {
+        tx.verify()?;
+
+        let mut pending = self.0.lock().await;
+        if pending
+            .values()
+            .flatten()
+            .any(|pending| pending.body == tx.body)
+        {
+            return Ok(());
+        }
+        if pending.values().map(Vec::len).sum::<usize>() >= Self::CAPACITY {
+            if let Some(earliest) = pending.first_entry() {
+                tracing::warn!(view = ?earliest.key(), "too many pending transfers, dropping view");
+                earliest.remove();
+            }
+        }
+        pending.entry(tx.view()).or_default().push(tx);
+        Ok(())
+    }
+
+    /// Take the transfers to include in a block proposed in `view`.
+    ///
+    /// Transfers for earlier views can no longer be executed, and are dropped.
+    pub async fn take(&self, view: ViewNumber) -> Vec<TransferTx> {
+        let mut pending = self.0.lock().await;
+        let later = pending.split_off(&ViewNumber::new(view.u64() + 1));
+        let txs = pending.remove(&view).unwrap_or_default();
+        *pending = later;
+        txs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn transfer(amount: u64, view: u64) -> TransferTx {
+        TransferTxBody {
+            amount: amount.into(),
+            view: ViewNumber::new(view),
+            ..Default::default()
+        }
+        .signed(&FeeAccount::test_key_pair())
+        .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pending_transfers() {
+        let pending = PendingTransfers::default();
+        for (amount, view) in [(1, 1), (2, 2), (3, 2), (4, 3)] {
+            pending.insert(transfer(amount, view)).await.unwrap();
+        }
+
+        // A transfer already held is not held twice.
+        pending.insert(transfer(2, 2)).await.unwrap();
+
+        // Transfers must be signed by the sending account.
+        let mut forged = transfer(6, 2);
+        forged.body.account = FeeAccount::generated_from_seed_indexed([1; 32], 0).0;
+        assert_eq!(
+            pending.insert(forged).await.unwrap_err(),
+            ExecutionError::InvalidSignature
+        );
+
+        // Taking the transfers for a view drops those for earlier views.
+        assert_eq!(
+            pending.take(ViewNumber::new(2)).await,
+            vec![transfer(2, 2), transfer(3, 2)]
+        );
+        assert_eq!(pending.take(ViewNumber::new(1)).await, vec![]);
+        assert_eq!(pending.take(ViewNumber::new(3)).await, vec![transfer(4, 3)]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pending_transfers_capacity() {
+        let pending = PendingTransfers::default();
+        for amount in 1..=PendingTransfers::CAPACITY as u64 {
+            pending.insert(transfer(amount, 1)).await.unwrap();
+        }
+
+        // When full, the transfers for the earliest view make room for new ones.
+        pending.insert(transfer(1, 2)).await.unwrap();
+        assert_eq!(pending.take(ViewNumber::new(1)).await, vec![]);
+        assert_eq!(pending.take(ViewNumber::new(2)).await, vec![transfer(1, 2)]);
+    }
+}
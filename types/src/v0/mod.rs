@@ -10,14 +10,21 @@ use hotshot_types::{
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
+mod api;
 mod header;
 mod impls;
 pub mod traits;
 mod utils;
+pub use api::{
+    BlocksFrontier, NamespaceMultiProofQueryData, NamespaceProofQueryData,
+    NamespaceProofRangeEntry, PublicHotShotConfig, PublicNetworkConfig, PublicValidatorConfig,
+    SequencerApiError, TransactionProofQueryData,
+};
 pub use header::Header;
 pub use impls::{
-    get_l1_deposits, retain_accounts, BuilderValidationError, FeeError, ProposalValidationError,
-    StateValidationError,
+    get_l1_deposits, retain_accounts, BuilderValidationError, ExecutionError, FeeError,
+    PendingTransfers, ProposalValidationError, StateValidationError, SubmitError,
+    TransactionStatus, TransactionValidationError,
 };
 pub use utils::*;
 use vbs::version::{StaticVersion, StaticVersionType};
@@ -35,7 +42,7 @@ pub use impls::mock;
 // instead we write `with_minor_versions!(some_macro!(args))`.
 macro_rules! with_minor_versions {
     ($m:ident!($($arg:tt),*)) => {
-        $m!($($arg,)* v0_1, v0_2, v0_3, v0_99);
+        $m!($($arg,)* v0_1, v0_2, v0_3, v0_4, v0_99);
     };
 }
 
@@ -92,6 +99,8 @@ reexport_unchanged_types!(
     NamespaceId,
     NsIndex,
     NsIter,
+    NsMultiProof,
+    NsNonInclusionProof,
     NsPayload,
     NsPayloadBuilder,
     NsPayloadByteLen,
@@ -178,6 +187,7 @@ pub type MockSequencerVersions = SequencerVersions<StaticVersion<0, 1>, StaticVe
 pub type V0_0 = StaticVersion<0, 0>;
 pub type V0_1 = StaticVersion<0, 1>;
 pub type FeeVersion = StaticVersion<0, 2>;
+pub type FeeMarketVersion = StaticVersion<0, 4>;
 pub type MarketplaceVersion = StaticVersion<0, 99>;
 pub type EpochVersion = StaticVersion<0, 100>;
 
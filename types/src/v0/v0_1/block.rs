@@ -17,6 +17,35 @@ pub struct NsProof {
     pub(crate) ns_proof: Option<LargeRangeProofType>, // `None` if ns_payload is empty
 }
 
+/// Proof of correctness for the payload bytes of several namespaces in a
+/// block.
+///
+/// Namespaces that are adjacent in the namespace table occupy a contiguous
+/// range of the payload, so each run of adjacent namespaces shares a single
+/// VID opening.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NsMultiProof {
+    pub(crate) groups: Vec<NsProofGroup>,
+}
+
+/// Proof of correctness for the payload bytes of a run of adjacent namespaces
+/// in a block. Part of a [`NsMultiProof`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NsProofGroup {
+    pub(crate) first_ns_index: NsIndex,
+    pub(crate) ns_payloads: Vec<NsPayloadOwned>,
+    pub(crate) ns_proof: Option<LargeRangeProofType>, // `None` if all ns_payloads are empty
+}
+
+/// Proof that a namespace is absent from a block.
+///
+/// The namespace table is committed in the block header, so the proof is
+/// simply the namespace table itself, verified against that commitment.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NsNonInclusionProof {
+    pub(crate) ns_table: NsTable,
+}
+
 /// Byte lengths for the different items that could appear in a namespace table.
 pub const NUM_NSS_BYTE_LEN: usize = 4;
 pub const NS_OFFSET_BYTE_LEN: usize = 4;
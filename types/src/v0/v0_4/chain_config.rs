@@ -0,0 +1,552 @@
+use crate::{v0_1, v0_99, BlockSize, ChainId, FeeAccount, FeeAmount, NamespaceId, NsTable};
+use anyhow::ensure;
+use committable::{Commitment, Committable};
+use ethers::types::{Address, U256};
+use itertools::{Either, Itertools};
+use serde::{Deserialize, Serialize};
+
+/// Global variables for an Espresso blockchain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChainConfig {
+    /// Espresso chain ID
+    pub chain_id: ChainId,
+
+    /// Maximum size in bytes of a block
+    pub max_block_size: BlockSize,
+
+    /// Minimum fee in WEI per byte of payload
+    pub base_fee: FeeAmount,
+
+    /// Fee contract address on L1.
+    ///
+    /// This is optional so that fees can easily be toggled on/off, with no need to deploy a
+    /// contract when they are off. In a future release, after fees are switched on and thoroughly
+    /// tested, this may be made mandatory.
+    pub fee_contract: Option<Address>,
+
+    /// Account that receives sequencing fees.
+    ///
+    /// This account in the Espresso fee ledger will always receive every fee paid in Espresso,
+    /// regardless of whether or not their is a `fee_contract` deployed. Once deployed, the fee
+    /// contract can decide what to do with tokens locked in this account in Espresso.
+    pub fee_recipient: FeeAccount,
+
+    /// Account that receives sequencing bids.
+    pub bid_recipient: Option<FeeAccount>,
+
+    /// Multipliers of the base fee for individual namespaces.
+    ///
+    /// Namespaces without a multiplier, or all namespaces if this is `None`, pay the base fee.
+    pub ns_fee_multipliers: Option<NsFeeMultipliers>,
+}
+
+/// Maximum number of entries in a [`NsFeeMultipliers`] table.
+pub const MAX_NS_FEE_MULTIPLIERS: usize = 16;
+
+/// The base fee multiplier of a single namespace.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct NsFeeMultiplier {
+    pub namespace: NamespaceId,
+    /// Multiplier of the base fee in basis points, so [`NsFeeMultipliers::UNIT`] charges the base
+    /// fee.
+    pub multiplier: u32,
+}
+
+/// A table of per-namespace base fee multipliers.
+///
+/// The table has a fixed capacity of [`MAX_NS_FEE_MULTIPLIERS`] so that [`ChainConfig`] stays
+/// `Copy`. Entries are kept sorted by namespace, which makes the commitment canonical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Vec<NsFeeMultiplier>", into = "Vec<NsFeeMultiplier>")]
+pub struct NsFeeMultipliers {
+    entries: [NsFeeMultiplier; MAX_NS_FEE_MULTIPLIERS],
+    len: usize,
+}
+
+impl NsFeeMultipliers {
+    /// The multiplier which charges exactly the base fee.
+    pub const UNIT: u32 = 10_000;
+
+    /// The entries of the table, sorted by namespace.
+    pub fn entries(&self) -> &[NsFeeMultiplier] {
+        &self.entries[..self.len]
+    }
+
+    /// The multiplier for namespace `ns_id`.
+    pub fn get(&self, ns_id: NamespaceId) -> u32 {
+        self.entries()
+            .binary_search_by_key(&ns_id, |entry| entry.namespace)
+            .map_or(Self::UNIT, |i| self.entries[i].multiplier)
+    }
+}
+
+impl TryFrom<Vec<NsFeeMultiplier>> for NsFeeMultipliers {
+    type Error = anyhow::Error;
+
+    fn try_from(mut entries: Vec<NsFeeMultiplier>) -> anyhow::Result<Self> {
+        ensure!(
+            entries.len() <= MAX_NS_FEE_MULTIPLIERS,
+            "too many namespace fee multipliers: {} (max {MAX_NS_FEE_MULTIPLIERS})",
+            entries.len()
+        );
+        entries.sort_by_key(|entry| entry.namespace);
+        ensure!(
+            entries.iter().map(|entry| entry.namespace).all_unique(),
+            "duplicate namespace in fee multipliers"
+        );
+
+        let mut table = Self {
+            entries: Default::default(),
+            len: entries.len(),
+        };
+        table.entries[..entries.len()].copy_from_slice(&entries);
+        Ok(table)
+    }
+}
+
+impl From<NsFeeMultipliers> for Vec<NsFeeMultiplier> {
+    fn from(table: NsFeeMultipliers) -> Self {
+        table.entries().to_vec()
+    }
+}
+
+impl Committable for NsFeeMultipliers {
+    fn tag() -> String {
+        "NS_FEE_MULTIPLIERS".to_string()
+    }
+
+    fn commit(&self) -> Commitment<Self> {
+        self.entries()
+            .iter()
+            .fold(
+                committable::RawCommitmentBuilder::new(&Self::tag())
+                    .u64_field("len", self.len as u64),
+                |comm, entry| {
+                    comm.u64_field("namespace", entry.namespace.0)
+                        .u64_field("multiplier", entry.multiplier.into())
+                },
+            )
+            .finalize()
+    }
+}
+
+impl ChainConfig {
+    /// The minimum total fee for a block of `block_size` bytes with namespace table `ns_table`,
+    /// when the base fee is `base_fee` per byte.
+    ///
+    /// Without [`ns_fee_multipliers`](Self::ns_fee_multipliers) this is `base_fee * block_size`.
+    /// Otherwise each namespace pays for its own bytes at `base_fee` scaled by its multiplier.
+    pub fn block_fee(&self, base_fee: FeeAmount, ns_table: &NsTable, block_size: u32) -> FeeAmount {
+        let Some(multipliers) = self.ns_fee_multipliers else {
+            return base_fee * block_size;
+        };
+        let weighted_bytes =
+            ns_table
+                .ns_byte_lens()
+                .fold(U256::zero(), |total, (ns_id, byte_len)| {
+                    total.saturating_add(
+                        U256::from(byte_len).saturating_mul(multipliers.get(ns_id).into()),
+                    )
+                });
+        FeeAmount(base_fee.0.saturating_mul(weighted_bytes) / NsFeeMultipliers::UNIT)
+    }
+
+    /// The highest fee per byte any namespace pays when the base fee is `base_fee`.
+    ///
+    /// Paying this for every byte of a block always covers [`block_fee`](Self::block_fee).
+    pub fn max_fee_per_byte(&self, base_fee: FeeAmount) -> FeeAmount {
+        let multiplier = self
+            .ns_fee_multipliers
+            .iter()
+            .flat_map(|multipliers| multipliers.entries())
+            .map(|entry| entry.multiplier)
+            .fold(NsFeeMultipliers::UNIT, u32::max);
+        // Round up, so that this never falls short of `block_fee`.
+        let unit = U256::from(NsFeeMultipliers::UNIT);
+        FeeAmount(
+            base_fee
+                .0
+                .saturating_mul(multiplier.into())
+                .saturating_add(unit - 1)
+                / unit,
+        )
+    }
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Deserialize, Serialize, Eq, Hash)]
+/// A commitment to a ChainConfig or a full ChainConfig.
+pub struct ResolvableChainConfig {
+    pub(crate) chain_config: Either<ChainConfig, Commitment<ChainConfig>>,
+}
+
+impl Committable for ChainConfig {
+    fn tag() -> String {
+        "CHAIN_CONFIG".to_string()
+    }
+
+    fn commit(&self) -> Commitment<Self> {
+        let comm = committable::RawCommitmentBuilder::new(&Self::tag())
+            .fixed_size_field("chain_id", &self.chain_id.to_fixed_bytes())
+            .u64_field("max_block_size", *self.max_block_size)
+            .fixed_size_field("base_fee", &self.base_fee.to_fixed_bytes())
+            .fixed_size_field("fee_recipient", &self.fee_recipient.to_fixed_bytes());
+        let comm = if let Some(addr) = self.fee_contract {
+            comm.u64_field("fee_contract", 1).fixed_size_bytes(&addr.0)
+        } else {
+            comm.u64_field("fee_contract", 0)
+        };
+
+        // With `ChainConfig` upgrades we want commitments w/out
+        // fields added >= v0_99 to have the same commitment as <= v0_99
+        // commitment. Therefore `None` values are simply ignored.
+        let comm = if let Some(bid_recipient) = self.bid_recipient {
+            comm.fixed_size_field("bid_recipient", &bid_recipient.to_fixed_bytes())
+        } else {
+            comm
+        };
+        let comm = if let Some(ns_fee_multipliers) = self.ns_fee_multipliers {
+            comm.field("ns_fee_multipliers", ns_fee_multipliers.commit())
+        } else {
+            comm
+        };
+
+        comm.finalize()
+    }
+}
+
+impl ResolvableChainConfig {
+    pub fn commit(&self) -> Commitment<ChainConfig> {
+        match self.chain_config {
+            Either::Left(config) => config.commit(),
+            Either::Right(commitment) => commitment,
+        }
+    }
+    pub fn resolve(self) -> Option<ChainConfig> {
+        match self.chain_config {
+            Either::Left(config) => Some(config),
+            Either::Right(_) => None,
+        }
+    }
+}
+
+impl From<Commitment<ChainConfig>> for ResolvableChainConfig {
+    fn from(value: Commitment<ChainConfig>) -> Self {
+        Self {
+            chain_config: Either::Right(value),
+        }
+    }
+}
+
+impl From<ChainConfig> for ResolvableChainConfig {
+    fn from(value: ChainConfig) -> Self {
+        Self {
+            chain_config: Either::Left(value),
+        }
+    }
+}
+
+impl From<&v0_1::ResolvableChainConfig> for ResolvableChainConfig {
+    fn from(
+        &v0_1::ResolvableChainConfig { chain_config }: &v0_1::ResolvableChainConfig,
+    ) -> ResolvableChainConfig {
+        match chain_config {
+            Either::Left(chain_config) => ResolvableChainConfig {
+                chain_config: Either::Left(ChainConfig::from(chain_config)),
+            },
+            Either::Right(c) => ResolvableChainConfig {
+                chain_config: Either::Right(Commitment::from_raw(*c.as_ref())),
+            },
+        }
+    }
+}
+
+impl From<&v0_99::ResolvableChainConfig> for ResolvableChainConfig {
+    fn from(
+        &v0_99::ResolvableChainConfig { chain_config }: &v0_99::ResolvableChainConfig,
+    ) -> ResolvableChainConfig {
+        match chain_config {
+            Either::Left(chain_config) => ResolvableChainConfig {
+                chain_config: Either::Left(ChainConfig::from(chain_config)),
+            },
+            Either::Right(c) => ResolvableChainConfig {
+                chain_config: Either::Right(Commitment::from_raw(*c.as_ref())),
+            },
+        }
+    }
+}
+
+impl From<v0_1::ChainConfig> for ChainConfig {
+    fn from(chain_config: v0_1::ChainConfig) -> ChainConfig {
+        let v0_1::ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            ..
+        } = chain_config;
+
+        ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            bid_recipient: None,
+            ns_fee_multipliers: None,
+        }
+    }
+}
+
+impl From<ChainConfig> for v0_1::ChainConfig {
+    fn from(chain_config: ChainConfig) -> v0_1::ChainConfig {
+        let ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            ..
+        } = chain_config;
+
+        v0_1::ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+        }
+    }
+}
+
+impl From<v0_99::ChainConfig> for ChainConfig {
+    fn from(chain_config: v0_99::ChainConfig) -> ChainConfig {
+        let v0_99::ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            bid_recipient,
+        } = chain_config;
+
+        ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            bid_recipient,
+            ns_fee_multipliers: None,
+        }
+    }
+}
+
+/// The v0.99 chain config has no namespace fee multipliers, so a config with a non-empty table
+/// cannot be converted without changing the fees it charges.
+impl TryFrom<ChainConfig> for v0_99::ChainConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(chain_config: ChainConfig) -> anyhow::Result<v0_99::ChainConfig> {
+        let ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            bid_recipient,
+            ns_fee_multipliers,
+        } = chain_config;
+        ensure!(
+            ns_fee_multipliers.is_none_or(|table| table.entries().is_empty()),
+            "namespace fee multipliers are not supported by the v0.99 chain config"
+        );
+
+        Ok(v0_99::ChainConfig {
+            chain_id,
+            max_block_size,
+            base_fee,
+            fee_contract,
+            fee_recipient,
+            bid_recipient,
+        })
+    }
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            chain_id: U256::from(35353).into(), // arbitrarily chosen chain ID
+            max_block_size: 30720.into(),
+            base_fee: 0.into(),
+            fee_contract: None,
+            fee_recipient: Default::default(),
+            bid_recipient: None,
+            ns_fee_multipliers: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::v0_1::NsTableBuilder;
+
+    #[test]
+    fn test_upgrade_chain_config_v4_resolvable_chain_config_from_v1() {
+        let expectation: ResolvableChainConfig = ChainConfig::default().into();
+        let v1_resolvable: v0_1::ResolvableChainConfig = v0_1::ChainConfig::default().into();
+        let v4_resolvable: ResolvableChainConfig = ResolvableChainConfig::from(&v1_resolvable);
+        assert_eq!(expectation, v4_resolvable);
+        let expectation: ResolvableChainConfig = ChainConfig::default().commit().into();
+        let v1_resolvable: v0_1::ResolvableChainConfig =
+            v0_1::ChainConfig::default().commit().into();
+        let v4_resolvable: ResolvableChainConfig = ResolvableChainConfig::from(&v1_resolvable);
+        assert_eq!(expectation, v4_resolvable);
+    }
+
+    #[test]
+    fn test_upgrade_chain_config_v4_resolvable_chain_config_from_v99() {
+        let v99_chain_config = v0_99::ChainConfig {
+            bid_recipient: Some(Default::default()),
+            ..Default::default()
+        };
+        let v4_chain_config = ChainConfig::from(v99_chain_config);
+        assert_eq!(
+            v4_chain_config.bid_recipient,
+            v99_chain_config.bid_recipient
+        );
+
+        // Without namespace fee multipliers, the commitment is unchanged.
+        assert_eq!(
+            v4_chain_config.commit().as_ref(),
+            v99_chain_config.commit().as_ref()
+        );
+        let v99_resolvable: v0_99::ResolvableChainConfig = v99_chain_config.commit().into();
+        assert_eq!(
+            ResolvableChainConfig::from(&v99_resolvable).commit(),
+            v4_chain_config.commit()
+        );
+        assert_eq!(
+            v0_99::ChainConfig::try_from(v4_chain_config).unwrap(),
+            v99_chain_config
+        );
+    }
+
+    #[test]
+    fn test_downgrade_chain_config_v99_with_ns_fee_multipliers() {
+        let empty = NsFeeMultipliers::try_from(Vec::<NsFeeMultiplier>::new()).unwrap();
+        let chain_config = ChainConfig {
+            ns_fee_multipliers: Some(empty),
+            ..Default::default()
+        };
+        assert_eq!(
+            v0_99::ChainConfig::try_from(chain_config).unwrap(),
+            v0_99::ChainConfig::default()
+        );
+
+        let table = NsFeeMultipliers::try_from(vec![NsFeeMultiplier {
+            namespace: 1u64.into(),
+            multiplier: 2 * NsFeeMultipliers::UNIT,
+        }])
+        .unwrap();
+        let chain_config = ChainConfig {
+            ns_fee_multipliers: Some(table),
+            ..Default::default()
+        };
+        v0_99::ChainConfig::try_from(chain_config).unwrap_err();
+    }
+
+    #[test]
+    fn test_upgrade_chain_config_v1_chain_config_from_v4() {
+        let expectation = v0_1::ChainConfig::default();
+        let v4_chain_config = ChainConfig::default();
+        let v1_chain_config = v0_1::ChainConfig::from(v4_chain_config);
+        assert_eq!(expectation, v1_chain_config);
+    }
+
+    #[test]
+    fn test_ns_fee_multipliers() {
+        let multiplier = |ns: u64, multiplier| NsFeeMultiplier {
+            namespace: ns.into(),
+            multiplier,
+        };
+
+        // Entries are sorted, so insertion order does not affect the table or its commitment.
+        let a =
+            NsFeeMultipliers::try_from(vec![multiplier(2, 5_000), multiplier(1, 20_000)]).unwrap();
+        let b =
+            NsFeeMultipliers::try_from(vec![multiplier(1, 20_000), multiplier(2, 5_000)]).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.commit(), b.commit());
+        assert_eq!(a.get(1u64.into()), 20_000);
+        assert_eq!(a.get(2u64.into()), 5_000);
+        assert_eq!(a.get(3u64.into()), NsFeeMultipliers::UNIT);
+
+        // Serialization round trip.
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(a, serde_json::from_str(&json).unwrap());
+
+        // Duplicate namespaces and oversized tables are rejected.
+        NsFeeMultipliers::try_from(vec![multiplier(1, 1), multiplier(1, 2)]).unwrap_err();
+        NsFeeMultipliers::try_from(
+            (0..=MAX_NS_FEE_MULTIPLIERS as u64)
+                .map(|ns| multiplier(ns, 1))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap_err();
+
+        // The table is only committed if present.
+        let cf = ChainConfig::default();
+        let with_multipliers = ChainConfig {
+            ns_fee_multipliers: Some(a),
+            ..cf
+        };
+        assert_ne!(cf.commit(), with_multipliers.commit());
+    }
+
+    #[test]
+    fn test_block_fee() {
+        let mut builder = NsTableBuilder::new();
+        builder.append_entry(1u64.into(), 100);
+        builder.append_entry(2u64.into(), 300);
+        builder.append_entry(3u64.into(), 400);
+        let ns_table = builder.into_ns_table();
+        let base_fee = FeeAmount::from(10u64);
+
+        // Without multipliers every byte pays the base fee.
+        let cf = ChainConfig::default();
+        assert_eq!(
+            cf.block_fee(base_fee, &ns_table, 400),
+            FeeAmount::from(4_000u64)
+        );
+        assert_eq!(cf.max_fee_per_byte(base_fee), base_fee);
+
+        // Namespace 1 pays double, namespace 2 pays half and namespace 3 is not listed.
+        let cf = ChainConfig {
+            ns_fee_multipliers: Some(
+                vec![
+                    NsFeeMultiplier {
+                        namespace: 1u64.into(),
+                        multiplier: 20_000,
+                    },
+                    NsFeeMultiplier {
+                        namespace: 2u64.into(),
+                        multiplier: 5_000,
+                    },
+                ]
+                .try_into()
+                .unwrap(),
+            ),
+            ..cf
+        };
+        assert_eq!(
+            cf.block_fee(base_fee, &ns_table, 400),
+            FeeAmount::from(100 * 20 + 200 * 5 + 100 * 10u64)
+        );
+
+        // Paying the fee of the most expensive namespace for every byte covers the block fee.
+        assert_eq!(cf.max_fee_per_byte(base_fee), FeeAmount::from(20u64));
+        assert!(cf.max_fee_per_byte(base_fee) * 400u32 >= cf.block_fee(base_fee, &ns_table, 400));
+    }
+}
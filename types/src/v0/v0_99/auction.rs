@@ -1,4 +1,5 @@
-use crate::{FeeAccount, FeeAmount, NamespaceId};
+use super::TransferTx;
+use crate::{ChainId, FeeAccount, FeeAmount, NamespaceId};
 use ethers::types::Signature;
 use hotshot_types::data::ViewNumber;
 use serde::{Deserialize, Serialize};
@@ -9,6 +10,7 @@ use url::Url;
 /// will be a variant of this enum.
 pub enum FullNetworkTx {
     Bid(BidTx),
+    Transfer(TransferTx),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, Hash)]
@@ -50,3 +52,18 @@ pub struct SolverAuctionResults {
     /// A list of reserve sequencers being used
     pub(crate) reserve_bids: Vec<(NamespaceId, Url)>,
 }
+
+/// The request a stake table node signs to fetch auction results from a solver before they are
+/// final.
+///
+/// The chain and the solver are part of the signed request, so that a signature for one solver
+/// cannot be replayed against another.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, Hash)]
+pub struct AuctionResultsRequest {
+    /// The chain the auction is for
+    pub(crate) chain_id: ChainId,
+    /// The base URL of the solver the request is addressed to
+    pub(crate) solver_url: Url,
+    /// view number the results are requested for
+    pub(crate) view_number: ViewNumber,
+}
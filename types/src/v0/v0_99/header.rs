@@ -1,6 +1,6 @@
 use super::{
-    BlockMerkleCommitment, BuilderSignature, FeeInfo, FeeMerkleCommitment, L1BlockInfo,
-    ResolvableChainConfig, SolverAuctionResults,
+    BlockMerkleCommitment, BuilderSignature, FeeAmount, FeeInfo, FeeMerkleCommitment,
+    FullNetworkTx, L1BlockInfo, ResolvableChainConfig, SolverAuctionResults,
 };
 use crate::NsTable;
 use ark_serialize::CanonicalSerialize;
@@ -25,6 +25,12 @@ pub struct Header {
     pub(crate) fee_info: Vec<FeeInfo>,
     pub(crate) builder_signature: Vec<BuilderSignature>,
     pub(crate) auction_results: SolverAuctionResults,
+    /// Full network transactions executed in this block, in order.
+    pub(crate) full_network_txs: Vec<FullNetworkTx>,
+    /// The dynamic base fee of this block, computed from the size of the parent block.
+    ///
+    /// This is `None` only for genesis headers, which have no parent.
+    pub(crate) base_fee: Option<FeeAmount>,
 }
 
 impl Committable for Header {
@@ -38,7 +44,7 @@ impl Committable for Header {
             .serialize_with_mode(&mut fmt_bytes, ark_serialize::Compress::Yes)
             .unwrap();
 
-        RawCommitmentBuilder::new(&Self::tag())
+        let comm = RawCommitmentBuilder::new(&Self::tag())
             .field("chain_config", self.chain_config.commit())
             .u64_field("height", self.height)
             .u64_field("timestamp", self.timestamp)
@@ -59,8 +65,29 @@ impl Committable for Header {
                     .map(Committable::commit)
                     .collect::<Vec<_>>(),
             )
-            .field("auction_results", self.auction_results.commit())
-            .finalize()
+            .field("auction_results", self.auction_results.commit());
+
+        // Only commit to full network transactions when there are some, so that the commitment of
+        // a header without any is unchanged.
+        let comm = if self.full_network_txs.is_empty() {
+            comm
+        } else {
+            comm.array_field(
+                "full_network_txs",
+                &self
+                    .full_network_txs
+                    .iter()
+                    .map(Committable::commit)
+                    .collect::<Vec<_>>(),
+            )
+        };
+        // Likewise, a header without a base fee commits as it did before the field was added.
+        let comm = if let Some(base_fee) = self.base_fee {
+            comm.fixed_size_field("base_fee", &base_fee.to_fixed_bytes())
+        } else {
+            comm
+        };
+        comm.finalize()
     }
 
     fn tag() -> String {
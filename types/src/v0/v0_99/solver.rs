@@ -55,4 +55,41 @@ pub struct RollupUpdatebody {
     pub signature_key: <SeqTypes as NodeType>::SignatureKey,
     // Optional field for human readable information
     pub text: Update<String>,
+    // Must be greater than the nonce of the last accepted update for this namespace
+    pub nonce: u64,
+}
+
+/// A change accepted by the solver for a rollup registration
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub enum RollupRegistrationChange {
+    Register(RollupRegistration),
+    Update(RollupUpdate),
+}
+
+/// An entry in the change log of a rollup registration
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct RollupRegistrationHistoryEntry {
+    // the signed registration or update that was accepted
+    pub change: RollupRegistrationChange,
+    // the registration after the change was applied
+    pub registration: RollupRegistration,
+}
+
+/// Changes to rollup registrations accepted by the solver after a given point in its change log
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct RollupRegistrationChanges {
+    // the changes, oldest first
+    pub changes: Vec<RollupRegistrationHistoryEntry>,
+    // position in the change log to request further changes from
+    pub cursor: u64,
+}
+
+/// The current rollup registrations, along with the position in the solver's change log they are
+/// up to date with
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct RollupRegistrationsSnapshot {
+    // the current registrations
+    pub registrations: Vec<RollupRegistration>,
+    // position in the change log to request further changes from
+    pub cursor: u64,
 }
@@ -0,0 +1,25 @@
+use crate::{FeeAccount, FeeAmount};
+use ethers::types::Signature;
+use hotshot_types::data::ViewNumber;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, Hash)]
+/// A transaction to move balance from one fee account to another. It
+/// is the `signed` form of `TransferTxBody`.
+pub struct TransferTx {
+    pub(crate) body: TransferTxBody,
+    pub(crate) signature: Signature,
+}
+
+/// A transaction body holding data required for a transfer.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, Hash)]
+pub struct TransferTxBody {
+    /// Account to debit, responsible for the signature
+    pub(crate) account: FeeAccount,
+    /// Account to credit
+    pub(crate) recipient: FeeAccount,
+    /// The amount to transfer, designated in Wei
+    pub(crate) amount: FeeAmount,
+    /// The only view in which this transfer can be executed
+    pub(crate) view: ViewNumber,
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Deserializer};
+
+use crate::{NsTable, VidCommitment};
+
+/// The parts of a block header needed to verify proofs against it.
+///
+/// Deserializes from the JSON serialization of any version of `espresso_types::Header`, ignoring
+/// the fields not needed here.
+#[derive(Clone, Debug)]
+pub struct Header {
+    fields: Fields,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct Fields {
+    height: u64,
+    payload_commitment: VidCommitment,
+    ns_table: NsTable,
+}
+
+impl<'de> Deserialize<'de> for Header {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Version 0.1 headers serialize their fields directly; later versions wrap them along
+        // with the version number.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Versioned {
+            Versioned { fields: Fields },
+            V1(Fields),
+        }
+
+        let (Versioned::Versioned { fields } | Versioned::V1(fields)) =
+            Versioned::deserialize(deserializer)?;
+        Ok(Self { fields })
+    }
+}
+
+impl Header {
+    pub fn height(&self) -> u64 {
+        self.fields.height
+    }
+
+    pub fn payload_commitment(&self) -> &VidCommitment {
+        &self.fields.payload_commitment
+    }
+
+    pub fn ns_table(&self) -> &NsTable {
+        &self.fields.ns_table
+    }
+}
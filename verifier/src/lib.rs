@@ -0,0 +1,27 @@
+//! Verification of Espresso namespace and transaction proofs, without the sequencer.
+//!
+//! This crate verifies the [`NsProof`] and [`TxProof`] served by the sequencer API against a block
+//! [`Header`], using only the namespace table format and VID payload verification. It has no
+//! `tokio`, `ethers` or `sqlx` dependencies, so it builds for `wasm32-unknown-unknown`, where the
+//! [`wasm`] module exposes it to JavaScript.
+//!
+//! The types here mirror their counterparts in `espresso-types` and share their serialization, so
+//! proofs fetched from the API deserialize directly into them. Any change to the binary formats
+//! in `espresso-types` must be reflected here; the `compat` tests check that the two agree.
+
+mod header;
+mod ns_payload;
+mod ns_proof;
+mod ns_table;
+mod transaction;
+mod tx_proof;
+mod uint_bytes;
+mod vid;
+pub mod wasm;
+
+pub use header::Header;
+pub use ns_proof::NsProof;
+pub use ns_table::{NsIndex, NsTable};
+pub use transaction::{NamespaceId, Transaction};
+pub use tx_proof::{TxIndex, TxProof};
+pub use vid::{Srs, Verifier, VidCommitment, VidCommon};
@@ -0,0 +1,81 @@
+//! Parsing of a namespace payload, as in `espresso_types::NsPayload`.
+//!
+//! A namespace payload is a tx table followed by the transaction bodies. The tx table is a 4-byte
+//! little-endian number of transactions, followed by that many 4-byte offsets of the end of each
+//! transaction, relative to the end of the tx table.
+
+use std::ops::Range;
+
+use crate::{
+    uint_bytes::{usize_from_bytes, NUM_TXS_BYTE_LEN, TX_OFFSET_BYTE_LEN},
+    NamespaceId, Transaction,
+};
+
+/// Byte range of the number of transactions declared in a namespace of length `byte_len`.
+pub(crate) fn num_txs_range(byte_len: usize) -> Range<usize> {
+    0..NUM_TXS_BYTE_LEN.min(byte_len)
+}
+
+/// The number of transactions in a namespace of length `byte_len`: the number declared in its tx
+/// table, capped by the number of tx table entries that could fit in the namespace.
+pub(crate) fn num_txs(num_txs_unchecked: usize, byte_len: usize) -> usize {
+    num_txs_unchecked.min(byte_len.saturating_sub(NUM_TXS_BYTE_LEN) / TX_OFFSET_BYTE_LEN)
+}
+
+/// Byte range of the tx table entries needed to locate the `index`th transaction: its own entry
+/// and, unless it is the first transaction, the entry before it.
+pub(crate) fn tx_table_entries_range(index: usize) -> Range<usize> {
+    let start = if index == 0 {
+        NUM_TXS_BYTE_LEN
+    } else {
+        (index - 1)
+            .saturating_mul(TX_OFFSET_BYTE_LEN)
+            .saturating_add(NUM_TXS_BYTE_LEN)
+    };
+    let end = index
+        .saturating_add(1)
+        .saturating_mul(TX_OFFSET_BYTE_LEN)
+        .saturating_add(NUM_TXS_BYTE_LEN);
+    start..end
+}
+
+/// Parse tx table entries read from [`tx_table_entries_range`] into `(prev, cur)` offsets.
+///
+/// Returns `None` if `bytes` holds neither one nor two entries.
+pub(crate) fn parse_tx_table_entries(bytes: &[u8]) -> Option<(Option<usize>, usize)> {
+    match bytes.len() {
+        TX_OFFSET_BYTE_LEN => Some((None, usize_from_bytes(bytes))),
+        len if len == 2 * TX_OFFSET_BYTE_LEN => Some((
+            Some(usize_from_bytes(&bytes[..TX_OFFSET_BYTE_LEN])),
+            usize_from_bytes(&bytes[TX_OFFSET_BYTE_LEN..]),
+        )),
+        _ => None,
+    }
+}
+
+/// Byte range of a transaction's payload within a namespace of length `byte_len`.
+pub(crate) fn tx_payload_range(
+    num_txs_unchecked: usize,
+    (prev, cur): (Option<usize>, usize),
+    byte_len: usize,
+) -> Range<usize> {
+    let tx_table_byte_len = num_txs_unchecked
+        .saturating_mul(TX_OFFSET_BYTE_LEN)
+        .saturating_add(NUM_TXS_BYTE_LEN);
+    let end = cur.saturating_add(tx_table_byte_len).min(byte_len);
+    let start = prev.unwrap_or(0).saturating_add(tx_table_byte_len).min(end);
+    start..end
+}
+
+/// Return all transactions in the namespace payload `bytes`, with namespace ID `ns_id`.
+pub(crate) fn export_all_txs(bytes: &[u8], ns_id: NamespaceId) -> Vec<Transaction> {
+    let num_txs_unchecked = usize_from_bytes(&bytes[num_txs_range(bytes.len())]);
+    (0..num_txs(num_txs_unchecked, bytes.len()))
+        .map(|index| {
+            let entries = parse_tx_table_entries(&bytes[tx_table_entries_range(index)])
+                .expect("entries range holds one or two entries");
+            let range = tx_payload_range(num_txs_unchecked, entries, bytes.len());
+            Transaction::new(ns_id, bytes[range].to_vec())
+        })
+        .collect()
+}
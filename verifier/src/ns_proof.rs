@@ -0,0 +1,63 @@
+use jf_vid::payload_prover::{PayloadProver, Statement};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ns_payload::export_all_txs,
+    vid::{LargeRangeProofType, Verifier},
+    NamespaceId, NsIndex, NsTable, Transaction, VidCommitment, VidCommon,
+};
+
+/// Proof of correctness for namespace payload bytes in a block, as in `espresso_types::NsProof`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NsProof {
+    pub(crate) ns_index: NsIndex,
+    #[serde(with = "base64_bytes")]
+    pub(crate) ns_payload: Vec<u8>,
+    pub(crate) ns_proof: Option<LargeRangeProofType>, // `None` if ns_payload is empty
+}
+
+impl NsProof {
+    /// Verify a [`NsProof`] against a payload commitment. Returns `None` on error or if
+    /// verification fails.
+    ///
+    /// If verification is successful then return the transactions in the namespace, along with
+    /// its [`NamespaceId`].
+    pub fn verify(
+        &self,
+        verifier: &Verifier,
+        ns_table: &NsTable,
+        commit: &VidCommitment,
+        common: &VidCommon,
+    ) -> Option<(Vec<Transaction>, NamespaceId)> {
+        if !ns_table.is_valid() || !Verifier::is_consistent(commit, common) {
+            return None;
+        }
+        let ns_id = ns_table.read_ns_id(&self.ns_index)?; // error: index out of bounds
+        let range = ns_table.ns_range(&self.ns_index, Verifier::payload_byte_len(common)?);
+        if self.ns_payload.len() != range.len() {
+            return None; // error: ns payload inconsistent with ns table
+        }
+
+        match (&self.ns_proof, range.is_empty()) {
+            (Some(proof), false) => {
+                verifier
+                    .vid(common)?
+                    .payload_verify(
+                        Statement {
+                            payload_subslice: &self.ns_payload,
+                            range,
+                            commit,
+                            common,
+                        },
+                        proof,
+                    )
+                    .ok()? // error: internal to payload_verify()
+                    .ok()?; // verification failure
+            }
+            (None, true) => {} // 0-length namespace, nothing to verify
+            _ => return None,  // error: proof inconsistent with ns payload range
+        }
+
+        Some((export_all_txs(&self.ns_payload, ns_id), ns_id))
+    }
+}
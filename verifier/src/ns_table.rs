@@ -0,0 +1,129 @@
+//! The namespace table of a block, as in `espresso_types::NsTable`.
+//!
+//! A namespace table is a 4-byte little-endian number of entries, followed by that many entries,
+//! each of which is a 4-byte namespace ID and the 4-byte offset of the end of that namespace's
+//! payload.
+
+use std::{collections::HashSet, ops::Range};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    uint_bytes::{
+        u32_from_bytes, usize_from_bytes, NS_ID_BYTE_LEN, NS_OFFSET_BYTE_LEN, NUM_NSS_BYTE_LEN,
+    },
+    NamespaceId,
+};
+
+/// Raw binary data for a namespace table.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NsTable {
+    #[serde(with = "base64_bytes")]
+    bytes: Vec<u8>,
+}
+
+/// Index for an entry in a namespace table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NsIndex(pub(crate) usize);
+
+impl Serialize for NsIndex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0 as u32).to_le_bytes().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NsIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <[u8; NUM_NSS_BYTE_LEN]>::deserialize(deserializer)
+            .map(|bytes| Self(usize_from_bytes(&bytes)))
+    }
+}
+
+impl NsTable {
+    /// Instantiate an `NsTable` from a byte slice.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    /// Number of entries in the namespace table.
+    ///
+    /// Defined as the maximum number of entries that could fit in the namespace table, ignoring
+    /// what's declared in the table header.
+    pub fn len(&self) -> usize {
+        self.bytes.len().saturating_sub(NUM_NSS_BYTE_LEN) / (NS_ID_BYTE_LEN + NS_OFFSET_BYTE_LEN)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Does the `index`th entry exist in the namespace table?
+    pub fn in_bounds(&self, index: &NsIndex) -> bool {
+        index.0 < self.len()
+    }
+
+    /// Search the namespace table for the index belonging to `ns_id`.
+    pub fn find_ns_id(&self, ns_id: &NamespaceId) -> Option<NsIndex> {
+        (0..self.len())
+            .map(NsIndex)
+            .find(|index| self.read_ns_id_unchecked(index) == *ns_id)
+    }
+
+    /// Read the namespace ID from the `index`th entry of the namespace table.
+    pub fn read_ns_id(&self, index: &NsIndex) -> Option<NamespaceId> {
+        self.in_bounds(index)
+            .then(|| self.read_ns_id_unchecked(index))
+    }
+
+    /// Are the bytes of this namespace table uncorrupted?
+    ///
+    /// Checks the conditions `espresso_types` enforces when deserializing a namespace table: the
+    /// byte length holds a whole number of entries, the header declares that number of entries,
+    /// offsets are nonzero and increase monotonically and namespace IDs are unique.
+    pub fn is_valid(&self) -> bool {
+        if self.bytes.len() < NUM_NSS_BYTE_LEN
+            || (self.bytes.len() - NUM_NSS_BYTE_LEN) % (NS_ID_BYTE_LEN + NS_OFFSET_BYTE_LEN) != 0
+        {
+            return false;
+        }
+        if usize_from_bytes(&self.bytes[..NUM_NSS_BYTE_LEN]) != self.len() {
+            return false;
+        }
+
+        let mut prev_offset = 0;
+        let mut ns_ids = HashSet::new();
+        for index in (0..self.len()).map(NsIndex) {
+            let offset = self.read_ns_offset_unchecked(&index);
+            if !ns_ids.insert(self.read_ns_id_unchecked(&index)) || offset <= prev_offset {
+                return false;
+            }
+            prev_offset = offset;
+        }
+        true
+    }
+
+    /// Byte range of the `index`th namespace within a payload of length `payload_byte_len`.
+    pub(crate) fn ns_range(&self, index: &NsIndex, payload_byte_len: usize) -> Range<usize> {
+        let end = self.read_ns_offset_unchecked(index).min(payload_byte_len);
+        let start = if index.0 == 0 {
+            0
+        } else {
+            self.read_ns_offset_unchecked(&NsIndex(index.0 - 1))
+        }
+        .min(end);
+        start..end
+    }
+
+    fn read_ns_id_unchecked(&self, index: &NsIndex) -> NamespaceId {
+        let start = index.0 * (NS_ID_BYTE_LEN + NS_OFFSET_BYTE_LEN) + NUM_NSS_BYTE_LEN;
+        NamespaceId(u32_from_bytes(&self.bytes[start..start + NS_ID_BYTE_LEN]).into())
+    }
+
+    fn read_ns_offset_unchecked(&self, index: &NsIndex) -> usize {
+        let start =
+            index.0 * (NS_ID_BYTE_LEN + NS_OFFSET_BYTE_LEN) + NUM_NSS_BYTE_LEN + NS_ID_BYTE_LEN;
+        usize_from_bytes(&self.bytes[start..start + NS_OFFSET_BYTE_LEN])
+    }
+}
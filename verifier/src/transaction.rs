@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Identifier of a namespace, as in `espresso_types::NamespaceId`.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub struct NamespaceId(pub u64);
+
+/// A transaction, as in `espresso_types::Transaction`.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Transaction {
+    namespace: NamespaceId,
+    #[serde(with = "base64_bytes")]
+    payload: Vec<u8>,
+}
+
+impl Transaction {
+    pub fn new(namespace: NamespaceId, payload: Vec<u8>) -> Self {
+        Self { namespace, payload }
+    }
+
+    pub fn namespace(&self) -> NamespaceId {
+        self.namespace
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
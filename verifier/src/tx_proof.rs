@@ -0,0 +1,145 @@
+use jf_vid::payload_prover::{PayloadProver, Statement};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    ns_payload::{
+        num_txs, num_txs_range, parse_tx_table_entries, tx_payload_range, tx_table_entries_range,
+    },
+    uint_bytes::{usize_from_bytes, NUM_TXS_BYTE_LEN},
+    vid::{SmallRangeProofType, Verifier},
+    NsTable, Transaction, VidCommitment, VidCommon,
+};
+
+/// Index for an entry in a tx table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TxIndex(pub(crate) usize);
+
+impl Serialize for TxIndex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0 as u32).to_le_bytes().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <[u8; NUM_TXS_BYTE_LEN]>::deserialize(deserializer)
+            .map(|bytes| Self(usize_from_bytes(&bytes)))
+    }
+}
+
+/// Proof of correctness for transaction bytes in a block, as in `espresso_types::TxProof`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TxProof {
+    pub(crate) tx_index: TxIndex,
+
+    // Number of txs declared in the tx table
+    pub(crate) payload_num_txs: [u8; NUM_TXS_BYTE_LEN],
+    pub(crate) payload_proof_num_txs: SmallRangeProofType,
+
+    // Tx table entries for this tx
+    pub(crate) payload_tx_table_entries: Vec<u8>,
+    pub(crate) payload_proof_tx_table_entries: SmallRangeProofType,
+
+    // This tx's payload bytes.
+    // `None` if this tx has zero length.
+    pub(crate) payload_proof_tx: Option<SmallRangeProofType>,
+}
+
+impl TxProof {
+    /// Verify a [`TxProof`] for `tx` against a payload commitment. Returns `None` on error.
+    pub fn verify(
+        &self,
+        verifier: &Verifier,
+        ns_table: &NsTable,
+        tx: &Transaction,
+        commit: &VidCommitment,
+        common: &VidCommon,
+    ) -> Option<bool> {
+        if !ns_table.is_valid() || !Verifier::is_consistent(commit, common) {
+            return None;
+        }
+        let ns_index = ns_table.find_ns_id(&tx.namespace())?; // error: ns id does not exist
+        let ns_range = ns_table.ns_range(&ns_index, Verifier::payload_byte_len(common)?);
+        let ns_byte_len = ns_range.len();
+        let block_range = |range: std::ops::Range<usize>| {
+            range.start + ns_range.start..range.end + ns_range.start
+        };
+
+        let num_txs_unchecked = usize_from_bytes(&self.payload_num_txs);
+        if self.tx_index.0 >= num_txs(num_txs_unchecked, ns_byte_len) {
+            return None; // error: tx index out of bounds
+        }
+        let tx_table_entries = parse_tx_table_entries(&self.payload_tx_table_entries)?;
+
+        let vid = verifier.vid(common)?;
+
+        // Verify proof for tx table len
+        if vid
+            .payload_verify(
+                Statement {
+                    payload_subslice: &self.payload_num_txs,
+                    range: block_range(num_txs_range(ns_byte_len)),
+                    commit,
+                    common,
+                },
+                &self.payload_proof_num_txs,
+            )
+            .ok()?
+            .is_err()
+        {
+            return Some(false);
+        }
+
+        // Verify proof for tx table entries
+        if vid
+            .payload_verify(
+                Statement {
+                    payload_subslice: &self.payload_tx_table_entries,
+                    range: block_range(tx_table_entries_range(self.tx_index.0)),
+                    commit,
+                    common,
+                },
+                &self.payload_proof_tx_table_entries,
+            )
+            .ok()?
+            .is_err()
+        {
+            return Some(false);
+        }
+
+        // Verify proof for tx payload
+        let range = block_range(tx_payload_range(
+            num_txs_unchecked,
+            tx_table_entries,
+            ns_byte_len,
+        ));
+        match (&self.payload_proof_tx, range.is_empty()) {
+            (Some(proof), false) => {
+                if vid
+                    .payload_verify(
+                        Statement {
+                            payload_subslice: tx.payload(),
+                            range,
+                            commit,
+                            common,
+                        },
+                        proof,
+                    )
+                    .ok()?
+                    .is_err()
+                {
+                    return Some(false);
+                }
+            }
+            (None, true) => {
+                // 0-length tx, nothing to verify
+                if !tx.payload().is_empty() {
+                    return Some(false);
+                }
+            }
+            _ => return None, // error: proof inconsistent with tx payload range
+        }
+
+        Some(true)
+    }
+}
@@ -0,0 +1,25 @@
+//! Little-endian encoding of the unsigned integers in a block payload.
+//!
+//! Mirrors `espresso_types::v0::impls::block::uint_bytes` for the 4-byte integers used by the
+//! namespace table and tx table.
+
+/// Byte lengths for the different items that could appear in a namespace table.
+pub(crate) const NUM_NSS_BYTE_LEN: usize = 4;
+pub(crate) const NS_OFFSET_BYTE_LEN: usize = 4;
+pub(crate) const NS_ID_BYTE_LEN: usize = 4;
+
+/// Byte lengths for the different items that could appear in a tx table.
+pub(crate) const NUM_TXS_BYTE_LEN: usize = 4;
+pub(crate) const TX_OFFSET_BYTE_LEN: usize = 4;
+
+/// Deserialize at most 4 `bytes` in little-endian form, padding with 0 as needed.
+pub(crate) fn u32_from_bytes(bytes: &[u8]) -> u32 {
+    let mut buf = [0; 4];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    u32::from_le_bytes(buf)
+}
+
+/// Like [`u32_from_bytes`], but as a `usize`.
+pub(crate) fn usize_from_bytes(bytes: &[u8]) -> usize {
+    u32_from_bytes(bytes) as usize
+}
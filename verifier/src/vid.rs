@@ -0,0 +1,76 @@
+//! VID payload verification, as configured by `hotshot_types::vid`.
+
+use ark_bn254::Bn254;
+use ark_serialize::{CanonicalDeserialize, SerializationError};
+use jf_pcs::{
+    prelude::{UnivariateKzgPCS, UnivariateUniversalParams},
+    PolynomialCommitmentScheme,
+};
+use jf_vid::{
+    advz::{
+        self,
+        payload_prover::{LargeRangeProof, SmallRangeProof},
+    },
+    VidScheme,
+};
+use sha2::Sha256;
+
+type E = Bn254;
+type H = Sha256;
+pub(crate) type Advz = advz::Advz<E, H>;
+
+/// Structured reference string for the KZG commitments used by VID.
+///
+/// This must be (a prefix of) the SRS the network uses, which is the Aztec ceremony SRS loaded by
+/// `hotshot_types::vid`.
+pub type Srs = UnivariateUniversalParams<E>;
+
+/// Commitment to a block payload, as in `hotshot_types::vid::VidCommitment`.
+pub type VidCommitment = <Advz as VidScheme>::Commit;
+
+/// Data common to all VID shares of a payload, as in `hotshot_types::vid::VidCommon`.
+pub type VidCommon = <Advz as VidScheme>::Common;
+
+pub(crate) type LargeRangeProofType =
+    LargeRangeProof<<UnivariateKzgPCS<E> as PolynomialCommitmentScheme>::Evaluation>;
+pub(crate) type SmallRangeProofType =
+    SmallRangeProof<<UnivariateKzgPCS<E> as PolynomialCommitmentScheme>::Proof>;
+
+/// Verifies proofs of payload data against a VID commitment.
+#[derive(Clone, Debug)]
+pub struct Verifier {
+    srs: Srs,
+}
+
+impl Verifier {
+    pub fn new(srs: Srs) -> Self {
+        Self { srs }
+    }
+
+    /// Load the SRS from its uncompressed canonical serialization.
+    pub fn from_srs_bytes(bytes: &[u8]) -> Result<Self, SerializationError> {
+        Ok(Self::new(Srs::deserialize_uncompressed(bytes)?))
+    }
+
+    /// Instantiate the VID scheme for the number of storage nodes in `common`, as
+    /// `hotshot_types::vid::vid_scheme` does. Returns `None` on error.
+    pub(crate) fn vid(&self, common: &VidCommon) -> Option<Advz> {
+        let num_storage_nodes = Advz::get_num_storage_nodes(common);
+        if num_storage_nodes == 0 {
+            return None; // error: no storage nodes
+        }
+        // The recovery threshold is the number of storage nodes rounded down to a power of two.
+        let recovery_threshold = 1 << num_storage_nodes.ilog2();
+        Advz::new(num_storage_nodes, recovery_threshold, &self.srs).ok()
+    }
+
+    /// Byte length of the payload that `common` belongs to. Returns `None` on error.
+    pub(crate) fn payload_byte_len(common: &VidCommon) -> Option<usize> {
+        Advz::get_payload_byte_len(common).try_into().ok()
+    }
+
+    /// Is `common` consistent with `commit`?
+    pub(crate) fn is_consistent(commit: &VidCommitment, common: &VidCommon) -> bool {
+        Advz::is_consistent(commit, common).is_ok()
+    }
+}
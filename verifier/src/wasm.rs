@@ -0,0 +1,435 @@
+//! Verification of proofs in their JSON serialization, and bindings exposing it to JavaScript.
+//!
+//! The functions here take the JSON served by the sequencer API: a block header from
+//! `availability/header/:height`, VID common data from `availability/vid/common/:height`, and the
+//! proofs from `availability/block/:height/namespace/:namespace` and
+//! `availability/transaction/hash/:hash/proof`.
+
+use ark_serialize::SerializationError;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::{Header, NamespaceId, NsProof, Transaction, TxProof, Verifier, VidCommon};
+
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("malformed {what}: {source}")]
+    Malformed {
+        what: &'static str,
+        source: serde_json::Error,
+    },
+    #[error("malformed SRS: {0}")]
+    Srs(#[from] SerializationError),
+    #[error("proof verification failed")]
+    VerificationFailed,
+    #[error("requested namespace {requested}, got proof for namespace {proven}")]
+    WrongNamespace { requested: u64, proven: u64 },
+}
+
+fn parse<T: DeserializeOwned>(what: &'static str, json: &str) -> Result<T, VerifyError> {
+    serde_json::from_str(json).map_err(|source| VerifyError::Malformed { what, source })
+}
+
+/// Verify a proof of namespace `ns_id` against a block header, returning the transactions in the
+/// namespace.
+pub fn verify_namespace(
+    verifier: &Verifier,
+    header: &str,
+    ns_id: NamespaceId,
+    proof: &str,
+    vid_common: &str,
+) -> Result<Vec<Transaction>, VerifyError> {
+    let header: Header = parse("header", header)?;
+    let proof: NsProof = parse("namespace proof", proof)?;
+    let vid_common: VidCommon = parse("VID common", vid_common)?;
+
+    let (transactions, proven) = proof
+        .verify(
+            verifier,
+            header.ns_table(),
+            header.payload_commitment(),
+            &vid_common,
+        )
+        .ok_or(VerifyError::VerificationFailed)?;
+    if proven != ns_id {
+        return Err(VerifyError::WrongNamespace {
+            requested: ns_id.0,
+            proven: proven.0,
+        });
+    }
+    Ok(transactions)
+}
+
+/// Verify that `transaction` is included in the block with the given header.
+pub fn verify_transaction(
+    verifier: &Verifier,
+    header: &str,
+    transaction: &str,
+    proof: &str,
+    vid_common: &str,
+) -> Result<(), VerifyError> {
+    let header: Header = parse("header", header)?;
+    let transaction: Transaction = parse("transaction", transaction)?;
+    let proof: TxProof = parse("transaction proof", proof)?;
+    let vid_common: VidCommon = parse("VID common", vid_common)?;
+
+    match proof.verify(
+        verifier,
+        header.ns_table(),
+        &transaction,
+        header.payload_commitment(),
+        &vid_common,
+    ) {
+        Some(true) => Ok(()),
+        _ => Err(VerifyError::VerificationFailed),
+    }
+}
+
+/// A proof verifier for use from JavaScript.
+#[wasm_bindgen(js_name = Verifier)]
+pub struct JsVerifier(Verifier);
+
+#[wasm_bindgen(js_class = Verifier)]
+impl JsVerifier {
+    /// Create a verifier from the uncompressed canonical serialization of the KZG SRS.
+    #[wasm_bindgen(constructor)]
+    pub fn new(srs: &[u8]) -> Result<JsVerifier, JsError> {
+        Ok(Self(
+            Verifier::from_srs_bytes(srs).map_err(VerifyError::from)?,
+        ))
+    }
+
+    /// Verify a proof of namespace `ns_id`, returning the JSON-serialized transactions in the
+    /// namespace.
+    #[wasm_bindgen(js_name = verifyNamespace)]
+    pub fn verify_namespace(
+        &self,
+        header: &str,
+        ns_id: u32,
+        proof: &str,
+        vid_common: &str,
+    ) -> Result<String, JsError> {
+        let transactions = verify_namespace(
+            &self.0,
+            header,
+            NamespaceId(ns_id.into()),
+            proof,
+            vid_common,
+        )?;
+        Ok(serde_json::to_string(&transactions)?)
+    }
+
+    /// Verify that a transaction is included in a block, throwing if it is not.
+    #[wasm_bindgen(js_name = verifyTransaction)]
+    pub fn verify_transaction(
+        &self,
+        header: &str,
+        transaction: &str,
+        proof: &str,
+        vid_common: &str,
+    ) -> Result<(), JsError> {
+        Ok(verify_transaction(
+            &self.0,
+            header,
+            transaction,
+            proof,
+            vid_common,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::Range;
+
+    use ark_bn254::Bn254;
+    use ark_serialize::CanonicalSerialize;
+    use jf_pcs::{prelude::UnivariateKzgPCS, PolynomialCommitmentScheme};
+    use jf_vid::{payload_prover::PayloadProver, VidScheme};
+    use serde_json::json;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+    use crate::{
+        ns_payload::{
+            num_txs_range, parse_tx_table_entries, tx_payload_range, tx_table_entries_range,
+        },
+        vid::Advz,
+        NamespaceId, NsIndex, NsTable, Srs, TxIndex, VidCommitment,
+    };
+
+    const NUM_STORAGE_NODES: u32 = 4;
+
+    fn test_srs() -> Srs {
+        UnivariateKzgPCS::<Bn254>::gen_srs_for_testing(&mut ark_std::test_rng(), 64).unwrap()
+    }
+
+    /// A block payload with the given transactions in each namespace, and its VID data.
+    struct TestBlock {
+        namespaces: Vec<(u64, Vec<Vec<u8>>)>,
+        payload: Vec<u8>,
+        ns_table: NsTable,
+        ns_ranges: Vec<Range<usize>>,
+        vid: Advz,
+        commit: VidCommitment,
+        common: VidCommon,
+    }
+
+    impl TestBlock {
+        fn new(srs: &Srs, namespaces: Vec<(u64, Vec<Vec<u8>>)>) -> Self {
+            let mut ns_table = (namespaces.len() as u32).to_le_bytes().to_vec();
+            let mut payload = vec![];
+            let mut ns_ranges = vec![];
+            for (ns_id, txs) in &namespaces {
+                let start = payload.len();
+                payload.extend((txs.len() as u32).to_le_bytes());
+                let mut offset = 0;
+                for tx in txs {
+                    offset += tx.len();
+                    payload.extend((offset as u32).to_le_bytes());
+                }
+                for tx in txs {
+                    payload.extend(tx);
+                }
+                ns_table.extend((*ns_id as u32).to_le_bytes());
+                ns_table.extend((payload.len() as u32).to_le_bytes());
+                ns_ranges.push(start..payload.len());
+            }
+
+            let mut vid = Advz::new(NUM_STORAGE_NODES, NUM_STORAGE_NODES, srs).unwrap();
+            let disperse = vid.disperse(&payload).unwrap();
+            Self {
+                namespaces,
+                payload,
+                ns_table: NsTable::from_bytes(&ns_table),
+                ns_ranges,
+                vid,
+                commit: disperse.commit,
+                common: disperse.common,
+            }
+        }
+
+        fn header(&self) -> String {
+            json!({
+                "version": { "Version": { "major": 0, "minor": 3 } },
+                "fields": {
+                    "height": 1,
+                    "timestamp": 0,
+                    "payload_commitment": self.commit,
+                    "ns_table": self.ns_table,
+                },
+            })
+            .to_string()
+        }
+
+        fn common(&self) -> String {
+            serde_json::to_string(&self.common).unwrap()
+        }
+
+        fn ns_proof(&self, index: usize) -> NsProof {
+            let range = self.ns_ranges[index].clone();
+            NsProof {
+                ns_index: NsIndex(index),
+                ns_payload: self.payload[range.clone()].to_vec(),
+                ns_proof: (!range.is_empty())
+                    .then(|| self.vid.payload_proof(&self.payload, range).unwrap()),
+            }
+        }
+
+        fn tx_proof(&self, ns: usize, tx: usize) -> (Transaction, TxProof) {
+            let ns_range = self.ns_ranges[ns].clone();
+            let ns_payload = &self.payload[ns_range.clone()];
+            let block_range =
+                |range: Range<usize>| range.start + ns_range.start..range.end + ns_range.start;
+            let num_txs = self.namespaces[ns].1.len();
+            let entries_range = tx_table_entries_range(tx);
+            let entries = &ns_payload[entries_range.clone()];
+            let tx_range = tx_payload_range(
+                num_txs,
+                parse_tx_table_entries(entries).unwrap(),
+                ns_payload.len(),
+            );
+
+            let proof = TxProof {
+                tx_index: TxIndex(tx),
+                payload_num_txs: (num_txs as u32).to_le_bytes(),
+                payload_proof_num_txs: self
+                    .vid
+                    .payload_proof(&self.payload, block_range(num_txs_range(ns_payload.len())))
+                    .unwrap(),
+                payload_tx_table_entries: entries.to_vec(),
+                payload_proof_tx_table_entries: self
+                    .vid
+                    .payload_proof(&self.payload, block_range(entries_range))
+                    .unwrap(),
+                payload_proof_tx: (!tx_range.is_empty()).then(|| {
+                    self.vid
+                        .payload_proof(&self.payload, block_range(tx_range.clone()))
+                        .unwrap()
+                }),
+            };
+            let transaction = Transaction::new(
+                NamespaceId(self.namespaces[ns].0),
+                ns_payload[tx_range].to_vec(),
+            );
+            (transaction, proof)
+        }
+    }
+
+    fn test_block(srs: &Srs) -> TestBlock {
+        TestBlock::new(
+            srs,
+            vec![
+                (1, vec![vec![1; 5], vec![2; 8], vec![3; 8]]),
+                (7, vec![vec![4; 7], vec![], vec![5; 11]]),
+                (42, vec![vec![6; 10]]),
+            ],
+        )
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_verify_namespace() {
+        let srs = test_srs();
+        let verifier = Verifier::new(srs.clone());
+        let block = test_block(&srs);
+
+        for (index, (ns_id, txs)) in block.namespaces.iter().enumerate() {
+            let proof = serde_json::to_string(&block.ns_proof(index)).unwrap();
+            let transactions = verify_namespace(
+                &verifier,
+                &block.header(),
+                NamespaceId(*ns_id),
+                &proof,
+                &block.common(),
+            )
+            .unwrap();
+            let expected: Vec<_> = txs
+                .iter()
+                .map(|tx| Transaction::new(NamespaceId(*ns_id), tx.clone()))
+                .collect();
+            assert_eq!(transactions, expected);
+        }
+
+        // A proof for one namespace does not verify as another.
+        let mut proof = block.ns_proof(0);
+        proof.ns_index = NsIndex(1);
+        let proof = serde_json::to_string(&proof).unwrap();
+        assert!(matches!(
+            verify_namespace(
+                &verifier,
+                &block.header(),
+                NamespaceId(7),
+                &proof,
+                &block.common()
+            ),
+            Err(VerifyError::VerificationFailed)
+        ));
+
+        // A valid proof of a namespace other than the one requested is rejected.
+        let proof = serde_json::to_string(&block.ns_proof(1)).unwrap();
+        assert!(matches!(
+            verify_namespace(
+                &verifier,
+                &block.header(),
+                NamespaceId(1),
+                &proof,
+                &block.common()
+            ),
+            Err(VerifyError::WrongNamespace {
+                requested: 1,
+                proven: 7
+            })
+        ));
+
+        // A proof does not verify against a different block.
+        let other = TestBlock::new(&srs, vec![(1, vec![vec![9; 21]])]);
+        let proof = serde_json::to_string(&block.ns_proof(0)).unwrap();
+        assert!(matches!(
+            verify_namespace(
+                &verifier,
+                &other.header(),
+                NamespaceId(1),
+                &proof,
+                &other.common()
+            ),
+            Err(VerifyError::VerificationFailed)
+        ));
+
+        // Malformed input is reported as such.
+        assert!(matches!(
+            verify_namespace(&verifier, "{}", NamespaceId(1), &proof, &block.common()),
+            Err(VerifyError::Malformed { what: "header", .. })
+        ));
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_verify_transaction() {
+        let srs = test_srs();
+        let verifier = Verifier::new(srs.clone());
+        let block = test_block(&srs);
+
+        for (ns, (_, txs)) in block.namespaces.iter().enumerate() {
+            for tx in 0..txs.len() {
+                let (transaction, proof) = block.tx_proof(ns, tx);
+                assert_eq!(transaction.payload(), &txs[tx]);
+                verify_transaction(
+                    &verifier,
+                    &block.header(),
+                    &serde_json::to_string(&transaction).unwrap(),
+                    &serde_json::to_string(&proof).unwrap(),
+                    &block.common(),
+                )
+                .unwrap();
+            }
+        }
+
+        // The proof does not verify for a different transaction.
+        let (_, proof) = block.tx_proof(0, 1);
+        let forged = Transaction::new(NamespaceId(1), vec![0; 8]);
+        assert!(matches!(
+            verify_transaction(
+                &verifier,
+                &block.header(),
+                &serde_json::to_string(&forged).unwrap(),
+                &serde_json::to_string(&proof).unwrap(),
+                &block.common(),
+            ),
+            Err(VerifyError::VerificationFailed)
+        ));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    #[wasm_bindgen_test]
+    fn test_js_verifier() {
+        let srs = test_srs();
+        let mut srs_bytes = vec![];
+        srs.serialize_uncompressed(&mut srs_bytes).unwrap();
+        let verifier = JsVerifier::new(&srs_bytes).unwrap();
+        let block = test_block(&srs);
+
+        let proof = serde_json::to_string(&block.ns_proof(2)).unwrap();
+        let transactions: Vec<Transaction> = serde_json::from_str(
+            &verifier
+                .verify_namespace(&block.header(), 42, &proof, &block.common())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            transactions,
+            vec![Transaction::new(NamespaceId(42), vec![6; 10])]
+        );
+
+        let (transaction, proof) = block.tx_proof(1, 2);
+        verifier
+            .verify_transaction(
+                &block.header(),
+                &serde_json::to_string(&transaction).unwrap(),
+                &serde_json::to_string(&proof).unwrap(),
+                &block.common(),
+            )
+            .unwrap();
+    }
+}
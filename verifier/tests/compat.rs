@@ -0,0 +1,82 @@
+//! Check that the verifier agrees with `espresso-types` on the serialization and verification of
+//! proofs.
+#![cfg(not(target_arch = "wasm32"))]
+
+use espresso_types::{Iter, NamespaceId, NsProof, Payload, Transaction, TxProof};
+use espresso_verifier::{Srs, Verifier};
+use hotshot_types::{
+    traits::{BlockPayload, EncodeBytes},
+    vid::vid_scheme,
+};
+use jf_vid::VidScheme;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The SRS `hotshot_types::vid` uses, truncated to a degree large enough for the test payload.
+fn aztec_srs() -> Srs {
+    let srs = ark_srs::kzg10::aztec20::setup(1 << 10).expect("Aztec SRS failed to load");
+    Srs {
+        powers_of_g: srs.powers_of_g,
+        h: srs.h,
+        beta_h: srs.beta_h,
+        powers_of_h: vec![srs.h, srs.beta_h],
+    }
+}
+
+/// Convert between the `espresso-types` and verifier versions of a type via their shared JSON
+/// serialization, checking that it round-trips.
+fn convert<T: Serialize, U: Serialize + DeserializeOwned>(value: &T) -> U {
+    let json = serde_json::to_value(value).unwrap();
+    let converted: U = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&converted).unwrap(), json);
+    converted
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_compat() {
+    let transactions = vec![
+        Transaction::new(NamespaceId::from(1u32), vec![1; 5]),
+        Transaction::new(NamespaceId::from(1u32), vec![]),
+        Transaction::new(NamespaceId::from(7u32), vec![2; 11]),
+        Transaction::new(NamespaceId::from(42u32), vec![3; 8]),
+    ];
+    let payload =
+        Payload::from_transactions(transactions, &Default::default(), &Default::default())
+            .await
+            .unwrap()
+            .0;
+    let ns_table = payload.ns_table();
+    let vid = vid_scheme(10).disperse(payload.encode()).unwrap();
+
+    let verifier = Verifier::new(aztec_srs());
+    let v_ns_table: espresso_verifier::NsTable = convert(ns_table);
+    let v_commit: espresso_verifier::VidCommitment = convert(&vid.commit);
+    let v_common: espresso_verifier::VidCommon = convert(&vid.common);
+    assert!(v_ns_table.is_valid());
+
+    for index in ns_table.iter() {
+        let proof = NsProof::new(&payload, &index, &vid.common).unwrap();
+        let (expected, _) = proof.verify(ns_table, &vid.commit, &vid.common).unwrap();
+
+        let v_proof: espresso_verifier::NsProof = convert(&proof);
+        let (transactions, _) = v_proof
+            .verify(&verifier, &v_ns_table, &v_commit, &v_common)
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(transactions).unwrap(),
+            serde_json::to_value(expected).unwrap()
+        );
+    }
+
+    for index in Iter::new(&payload) {
+        let (transaction, proof) = TxProof::new(&index, &payload, &vid.common).unwrap();
+        assert!(proof
+            .verify(ns_table, &transaction, &vid.commit, &vid.common)
+            .unwrap());
+
+        let v_proof: espresso_verifier::TxProof = convert(&proof);
+        let v_transaction: espresso_verifier::Transaction = convert(&transaction);
+        assert!(v_proof
+            .verify(&verifier, &v_ns_table, &v_transaction, &v_commit, &v_common)
+            .unwrap());
+    }
+}